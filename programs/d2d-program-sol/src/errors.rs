@@ -66,4 +66,108 @@ pub enum ErrorCode {
     InvalidAccountOwner,
     #[msg("Pool utilization would exceed 80% - cannot unstake this amount")]
     PoolUtilizationTooHigh,
+    #[msg("Deployment funds have already been dispatched for this request")]
+    DeploymentAlreadyFunded,
+    #[msg("Pool is still undercollateralized - shortfall must be resolved first")]
+    PoolStillUndercollateralized,
+    #[msg("Too many accounts in batch")]
+    BatchTooLarge,
+    #[msg("Proposal value is out of the allowed range")]
+    InvalidProposalValue,
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    #[msg("Voting period has not ended yet")]
+    VotingStillOpen,
+    #[msg("Stake was added after the proposal was created")]
+    StakeAfterProposal,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal did not reach quorum and majority")]
+    ProposalNotPassed,
+    #[msg("Invalid admin key")]
+    InvalidAdmin,
+    #[msg("No pending admin transfer")]
+    NoPendingAdmin,
+    #[msg("Deposit would exceed the per-backer deposit cap")]
+    DepositCapExceeded,
+    #[msg("Deposit is below the minimum deposit")]
+    DepositTooSmall,
+    #[msg("Deposit would overflow reward_debt at the current reward_per_share - deposit a smaller amount")]
+    RewardDebtOverflow,
+    #[msg("Deployments are paused")]
+    DeploymentsPaused,
+    #[msg("Deployments are not paused")]
+    DeploymentsNotPaused,
+    #[msg("Claim amount exceeds claimable rewards")]
+    ClaimExceedsClaimable,
+    #[msg("Reward pool is isolated from deployment funding")]
+    RewardPoolIsolated,
+    #[msg("No monthly fee to accrue yet")]
+    NoFeeToAccrue,
+    #[msg("Emergency exit is only available while withdrawals are paused")]
+    ProgramNotPaused,
+    #[msg("Claim amount is below the minimum claim amount")]
+    ClaimBelowThreshold,
+    #[msg("Deployment funds have not been dispatched for this request")]
+    DeploymentNotFunded,
+    #[msg("Deployment funds are in flight for this request; wait for confirmation")]
+    DeploymentInProgress,
+    #[msg("Daily deploy limit exceeded for this developer")]
+    DailyDeployLimitExceeded,
+    #[msg("Token account mint does not match the token treasury mint")]
+    InvalidMint,
+    #[msg("No SPL token deposits to distribute rewards to")]
+    NoTokenDeposits,
+    #[msg("Subscription months exceed the maximum prepay period")]
+    SubscriptionTooLong,
+    #[msg("Subscription grace period has elapsed")]
+    GracePeriodElapsed,
+    #[msg("Tracked pool balance diverges from PDA lamports beyond tolerance")]
+    PoolBalanceMismatch,
+    #[msg("Deployment funds are out on loan; wait until total_borrowed is zero")]
+    BorrowedFundsOutstanding,
+    #[msg("Subscription is not within the renewal window yet")]
+    RenewalNotDue,
+    #[msg("Prepaid renewal balance does not cover the monthly fee")]
+    InsufficientRenewalBalance,
+    #[msg("Deploy request still holds the developer's renewal balance")]
+    RenewalBalanceOutstanding,
+    #[msg("Deposit would push total_deposited past the pool cap")]
+    PoolCapReached,
+    #[msg("Developer is blacklisted")]
+    DeveloperBlacklisted,
+    #[msg("Reason string exceeds the maximum length")]
+    ReasonTooLong,
+    #[msg("Suspended request lapsed past the reinstatement window; redeploy instead")]
+    LapseWindowElapsed,
+    #[msg("Treasury PDA lamports (plus funds out on deployment) do not cover total_deposited")]
+    TreasuryInsolvent,
+    #[msg("Reward Pool PDA lamports do not cover rewards owed, prepaid escrow and dust")]
+    RewardPoolInsolvent,
+    #[msg("Platform Pool PDA lamports do not cover platform_pool_balance")]
+    PlatformPoolInsolvent,
+    #[msg("Treasury Pool is already at the current layout version")]
+    TreasuryPoolAlreadyMigrated,
+    #[msg("Treasury Pool layout is older than this program expects; run migrate_treasury_pool")]
+    StaleLayout,
+    #[msg("Deploy request is not abandoned: subscription is still within the grace / abandonment window")]
+    RequestNotAbandoned,
+    #[msg("Deposit receipt accounts are required to burn the receipt on a full withdrawal")]
+    DepositReceiptRequired,
+    #[msg("New developer must differ from the current developer")]
+    SameDeveloper,
+    #[msg("Claim cooldown active: rewards cannot be claimed this soon after a deposit")]
+    ClaimCooldownActive,
+    #[msg("Reward Pool PDA already covers rewards owed, prepaid escrow and dust; nothing to backstop")]
+    RewardPoolSolvent,
+    #[msg("Withdrawal would take the Reward Pool below the rewards owed to backers (total_claimable)")]
+    RewardsOwedToBackers,
+    #[msg("Position still holds principal, claimable rewards or a deposit receipt")]
+    StakeNotEmpty,
+    #[msg("deployment_cost exceeds max_deployment_cost or max_deployment_bps of liquid_balance")]
+    DeploymentCostTooLarge,
+    #[msg("Treasury Pool still holds backer principal or rewards; pass force to close anyway")]
+    PoolHoldsBackerFunds,
+    #[msg("Fee with this fee_id has already been credited")]
+    DuplicateFee,
 }
@@ -1,3 +1,4 @@
+use crate::states::ProposalParam;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -20,6 +21,15 @@ pub struct SolUnstaked {
     pub lender: Pubkey,
     pub amount: u64,
     pub remaining_staked: u64,
+    pub haircut: u64, // Principal forfeited in pro-rata mode (0 when fully collateralized)
+    pub penalty: u64, // Early-unstake penalty routed to the reward pool
+}
+
+#[event]
+pub struct StakeClosed {
+    pub lender: Pubkey,
+    pub recovered_rent: u64, // Rent returned to the backer
+    pub closed_at: i64,
 }
 
 #[event]
@@ -38,6 +48,7 @@ pub struct DeployRequested {
     pub monthly_fee: u64,
     pub initial_months: u32,
     pub total_payment: u64,
+    pub requested_at: i64, // Same clock reading as DeploymentFundsRequested.requested_at
 }
 
 #[event]
@@ -51,6 +62,9 @@ pub struct DeploymentFundsRequested {
     pub deployment_cost: u64,
     pub total_payment: u64,
     pub requested_at: i64,
+    pub reward_split_bps: u64,     // Effective share of the up-front fee sent to the Reward Pool
+    pub reward_fee_amount: u64,    // Credited to the Reward Pool at request time (escrowed months excluded)
+    pub platform_fee_amount: u64,  // Up-front fee credited to the Platform Pool
 }
 
 #[event]
@@ -82,6 +96,10 @@ pub struct DeploymentConfirmed {
     pub deployed_program_id: Pubkey,
     pub deployment_cost: u64,
     pub recovered_funds: u64,
+    pub recovered_to_liquid: u64,
+    pub recovered_to_platform: u64,
+    pub deployment_loss: u64, // deployment_cost - recovered_funds
+    pub deploy_latency_secs: i64, // deployed_at (= confirmed_at) - created_at
     pub confirmed_at: i64,
 }
 
@@ -91,6 +109,7 @@ pub struct DeploymentFailed {
     pub developer: Pubkey,
     pub failure_reason: String,
     pub refund_amount: u64,
+    pub slashed_amount: u64,
     pub deployment_cost_returned: u64,
     pub failed_at: i64,
 }
@@ -100,8 +119,19 @@ pub struct SubscriptionPaid {
     pub request_id: [u8; 32],
     pub developer: Pubkey,
     pub months: u32,
-    pub payment_amount: u64,
+    pub payment_amount: u64, // After the prepay discount
+    pub subscription_valid_until: i64,
+}
+
+#[event]
+pub struct SubscriptionCredited {
+    pub request_id: [u8; 32],
+    pub developer: Pubkey,
+    pub admin: Pubkey,
+    pub months: u32,           // Free months granted by this call (no payment)
+    pub credited_months: u32,  // Running total of free months on the request
     pub subscription_valid_until: i64,
+    pub credited_at: i64,
 }
 
 #[event]
@@ -121,7 +151,8 @@ pub struct ApyUpdated {
 
 #[event]
 pub struct ProgramsSuspended {
-    pub suspended_count: u32,
+    pub expired_count: u32,   // Moved to SubscriptionExpired (within grace period)
+    pub suspended_count: u32, // Moved to Suspended (grace period elapsed)
     pub suspended_at: i64,
 }
 
@@ -137,6 +168,7 @@ pub struct ProgramClosed {
     pub program_id: Pubkey,
     pub developer: Pubkey,
     pub recovered_lamports: u64,
+    pub refunded_amount: u64, // monthly_fee * unused months returned to the developer
     pub closed_at: i64,
 }
 
@@ -156,6 +188,23 @@ pub struct AdminMovedToRewardPool {
     pub moved_at: i64,
 }
 
+#[event]
+pub struct RewardPoolBackstopped {
+    pub admin: Pubkey,
+    pub amount: u64,                // Lamports moved Platform Pool -> Reward Pool
+    pub remaining_shortfall: u64,   // Shortfall left if the Platform Pool could not cover it all
+    pub platform_pool_balance: u64, // Tracked platform balance after the backstop
+    pub backstopped_at: i64,
+}
+
+#[event]
+pub struct DustSwept {
+    pub admin: Pubkey,
+    pub amount: u64,
+    pub platform_pool_balance: u64,
+    pub swept_at: i64,
+}
+
 #[event]
 pub struct DepositMade {
     pub backer: Pubkey,
@@ -170,6 +219,7 @@ pub struct DepositMade {
 
 #[event]
 pub struct RewardCredited {
+    pub fee_id: [u8; 32], // credit_fee_to_pool fee_id (zeroed for sponsor_reward_pool)
     pub fee_reward: u64,
     pub fee_platform: u64,
     pub reward_per_share: u128,
@@ -183,9 +233,34 @@ pub struct Claimed {
     pub amount: u64,
     pub claimed_total: u64,
     pub reward_per_share: u128,
+    pub claim_authority: Option<Pubkey>, // Delegate that signed the claim (None = backer)
+    pub destination: Pubkey,
     pub claimed_at: i64,
 }
 
+#[event]
+pub struct ClaimAuthorityUpdated {
+    pub backer: Pubkey,
+    pub old_claim_authority: Option<Pubkey>,
+    pub new_claim_authority: Option<Pubkey>,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DepositReceiptMinted {
+    pub backer: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub receipt_token_account: Pubkey,
+    pub minted_at: i64,
+}
+
+#[event]
+pub struct DepositReceiptBurned {
+    pub backer: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub burned_at: i64,
+}
+
 #[event]
 pub struct WithdrawRequested {
     pub backer: Pubkey,
@@ -193,3 +268,494 @@ pub struct WithdrawRequested {
     pub request_id: [u8; 32],
     pub requested_at: i64,
 }
+
+#[event]
+pub struct DeployRequestCancelled {
+    pub request_id: [u8; 32],
+    pub developer: Pubkey,
+    pub refund_amount: u64,
+    pub months_refunded: u64,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct Undercollateralized {
+    pub assets: u64,
+    pub obligations: u64,
+    pub shortfall: u64,
+    pub flagged_by: Pubkey,
+    pub flagged_at: i64,
+}
+
+#[event]
+pub struct UndercollateralizationCleared {
+    pub admin: Pubkey,
+    pub assets: u64,
+    pub obligations: u64,
+    pub cleared_at: i64,
+}
+
+#[event]
+pub struct DeployRequestClosed {
+    pub request_id: [u8; 32],
+    pub developer: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub recovered_rent: u64,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub param: ProposalParam,
+    pub new_value: u64,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub param: ProposalParam,
+    pub old_value: u64,
+    pub new_value: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled_admin: Pubkey,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub transferred_at: i64,
+}
+
+#[event]
+pub struct EmergencyKeysRotated {
+    pub authority: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub old_reward_admin: Pubkey,
+    pub new_reward_admin: Pubkey,
+    pub rotated_at: i64,
+}
+
+#[event]
+pub struct RewardAdminUpdated {
+    pub old_reward_admin: Pubkey,
+    pub new_reward_admin: Pubkey,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct RecoveryAuthorityUpdated {
+    pub old_authority: Option<Pubkey>,
+    pub new_authority: Option<Pubkey>,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DepositCapUpdated {
+    pub old_cap: u64,
+    pub new_cap: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct MinDepositUpdated {
+    pub old_min_deposit: u64,
+    pub new_min_deposit: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct PenaltyBpsUpdated {
+    pub old_penalty_bps: u64,
+    pub new_penalty_bps: u64,
+    pub updated_at: i64,
+}
+
+/// Forward-looking estimate, not a guaranteed payout
+#[event]
+pub struct ProjectedRewards {
+    pub lender: Pubkey,
+    pub horizon_seconds: i64,
+    pub projected_amount: u64,
+    pub window_fees: u64,
+    pub window_seconds: i64,
+}
+
+#[event]
+pub struct DeploymentsPauseToggled {
+    pub paused: bool,
+    pub toggled_at: i64,
+}
+
+#[event]
+pub struct HaltRefund {
+    pub request_id: [u8; 32],
+    pub developer: Pubkey,
+    pub refund_amount: u64,  // service_fee + monthly_fee * months_paid
+    pub months_refunded: u64,
+    pub refunded_at: i64,
+}
+
+#[event]
+pub struct RewardPoolIsolationUpdated {
+    pub isolated: bool,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct RewardsCompounded {
+    pub backer: Pubkey,
+    pub amount: u64,           // Rewards moved into principal
+    pub deposited_amount: u64, // Position after compounding
+    pub reward_per_share: u128,
+    pub compounded_at: i64,
+}
+
+#[event]
+pub struct BackerDepositMigrated {
+    pub backer: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub layout_version: u8,
+    pub migrated_at: i64,
+}
+
+#[event]
+pub struct TreasuryPoolMigrated {
+    pub old_size: u64,
+    pub new_size: u64,
+    pub old_layout_version: u8,
+    pub layout_version: u8,
+    pub total_deposited: u64, // Preserved across the migration
+    pub migrated_at: i64,
+}
+
+#[event]
+pub struct RewardsClaimedBatch {
+    pub backers_paid: u32,
+    pub backers_skipped: u32, // Invalid accounts or not covered by the reward pool
+    pub total_paid: u64,
+    pub reward_per_share: u128,
+    pub claimed_at: i64,
+}
+
+#[event]
+pub struct MonthlyFeeAccrued {
+    pub request_id: [u8; 32],
+    pub months: u64,
+    pub fee_due: u64,      // borrowed_amount * 1% * months
+    pub fee_charged: u64,  // Credited to backers (less than fee_due if escrow ran out)
+    pub prepaid_remaining: u64,
+    pub subscription_expired: bool,
+    pub accrued_at: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawFeeUpdated {
+    pub old_fee_bps: u64,
+    pub new_fee_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct EmergencyUnstaked {
+    pub lender: Pubkey,
+    pub amount: u64,           // Paid to the lender (after haircut and fee)
+    pub remaining_staked: u64,
+    pub haircut: u64,          // Principal forfeited in pro-rata mode
+    pub fee: u64,              // Emergency withdrawal fee routed to the insurance pool
+    pub insurance_pool_balance: u64,
+}
+
+/// Quote for a full exit (unstake_sol of the whole deposit + claim_rewards)
+#[event]
+pub struct ExitPreview {
+    pub lender: Pubkey,
+    pub principal: u64, // deposited_amount
+    pub haircut: u64,   // Principal forfeited in pro-rata mode
+    pub rewards: u64,   // Claimable rewards
+    pub penalty: u64,   // Early-unstake penalty if still locked
+    pub net: u64,       // principal - haircut - penalty + rewards
+}
+
+#[event]
+pub struct FeeRatesUpdated {
+    pub old_reward_fee_bps: u64,
+    pub new_reward_fee_bps: u64,
+    pub old_platform_fee_bps: u64,
+    pub new_platform_fee_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub old_paused_ops: u8,
+    pub new_paused_ops: u8,
+    pub emergency_pause: bool, // true when every bit is set
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct MinClaimUpdated {
+    pub old_min_claim_amount: u64,
+    pub new_min_claim_amount: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct MonthlyFeeBpsUpdated {
+    pub old_monthly_fee_bps: u64,
+    pub new_monthly_fee_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DeployLimitsUpdated {
+    pub old_max_concurrent_sessions: u32,
+    pub new_max_concurrent_sessions: u32,
+    pub old_max_daily_deploys: u32,
+    pub new_max_daily_deploys: u32,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct OutflowBreakerUpdated {
+    pub old_max_outflow_per_window: u64,
+    pub new_max_outflow_per_window: u64,
+    pub old_outflow_window_secs: i64,
+    pub new_outflow_window_secs: i64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DeploymentCostCapUpdated {
+    pub old_max_deployment_cost: u64,
+    pub new_max_deployment_cost: u64,
+    pub old_max_deployment_bps: u64,
+    pub new_max_deployment_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct TokenTreasuryInitialized {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub initialized_at: i64,
+}
+
+#[event]
+pub struct SplRewardsFunded {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub reward_per_share: u128,
+    pub funded_at: i64,
+}
+
+#[event]
+pub struct SplStaked {
+    pub backer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct SplUnstaked {
+    pub backer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub remaining_staked: u64,
+}
+
+#[event]
+pub struct SplRewardsClaimed {
+    pub backer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct GracePeriodUpdated {
+    pub old_grace_period_secs: i64,
+    pub new_grace_period_secs: i64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct MaxLapseUpdated {
+    pub old_max_lapse_secs: i64,
+    pub new_max_lapse_secs: i64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct EphemeralRecoveryTimeoutUpdated {
+    pub old_timeout_secs: i64,
+    pub new_timeout_secs: i64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct RewardHistoryInitialized {
+    pub reward_per_share: u128,
+    pub total_deposited: u64,
+    pub initialized_at: i64,
+}
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_rate: u64,
+    pub max_concurrent_per_user: u32,
+    pub initialized_at: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub old_fee_rate: u64,
+    pub new_fee_rate: u64,
+    pub old_max_concurrent_per_user: u32,
+    pub new_max_concurrent_per_user: u32,
+    pub is_paused: bool,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct RecoveryPlatformBpsUpdated {
+    pub old_recovery_platform_bps: u64,
+    pub new_recovery_platform_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct PoolsReconciled {
+    pub liquid_before: u64,
+    pub liquid_after: u64,
+    pub reward_pool_before: u64,
+    pub reward_pool_after: u64,
+    pub platform_pool_before: u64,
+    pub platform_pool_after: u64,
+    pub forced: bool,
+    pub reconciled_at: i64,
+}
+
+#[event]
+pub struct PrepaidToppedUp {
+    pub request_id: [u8; 32],
+    pub developer: Pubkey,
+    pub amount: u64,
+    pub renewal_balance: u64,
+}
+
+#[event]
+pub struct LiquidBalanceSynced {
+    pub old_liquid_balance: u64,
+    pub new_liquid_balance: u64,
+    pub account_lamports: u64,
+    pub rent_exemption: u64,
+    pub synced_at: i64,
+}
+
+#[event]
+pub struct DeployRequestMigrated {
+    pub request_id: [u8; 32],
+    pub developer: Pubkey,
+    pub legacy_address: Pubkey,
+    pub new_address: Pubkey,
+    pub migrated_at: i64,
+}
+
+#[event]
+pub struct PoolCapUpdated {
+    pub old_cap: u64,
+    pub new_cap: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DeveloperBlacklistUpdated {
+    pub developer: Pubkey,
+    pub blacklisted: bool,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct FailureSlashUpdated {
+    pub old_threshold: u32,
+    pub new_threshold: u32,
+    pub old_slash_bps: u64,
+    pub new_slash_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct UserStatsMigrated {
+    pub developer: Pubkey,
+    pub old_size: u64,
+    pub new_size: u64,
+    pub migrated_at: i64,
+}
+
+#[event]
+pub struct DeployOwnershipTransferred {
+    pub request_id: [u8; 32],
+    pub old_developer: Pubkey,
+    pub new_developer: Pubkey,
+    pub original_developer: Pubkey, // PDA seed developer (unchanged by transfers)
+    pub transferred_at: i64,
+}
+
+#[event]
+pub struct RewardSplitBoundsUpdated {
+    pub old_min_reward_split_bps: u64,
+    pub new_min_reward_split_bps: u64,
+    pub old_max_reward_split_bps: u64,
+    pub new_max_reward_split_bps: u64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct ClaimCooldownUpdated {
+    pub old_claim_cooldown_secs: i64,
+    pub new_claim_cooldown_secs: i64,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DailyStatsReset {
+    pub user: Pubkey,
+    pub previous_daily_deploys: u32, // daily_deploys discarded by the reset
+    pub last_reset: i64,             // New start of the daily window
+    pub total_deploys: u64,          // Lifetime deploys (never reset)
+}
@@ -4,22 +4,25 @@ use crate::states::TreasuryPool;
 use anchor_lang::prelude::*;
 
 /// Admin withdraw funds from Admin Pool
-/// 
+///
+/// The Admin Pool is the Platform Pool (ADMIN_POOL_SEED aliases platform_pool), so this debits
+/// platform_pool_balance like withdraw_platform_pool; the deprecated admin_pool_balance is never credited.
 /// Safety: Only admin can withdraw, with event logging for audit
 #[derive(Accounts)]
 pub struct AdminWithdraw<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
-    /// CHECK: Admin Pool PDA (program-owned, holds platform funds)
+    /// CHECK: Admin Pool PDA = Platform Pool PDA (program-owned, holds platform funds)
     #[account(
         mut,
         seeds = [TreasuryPool::ADMIN_POOL_SEED],
-        bump = treasury_pool.admin_pool_bump
+        bump = treasury_pool.platform_pool_bump
     )]
     pub admin_pool: UncheckedAccount<'info>,
     
@@ -40,9 +43,9 @@ pub struct AdminWithdraw<'info> {
 /// 
 /// Flow:
 /// 1. Verify admin authorization
-/// 2. Check Admin Pool has enough lamports
+/// 2. Check platform_pool_balance and the PDA's lamports above rent exemption cover the amount
 /// 3. Transfer from Admin Pool PDA -> destination (via lamport mutation or CPI)
-/// 4. Update admin_pool_balance in state
+/// 4. Decrement platform_pool_balance in state
 pub fn admin_withdraw(
     ctx: Context<AdminWithdraw>,
     amount: u64,
@@ -55,13 +58,18 @@ pub fn admin_withdraw(
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
     require!(amount > 0, ErrorCode::InvalidAmount);
     require!(
-        treasury_pool.admin_pool_balance >= amount as u128,
+        reason.len() <= TreasuryPool::MAX_REASON_LEN,
+        ErrorCode::ReasonTooLong
+    );
+    require!(
+        treasury_pool.platform_pool_balance >= amount,
         ErrorCode::InsufficientTreasuryFunds
     );
 
-    // Check Admin Pool PDA has enough lamports
+    // Check Admin Pool PDA has enough lamports, keeping it rent-exempt
+    let rent_exemption = Rent::get()?.minimum_balance(admin_pool_info.data_len());
     require!(
-        admin_pool_info.lamports() >= amount,
+        admin_pool_info.lamports().saturating_sub(rent_exemption) >= amount,
         ErrorCode::InsufficientTreasuryFunds
     );
 
@@ -79,10 +87,10 @@ pub fn admin_withdraw(
             .ok_or(ErrorCode::CalculationOverflow)?;
     }
 
-    // Update Admin Pool balance in state
-    treasury_pool.admin_pool_balance = treasury_pool
-        .admin_pool_balance
-        .checked_sub(amount as u128)
+    // Update Platform Pool balance in state
+    treasury_pool.platform_pool_balance = treasury_pool
+        .platform_pool_balance
+        .checked_sub(amount)
         .ok_or(ErrorCode::CalculationOverflow)?;
 
     emit!(AdminWithdrew {
@@ -1,27 +1,22 @@
 use crate::errors::ErrorCode;
-use crate::events::AdminWithdrew;
+use crate::events::{AdminWithdrew, EmergencyPauseToggled};
 use crate::states::TreasuryPool;
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
-/// Authorized admin for withdrawing excess rewards from Reward Pool
-/// This admin can only withdraw the excess (surplus) after all backers' claimable rewards are accounted for
-const AUTHORIZED_REWARD_ADMIN: Pubkey = anchor_lang::solana_program::pubkey!("A1dVA8adW1XXgcVmLCtbrvbVEVA1n3Q7kNPaTZVonjpq");
-
 /// Admin withdraw funds from Reward Pool
 /// 
-/// Safety: Only the authorized reward admin can withdraw excess rewards
+/// Safety: Only the authorized reward admin (treasury_pool.effective_reward_admin()) can withdraw excess rewards
 /// This allows withdrawing surplus rewards that exceed total claimable rewards
 /// 
-/// IMPORTANT: This should only be used to withdraw excess/surplus rewards.
-/// The backend must calculate total claimable rewards for all backers first,
-/// and only allow withdrawal of: reward_pool_balance - total_claimable_rewards
+/// Enforced on-chain: at most reward_pool_balance - total_claimable can be withdrawn
 #[derive(Accounts)]
 pub struct AdminWithdrawRewardPool<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
@@ -35,7 +30,7 @@ pub struct AdminWithdrawRewardPool<'info> {
     
     /// CHECK: Only the authorized reward admin can withdraw
     #[account(
-        constraint = admin.key() == AUTHORIZED_REWARD_ADMIN @ ErrorCode::Unauthorized
+        constraint = admin.key() == treasury_pool.effective_reward_admin() @ ErrorCode::Unauthorized
     )]
     pub admin: Signer<'info>,
     
@@ -50,15 +45,14 @@ pub struct AdminWithdrawRewardPool<'info> {
 /// 
 /// Flow:
 /// 1. Verify admin is the authorized reward admin
-/// 2. Check Reward Pool has enough lamports
-/// 3. Transfer from Reward Pool PDA -> destination (via CPI)
-/// 4. Update reward_pool_balance in state
-/// 
-/// IMPORTANT: This instruction only allows withdrawing EXCESS rewards.
-/// The backend must calculate total claimable rewards for all backers first,
-/// and only allow withdrawal of: reward_pool_balance - total_claimable_rewards
+/// 2. Check amount <= reward_pool_balance - total_claimable (RewardsOwedToBackers otherwise)
+/// 3. Check Reward Pool has enough lamports
+/// 4. Count the amount against the outflow circuit breaker (pauses and withdraws nothing if tripped)
+/// 5. Transfer from Reward Pool PDA -> destination (via CPI)
+/// 6. Update reward_pool_balance in state
 /// 
-/// This ensures that backers' claimable rewards are always protected.
+/// total_claimable is maintained as rewards are credited and paid out, so backers'
+/// claimable rewards are always protected, even from a compromised reward admin.
 pub fn admin_withdraw_reward_pool(
     ctx: Context<AdminWithdrawRewardPool>,
     amount: u64,
@@ -70,10 +64,14 @@ pub fn admin_withdraw_reward_pool(
 
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
     require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        reason.len() <= TreasuryPool::MAX_REASON_LEN,
+        ErrorCode::ReasonTooLong
+    );
     
     // Verify admin is the authorized reward admin
     require!(
-        ctx.accounts.admin.key() == AUTHORIZED_REWARD_ADMIN,
+        ctx.accounts.admin.key() == treasury_pool.effective_reward_admin(),
         ErrorCode::Unauthorized
     );
     
@@ -83,13 +81,30 @@ pub fn admin_withdraw_reward_pool(
         ErrorCode::InsufficientTreasuryFunds
     );
 
-    // Check actual Reward Pool PDA has enough lamports
+    // Never dip into rewards owed to backers
+    require!(
+        treasury_pool.excess_rewards() >= amount,
+        ErrorCode::RewardsOwedToBackers
+    );
+
+    // Check actual Reward Pool PDA has enough lamports (escrowed prepaid fees are not withdrawable)
     let actual_balance = reward_pool_info.lamports();
     require!(
-        actual_balance >= amount,
+        actual_balance.saturating_sub(treasury_pool.prepaid_fees_balance) >= amount,
         ErrorCode::InsufficientTreasuryFunds
     );
     
+    // Circuit breaker: a withdrawal that would exceed the outflow limit pauses the program instead
+    let current_time = Clock::get()?.unix_timestamp;
+    if treasury_pool.record_reward_outflow(amount, current_time)? {
+        msg!("[ADMIN_WITHDRAW_REWARD] Reward outflow limit reached - program paused, nothing withdrawn");
+        emit!(EmergencyPauseToggled {
+            paused: true,
+            toggled_at: current_time,
+        });
+        return Ok(());
+    }
+
     msg!("[ADMIN_WITHDRAW_REWARD] Authorized admin {} withdrawing {} lamports", 
          ctx.accounts.admin.key(), amount);
     msg!("[ADMIN_WITHDRAW_REWARD] Reward Pool balance before: {} lamports", 
@@ -122,7 +137,7 @@ pub fn admin_withdraw_reward_pool(
         amount,
         destination: destination_info.key(),
         reason,
-        withdrawn_at: Clock::get()?.unix_timestamp,
+        withdrawn_at: current_time,
     });
 
     Ok(())
@@ -0,0 +1,100 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardPoolBackstopped;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin cover a Reward Pool shortfall out of platform fees
+///
+/// Platform Pool PDA -> Reward Pool PDA, only as much as the Reward Pool is missing
+/// against the RewardPoolInsolvent invariant (rewards owed + prepaid escrow + dust).
+#[derive(Accounts)]
+pub struct BackstopRewardPool<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Platform Pool PDA (program-owned, funds the backstop)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (receives the backstop)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Backstop the reward pool
+///
+/// Flow:
+/// 1. shortfall = reward_pool_balance + prepaid_fees_balance + undistributed_dust - Reward Pool lamports
+///    (RewardPoolSolvent if there is none)
+/// 2. amount = min(shortfall, platform_pool_balance, Platform Pool lamports above rent)
+/// 3. Transfer Platform Pool PDA -> Reward Pool PDA via lamport mutation
+/// 4. platform_pool_balance -= amount
+///
+/// reward_pool_balance and reward_per_share are untouched: the lamports back rewards
+/// already owed rather than crediting new ones. A partial backstop can be topped up later.
+pub fn backstop_reward_pool(ctx: Context<BackstopRewardPool>) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let platform_pool_info = ctx.accounts.platform_pool.to_account_info();
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+
+    let shortfall = treasury_pool.reward_pool_shortfall(reward_pool_info.lamports())?;
+    require!(shortfall > 0, ErrorCode::RewardPoolSolvent);
+
+    // Keep the Platform Pool PDA rent-exempt
+    let rent_exemption = Rent::get()?.minimum_balance(platform_pool_info.data_len());
+    let platform_available = platform_pool_info
+        .lamports()
+        .saturating_sub(rent_exemption)
+        .min(treasury_pool.platform_pool_balance);
+    let amount = shortfall.min(platform_available);
+    require!(amount > 0, ErrorCode::InsufficientTreasuryFunds);
+
+    {
+        let mut platform_pool_lamports = platform_pool_info.try_borrow_mut_lamports()?;
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+
+        **platform_pool_lamports = (**platform_pool_lamports)
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    treasury_pool.platform_pool_balance = treasury_pool
+        .platform_pool_balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    let remaining_shortfall = shortfall - amount;
+    msg!("[BACKSTOP] Moved {} lamports from Platform Pool to Reward Pool", amount);
+    msg!("[BACKSTOP] Remaining shortfall: {} lamports", remaining_shortfall);
+
+    emit!(RewardPoolBackstopped {
+        admin: ctx.accounts.admin.key(),
+        amount,
+        remaining_shortfall,
+        platform_pool_balance: treasury_pool.platform_pool_balance,
+        backstopped_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
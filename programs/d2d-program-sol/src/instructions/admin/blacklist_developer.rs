@@ -0,0 +1,55 @@
+use crate::errors::ErrorCode;
+use crate::events::DeveloperBlacklistUpdated;
+use crate::states::{Blacklist, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Admin blacklist a developer
+/// While the Blacklist PDA exists, create_deploy_request and request_deployment_funds reject the developer
+#[derive(Accounts)]
+pub struct BlacklistDeveloper<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Blacklist::INIT_SPACE,
+        seeds = [Blacklist::PREFIX_SEED, developer.key().as_ref()],
+        bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    /// CHECK: Developer being blacklisted (no signature needed)
+    pub developer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn blacklist_developer(ctx: Context<BlacklistDeveloper>) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    blacklist.developer = ctx.accounts.developer.key();
+    blacklist.blacklisted_at = current_time;
+    blacklist.bump = ctx.bumps.blacklist;
+
+    msg!("[ADMIN] Developer blacklisted: {}", blacklist.developer);
+
+    emit!(DeveloperBlacklistUpdated {
+        developer: blacklist.developer,
+        blacklisted: true,
+        updated_at: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::errors::ErrorCode;
+use crate::events::{EmergencyPauseToggled, RewardsClaimedBatch};
+use crate::states::{BackerDeposit, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Maximum backers paid per call (keeps the instruction under the compute budget)
+pub const MAX_CLAIM_BATCH: usize = 10;
+
+/// Admin crank: pay out claimable rewards for many backers in one instruction
+///
+/// `remaining_accounts` are passed in pairs: [backer_deposit (writable), backer wallet (writable)].
+/// For a deposit with a claim_authority, the wallet must be the claim_authority instead.
+/// Pairs that are not a program-owned BackerDeposit with a matching writable wallet,
+/// or have nothing to claim, are skipped. If the Reward Pool cannot cover a backer's
+/// claim, that backer is skipped and the batch continues. A claim that would exceed the
+/// outflow limit pauses the program and ends the batch; backers already paid keep their rewards.
+#[derive(Accounts)]
+pub struct ClaimRewardsBatch<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (holds reward fees)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+// usize::is_multiple_of is newer than the platform-tools rustc used for SBF builds
+#[allow(clippy::manual_is_multiple_of)]
+pub fn claim_rewards_batch(ctx: Context<ClaimRewardsBatch>) -> Result<()> {
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_CLAIM), ErrorCode::ProgramPaused);
+    require!(
+        ctx.remaining_accounts.len() % 2 == 0,
+        ErrorCode::InvalidAmount
+    );
+    require!(
+        ctx.remaining_accounts.len() / 2 <= MAX_CLAIM_BATCH,
+        ErrorCode::BatchTooLarge
+    );
+
+    let mut backers_paid: u32 = 0;
+    let mut backers_skipped: u32 = 0;
+    let mut total_paid: u64 = 0;
+
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (deposit_info, wallet_info) = (&pair[0], &pair[1]);
+
+        if deposit_info.owner != ctx.program_id || !deposit_info.is_writable || !wallet_info.is_writable {
+            msg!("[CLAIM_BATCH] Skipping {}: not a writable program account", deposit_info.key());
+            backers_skipped += 1;
+            continue;
+        }
+
+        // Discriminator check happens in try_deserialize - skip anything else
+        let mut deposit = match BackerDeposit::try_deserialize(&mut &deposit_info.data.borrow()[..]) {
+            Ok(deposit) => deposit,
+            Err(_) => {
+                msg!("[CLAIM_BATCH] Skipping {}: not a BackerDeposit", deposit_info.key());
+                backers_skipped += 1;
+                continue;
+            }
+        };
+
+        if deposit.claim_signer() != wallet_info.key() {
+            msg!("[CLAIM_BATCH] Skipping {}: wallet does not match backer or claim_authority", deposit_info.key());
+            backers_skipped += 1;
+            continue;
+        }
+
+        if deposit.claim_cooldown_active(treasury_pool.claim_cooldown_secs, current_time) {
+            msg!("[CLAIM_BATCH] Skipping {}: claim cooldown active", deposit.backer);
+            backers_skipped += 1;
+            continue;
+        }
+
+        let claimable = deposit.calculate_claimable_rewards(treasury_pool.reward_per_share)?;
+        // Nothing worth paying (zero or below min_claim_amount)
+        if claimable == 0 || claimable < treasury_pool.min_claim_amount {
+            continue;
+        }
+
+        // Reward pool ran dry for this backer - pay who we can, skip the rest
+        if treasury_pool.reward_pool_balance < claimable || reward_pool_info.lamports() < claimable {
+            msg!("[CLAIM_BATCH] Skipping {}: reward pool cannot cover {} lamports", deposit.backer, claimable);
+            backers_skipped += 1;
+            continue;
+        }
+
+        // Circuit breaker: pause instead of paying, and stop the batch
+        if treasury_pool.record_reward_outflow(claimable, current_time)? {
+            msg!("[CLAIM_BATCH] Reward outflow limit reached - program paused, {} not paid", deposit.backer);
+            emit!(EmergencyPauseToggled {
+                paused: true,
+                toggled_at: current_time,
+            });
+            break;
+        }
+
+        deposit.claimed_total = deposit
+            .claimed_total
+            .checked_add(claimable)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        deposit.pending_rewards = 0;
+        deposit.update_reward_debt(treasury_pool.reward_per_share)?;
+        {
+            let mut data = deposit_info.try_borrow_mut_data()?;
+            deposit.try_serialize(&mut &mut data[..])?;
+        }
+
+        treasury_pool.pay_rewards(claimable)?;
+
+        // Transfer rewards from Reward Pool PDA -> backer via lamport mutation
+        {
+            let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+            let mut wallet_lamports = wallet_info.try_borrow_mut_lamports()?;
+
+            **reward_pool_lamports = (**reward_pool_lamports)
+                .checked_sub(claimable)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+            **wallet_lamports = (**wallet_lamports)
+                .checked_add(claimable)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        }
+
+        msg!("[CLAIM_BATCH] Paid {} lamports to {}", claimable, deposit.backer);
+        backers_paid += 1;
+        total_paid = total_paid
+            .checked_add(claimable)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    emit!(RewardsClaimedBatch {
+        backers_paid,
+        backers_skipped,
+        total_paid,
+        reward_per_share: treasury_pool.reward_per_share,
+        claimed_at: current_time,
+    });
+
+    Ok(())
+}
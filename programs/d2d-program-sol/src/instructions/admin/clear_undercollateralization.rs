@@ -0,0 +1,57 @@
+use crate::errors::ErrorCode;
+use crate::events::UndercollateralizationCleared;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Clear the undercollateralized flag (Admin only)
+/// Only succeeds once the shortfall has actually been resolved
+#[derive(Accounts)]
+pub struct ClearUndercollateralization<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (lamports counted as assets)
+    #[account(
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn clear_undercollateralization(ctx: Context<ClearUndercollateralization>) -> Result<()> {
+    let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
+    let reward_pool_lamports = ctx.accounts.reward_pool.lamports();
+
+    let rent_exemption = Rent::get()?.minimum_balance(treasury_pool_info.data_len());
+    let treasury_available = treasury_pool_info.lamports().saturating_sub(rent_exemption);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let (assets, obligations) =
+        treasury_pool.collateral_position(treasury_available, reward_pool_lamports)?;
+
+    // Re-verify solvency on-chain rather than trusting the admin
+    require!(assets >= obligations, ErrorCode::PoolStillUndercollateralized);
+
+    treasury_pool.undercollateralized = false;
+
+    msg!("[SOLVENCY] Undercollateralized flag cleared by admin {}", ctx.accounts.admin.key());
+
+    emit!(UndercollateralizationCleared {
+        admin: ctx.accounts.admin.key(),
+        assets,
+        obligations,
+        cleared_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use crate::errors::ErrorCode;
+use crate::events::DeployRequestClosed;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Close a deploy request in a terminal state and reclaim its rent
+/// Rent goes to the admin, or to the developer if passed as rent_recipient
+/// (required while renewal_balance is unspent)
+#[derive(Accounts)]
+#[instruction(request_id: [u8; 32])]
+pub struct CloseDeployRequest<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump,
+        close = rent_recipient
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Receives the reclaimed rent - must be the admin or the request's developer
+    #[account(
+        mut,
+        constraint = rent_recipient.key() == admin.key()
+            || rent_recipient.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+pub fn close_deploy_request(ctx: Context<CloseDeployRequest>, request_id: [u8; 32]) -> Result<()> {
+    let deploy_request = &ctx.accounts.deploy_request;
+
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+
+    // Only terminal states - Active / PendingDeployment still hold live state
+    require!(
+        matches!(
+            deploy_request.status,
+            DeployRequestStatus::Failed
+                | DeployRequestStatus::Cancelled
+                | DeployRequestStatus::Closed
+        ),
+        ErrorCode::InvalidRequestStatus
+    );
+
+    // Unspent auto-renewal top-ups can only go back to the developer
+    require!(
+        deploy_request.renewal_balance == 0
+            || ctx.accounts.rent_recipient.key() == deploy_request.developer,
+        ErrorCode::RenewalBalanceOutstanding
+    );
+
+    // Anchor `close` moves every lamport in the account to rent_recipient
+    let recovered_rent = deploy_request.to_account_info().lamports();
+    msg!("[CLOSE_REQUEST] Reclaiming {} lamports of rent", recovered_rent);
+
+    emit!(DeployRequestClosed {
+        request_id,
+        developer: deploy_request.developer,
+        rent_recipient: ctx.accounts.rent_recipient.key(),
+        recovered_rent,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,29 +1,38 @@
 use crate::errors::ErrorCode;
 use crate::events::ProgramClosed;
-use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 /// Close a deployed program and refund recovered lamports to pool
 /// This is called after a program is closed on-chain
+/// The developer gets back the subscription months they paid for but will not use
 #[derive(Accounts)]
 #[instruction(request_id: [u8; 32])]
 pub struct CloseProgramAndRefund<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
 
     #[account(
         mut,
-        seeds = [DeployRequest::PREFIX_SEED, request_id.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), request_id.as_ref()],
         bump = deploy_request.bump,
         constraint = deploy_request.status == DeployRequestStatus::Active @ ErrorCode::InvalidDeploymentStatus
     )]
     pub deploy_request: Account<'info, DeployRequest>,
 
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, deploy_request.developer.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
     #[account(
         mut,
         constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
@@ -34,6 +43,21 @@ pub struct CloseProgramAndRefund<'info> {
     #[account(mut)]
     pub refund_source: UncheckedAccount<'info>,
 
+    /// CHECK: Reward Pool PDA (holds prepaid subscription fees)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Developer wallet receiving the unused subscription refund
+    #[account(
+        mut,
+        constraint = developer_wallet.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub developer_wallet: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -44,9 +68,12 @@ pub fn close_program_and_refund(
 ) -> Result<()> {
     // Get account info before mutable borrow
     let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let developer_wallet_info = ctx.accounts.developer_wallet.to_account_info();
     
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
     let current_time = Clock::get()?.unix_timestamp;
 
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
@@ -65,14 +92,53 @@ pub fn close_program_and_refund(
     // Update treasury pool balance
     treasury_pool.total_staked += recovered_lamports;
 
+    // Refund whole subscription months paid for but not used (nothing if already expired)
+    let unused_months = deploy_request.months_unused(current_time);
+    let monthly_refund = deploy_request
+        .monthly_fee
+        .checked_mul(unused_months)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let refunded_amount = if monthly_refund > 0 {
+        treasury_pool.refund_fees(deploy_request, 0, monthly_refund)?
+    } else {
+        0
+    };
+
+    if refunded_amount > 0 {
+        require!(
+            reward_pool_info.lamports() >= refunded_amount,
+            ErrorCode::InsufficientTreasuryFunds
+        );
+
+        // Refund developer from Reward Pool PDA via direct lamport manipulation
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut developer_lamports = developer_wallet_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(refunded_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **developer_lamports = (**developer_lamports)
+            .checked_add(refunded_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+    msg!("[CLOSE] Unused months: {}, refunded: {} lamports", unused_months, refunded_amount);
+
+    user_stats.active_sessions = user_stats.active_sessions.saturating_sub(1);
+
     // Mark deploy request as closed
     deploy_request.status = DeployRequestStatus::Closed;
+    deploy_request.subscription_paid_until = current_time;
+
+    // Unaccrued prepaid months go to backers
+    let leftover_escrow = deploy_request.prepaid_balance;
+    treasury_pool.accrue_prepaid_fees(deploy_request, leftover_escrow, current_time)?;
 
     emit!(ProgramClosed {
         request_id: deploy_request.request_id,
         program_id: deploy_request.deployed_program_id.unwrap_or_default(),
         developer: deploy_request.developer,
         recovered_lamports,
+        refunded_amount,
         closed_at: current_time,
     });
 
@@ -10,6 +10,9 @@ use anchor_lang::prelude::*;
 /// WARNING: This will transfer all funds to admin and make the account rent-exempt!
 /// Use this when migrating from old struct layout to new layout.
 /// 
+/// A pool on the current layout is deserialized first: if backers still have principal
+/// (total_deposited) or rewards (reward_pool_balance) in it, the close is refused unless force is set.
+/// 
 /// After closing, you can call initialize() again to create a new account with the new layout.
 #[derive(Accounts)]
 pub struct CloseTreasuryPool<'info> {
@@ -32,7 +35,7 @@ pub struct CloseTreasuryPool<'info> {
 
 /// Close treasury pool account by transferring all lamports to admin
 /// This works even if the account has an old struct layout
-pub fn close_treasury_pool(ctx: Context<CloseTreasuryPool>) -> Result<()> {
+pub fn close_treasury_pool(ctx: Context<CloseTreasuryPool>, force: bool) -> Result<()> {
     msg!("[CLOSE] Closing Treasury Pool account");
     msg!("[CLOSE] Admin: {}", ctx.accounts.admin.key());
     msg!("[CLOSE] Treasury Pool PDA: {}", ctx.accounts.treasury_pool.key());
@@ -57,6 +60,19 @@ pub fn close_treasury_pool(ctx: Context<CloseTreasuryPool>) -> Result<()> {
     
     msg!("[CLOSE] PDA verified - bump: {}", bump);
     
+    // Old layouts may not decode; only a current-layout pool can be checked for backer funds
+    let current_pool = TreasuryPool::try_deserialize(&mut &treasury_account.data.borrow()[..])
+        .ok()
+        .filter(|pool| pool.is_current_layout());
+    if let Some(pool) = current_pool {
+        msg!("[CLOSE] total_deposited: {} lamports, reward_pool_balance: {} lamports",
+             pool.total_deposited, pool.reward_pool_balance);
+        if pool.holds_backer_funds() {
+            require!(force, ErrorCode::PoolHoldsBackerFunds);
+            msg!("[CLOSE] WARNING: force closing a pool that still holds backer funds");
+        }
+    }
+    
     // Calculate rent-exempt minimum (account data size + rent)
     // For old layout: ~114 bytes, for new layout: ~278 bytes
     // We'll use a conservative estimate: 300 bytes
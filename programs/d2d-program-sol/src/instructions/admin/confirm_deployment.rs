@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::events::{DeploymentConfirmed, DeploymentFailed};
-use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
@@ -9,17 +9,25 @@ pub struct ConfirmDeployment<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
     #[account(
         mut,
-        seeds = [DeployRequest::PREFIX_SEED, deploy_request.program_hash.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
         bump = deploy_request.bump
     )]
     pub deploy_request: Account<'info, DeployRequest>,
     
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, deploy_request.developer.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+    
     #[account(
         mut,
         constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
@@ -35,8 +43,7 @@ pub struct ConfirmDeployment<'info> {
     pub developer_wallet: UncheckedAccount<'info>,
     
     /// CHECK: Treasury Pool PDA (for recovered funds transfer)
-    /// Note: Recovered funds go back to TreasuryPool, not PlatformPool
-    /// PlatformPool only receives 0.1% developer fees
+    /// Note: Recovered funds go back to TreasuryPool, except the recovery_platform_bps cut
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
@@ -51,6 +58,14 @@ pub struct ConfirmDeployment<'info> {
         bump = treasury_pool.reward_pool_bump
     )]
     pub reward_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Platform Pool PDA (receives recovery_platform_bps of recovered funds on success)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
 }
@@ -67,6 +82,7 @@ pub fn confirm_deployment_success(
     
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request = &mut ctx.accounts.deploy_request;
+    let current_time = Clock::get()?.unix_timestamp;
 
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
     require!(
@@ -84,21 +100,37 @@ pub fn confirm_deployment_success(
         ErrorCode::InvalidRecoveredFunds
     );
 
-    // Verify ephemeral_key matches the one in deploy_request
-    if let Some(expected_ephemeral) = deploy_request.ephemeral_key {
-        require!(
-            ephemeral_key_info.key() == expected_ephemeral,
-            ErrorCode::InvalidEphemeralKey
-        );
-    }
+    // Verify ephemeral_key is set (fund_temporary_wallet ran) and matches the one in deploy_request
+    require!(
+        deploy_request.ephemeral_key.is_some(),
+        ErrorCode::InvalidEphemeralKey
+    );
+    require!(
+        deploy_request.ephemeral_key == Some(ephemeral_key_info.key()),
+        ErrorCode::InvalidEphemeralKey
+    );
+
+    // borrowed_amount is set by fund_temporary_wallet; the monthly borrow fee depends on it
+    require!(
+        deploy_request.borrowed_amount > 0,
+        ErrorCode::DeploymentNotFunded
+    );
+
+    // A successful deployment clears the developer's failure streak
+    ctx.accounts.user_stats.failed_deploys = 0;
 
     // Update deploy request
     deploy_request.status = DeployRequestStatus::Active;
+    deploy_request.funding_locked = false;
     deploy_request.deployed_program_id = Some(deployed_program_id);
-    // borrowed_amount is already set in fund_temporary_wallet
+    deploy_request.deployed_at = Some(current_time);
+
+    // Deployment is no longer in flight
+    treasury_pool.total_borrowed = treasury_pool
+        .total_borrowed
+        .saturating_sub(deploy_request.borrowed_amount);
+    treasury_pool.release_deployment(deploy_request);
 
-    // If there are recovered funds, transfer them back to Platform Pool
-    // Note: Recovered funds go to Platform Pool (not Reward Pool) as they're operational funds
     // Note: Only recover what's actually available in ephemeral key (may have been partially drained)
     let ephemeral_balance = ephemeral_key_info.lamports();
     let actual_recovered = if recovered_funds > 0 && ephemeral_balance > 0 {
@@ -109,46 +141,64 @@ pub fn confirm_deployment_success(
         0
     };
 
-    if actual_recovered > 0 {
+    // Most recovered funds return to liquid_balance; recovery_platform_bps covers platform costs
+    let (recovered_to_liquid, recovered_to_platform) =
+        TreasuryPool::split_recovered(actual_recovered, treasury_pool.recovery_platform_bps)?;
+
+    if recovered_to_liquid > 0 {
         // Transfer recovered funds back to Treasury Pool PDA via CPI System Program transfer
-        // CRITICAL: Recovered funds go to TreasuryPool (liquid_balance), NOT PlatformPool
-        // PlatformPool only receives 0.1% developer fees, not operational funds
         // Note: ephemeral_key must be a signer for this transfer
-        let treasury_seeds = &[
-            TreasuryPool::PREFIX_SEED,
-            &[treasury_pool.bump],
-        ];
-        let signer_seeds = &[&treasury_seeds[..]];
-        
-        // Use CPI System Program transfer from ephemeral_key to treasury_pda
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
-                from: ephemeral_key_info,
+                from: ephemeral_key_info.clone(),
                 to: treasury_pda_info,
             },
         );
-        system_program::transfer(cpi_context, actual_recovered)?;
+        system_program::transfer(cpi_context, recovered_to_liquid)?;
 
         // Update liquid_balance (recovered funds are available for deployments)
-        // This is the correct place for recovered deployment funds
         treasury_pool.liquid_balance = treasury_pool
             .liquid_balance
-            .checked_add(actual_recovered)
+            .checked_add(recovered_to_liquid)
             .ok_or(ErrorCode::CalculationOverflow)?;
-        
-        
-        // NOTE: Do NOT update platform_pool_balance
-        // PlatformPool only receives 0.1% developer fees, not recovered deployment funds
     }
 
+    if recovered_to_platform > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ephemeral_key_info,
+                to: ctx.accounts.platform_pool.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, recovered_to_platform)?;
+
+        treasury_pool.credit_platform_pool(recovered_to_platform as u128)?;
+    }
+
+    // Whatever the deployment consumed is a realized cost to backers; tracked, not deducted
+    // from total_deposited (that must keep matching the sum of backer deposits)
+    let deployment_loss = deploy_request
+        .deployment_cost
+        .saturating_sub(actual_recovered);
+    treasury_pool.total_deployment_losses = treasury_pool
+        .total_deployment_losses
+        .checked_add(deployment_loss)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    msg!("[CONFIRM_SUCCESS] Unrecovered deployment cost: {} lamports (total losses: {})", deployment_loss, treasury_pool.total_deployment_losses);
+
     emit!(DeploymentConfirmed {
         request_id: deploy_request.request_id,
         developer: deploy_request.developer,
         deployed_program_id,
         deployment_cost: deploy_request.deployment_cost,
         recovered_funds: actual_recovered, // Emit actual recovered amount, not requested
-        confirmed_at: Clock::get()?.unix_timestamp,
+        recovered_to_liquid,
+        recovered_to_platform,
+        deployment_loss,
+        deploy_latency_secs: deploy_request.deploy_latency_secs().unwrap_or(0),
+        confirmed_at: current_time,
     });
 
     Ok(())
@@ -165,8 +215,13 @@ pub fn confirm_deployment_failure(
     
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
 
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(
+        failure_reason.len() <= TreasuryPool::MAX_REASON_LEN,
+        ErrorCode::ReasonTooLong
+    );
     require!(
         deploy_request.request_id == request_id,
         ErrorCode::InvalidRequestId
@@ -176,20 +231,49 @@ pub fn confirm_deployment_failure(
         ErrorCode::InvalidRequestStatus
     );
 
-    // Calculate refund amount
-    let total_payment = deploy_request.service_fee
-        .checked_add(deploy_request.monthly_fee)
+    // Calculate refund amount: service fee plus every month prepaid at creation
+    let monthly_refund = deploy_request
+        .monthly_fee
+        .checked_mul(deploy_request.prepaid_months())
         .ok_or(ErrorCode::CalculationOverflow)?;
-    let refund_amount = total_payment; // Full refund for failed deployment
+    let service_refund = deploy_request.service_fee;
+    let current_time = Clock::get()?.unix_timestamp;
 
     // Validate refund amount is reasonable
     require!(
-        refund_amount <= TreasuryPool::MAX_FEE_AMOUNT as u64,
+        service_refund.saturating_add(monthly_refund) <= TreasuryPool::MAX_FEE_AMOUNT as u64,
         ErrorCode::FeeAmountTooLarge
     );
 
+    // Refund fees collected (escrow first, then rewards not yet owed to backers)
+    let refund_amount = treasury_pool.refund_fees(deploy_request, service_refund, monthly_refund)?;
+    // Remaining prepaid months go to backers, as before escrow was introduced
+    let leftover_escrow = deploy_request.prepaid_balance;
+    treasury_pool.accrue_prepaid_fees(deploy_request, leftover_escrow, current_time)?;
+
+    user_stats.active_sessions = user_stats.active_sessions.saturating_sub(1);
+    user_stats.failed_deploys = user_stats.failed_deploys.saturating_add(1);
+
+    // Repeat failures forfeit failure_slash_bps of the refund to the Platform Pool
+    let slashed_amount = TreasuryPool::failure_slash(
+        refund_amount,
+        user_stats.failed_deploys,
+        treasury_pool.failure_slash_threshold,
+        treasury_pool.failure_slash_bps,
+    )?;
+    let developer_refund = refund_amount
+        .checked_sub(slashed_amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
     // Update deploy request
     deploy_request.status = DeployRequestStatus::Failed;
+    deploy_request.funding_locked = false;
+
+    // Deployment is no longer in flight
+    treasury_pool.total_borrowed = treasury_pool
+        .total_borrowed
+        .saturating_sub(deploy_request.borrowed_amount);
+    treasury_pool.release_deployment(deploy_request);
 
     // Check Reward Pool has enough lamports for refund
     let reward_pool_lamports = reward_pool_info.lamports();
@@ -199,18 +283,28 @@ pub fn confirm_deployment_failure(
     );
 
     // Refund developer payment from Reward Pool PDA via direct lamport manipulation
+    // Any slashed portion moves to the Platform Pool instead
     {
         let developer_wallet_info = ctx.accounts.developer_wallet.to_account_info();
+        let platform_pool_info = ctx.accounts.platform_pool.to_account_info();
         let mut reward_pool_lamports_mut = reward_pool_info.try_borrow_mut_lamports()?;
         let mut developer_lamports = developer_wallet_info.try_borrow_mut_lamports()?;
+        let mut platform_pool_lamports = platform_pool_info.try_borrow_mut_lamports()?;
 
         **reward_pool_lamports_mut = (**reward_pool_lamports_mut)
             .checked_sub(refund_amount)
             .ok_or(ErrorCode::CalculationOverflow)?;
         **developer_lamports = (**developer_lamports)
-            .checked_add(refund_amount)
+            .checked_add(developer_refund)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **platform_pool_lamports = (**platform_pool_lamports)
+            .checked_add(slashed_amount)
             .ok_or(ErrorCode::CalculationOverflow)?;
     }
+    if slashed_amount > 0 {
+        treasury_pool.credit_platform_pool(slashed_amount as u128)?;
+        msg!("[CONFIRM_FAILURE] Failure #{} for developer, slashed {} lamports to Platform Pool", user_stats.failed_deploys, slashed_amount);
+    }
  
     // Return deployment cost to liquid_balance (where it came from)
     // Recovered funds increase liquid_balance for withdrawals
@@ -239,16 +333,14 @@ pub fn confirm_deployment_failure(
         // PlatformPool only receives 0.1% developer fees, not recovered deployment funds
     }
 
-    // IMPORTANT: Refund fees collected (decrease reward_pool_balance)
-    treasury_pool.debit_reward_pool(refund_amount)?;
-
     emit!(DeploymentFailed {
         request_id: deploy_request.request_id,
         developer: deploy_request.developer,
         failure_reason,
-        refund_amount,
+        refund_amount: developer_refund,
+        slashed_amount,
         deployment_cost_returned: deploy_request.deployment_cost,
-        failed_at: Clock::get()?.unix_timestamp,
+        failed_at: current_time,
     });
 
     Ok(())
@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
-use crate::events::DeploymentFundsRequested;
-use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use crate::events::{DailyStatsReset, DeploymentFundsRequested};
+use crate::states::{Blacklist, D2DConfig, DeployPayment, DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_lang::solana_program::rent::Rent;
@@ -17,7 +17,8 @@ pub struct CreateDeployRequest<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
@@ -41,7 +42,7 @@ pub struct CreateDeployRequest<'info> {
     /// We use UncheckedAccount to handle old layouts, then manually deserialize/resize
     #[account(
         mut,
-        seeds = [DeployRequest::PREFIX_SEED, program_hash.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, developer.key().as_ref(), program_hash.as_ref()],
         bump
     )]
     pub deploy_request: UncheckedAccount<'info>,
@@ -59,6 +60,20 @@ pub struct CreateDeployRequest<'info> {
     #[account(mut)]
     pub developer: UncheckedAccount<'info>,
     
+    /// CHECK: Blacklist PDA for the developer - usually does not exist; rejected if it is a live Blacklist account
+    #[account(
+        seeds = [Blacklist::PREFIX_SEED, developer.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    
+    /// Optional D2DConfig; when passed its fee_rate and max_concurrent_per_user apply
+    #[account(
+        seeds = [D2DConfig::PREFIX_SEED],
+        bump = config.bump
+    )]
+    pub config: Option<Account<'info, D2DConfig>>,
+    
     #[account(
         mut,
         constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
@@ -75,7 +90,15 @@ pub fn create_deploy_request(
     monthly_fee: u64,
     initial_months: u32,
     deployment_cost: u64,
+    reward_split_bps: Option<u64>,
 ) -> Result<()> {
+    // Config limits fall back to the pool defaults when no D2DConfig is passed
+    let config = ctx.accounts.config.as_ref();
+    require!(!config.is_some_and(|c| c.is_paused), ErrorCode::ProgramPaused);
+    let max_concurrent_sessions = config
+        .map_or(ctx.accounts.treasury_pool.max_concurrent_sessions, |c| c.max_concurrent_per_user);
+    let min_service_fee = config.map_or(1, |c| c.fee_rate.max(1));
+
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request_info = ctx.accounts.deploy_request.to_account_info();
     let user_stats = &mut ctx.accounts.user_stats;
@@ -86,6 +109,22 @@ pub fn create_deploy_request(
     let required_space = 8 + DeployRequest::INIT_SPACE;
     let current_space = deploy_request_info.data_len();
     let is_new_account = current_space == 0;
+
+    // The account is unchecked and may be created or resized below: re-derive the PDA from
+    // program_hash and require both address and bump to match before anything is written
+    let (expected_deploy_request, expected_bump) = Pubkey::find_program_address(
+        &[
+            DeployRequest::PREFIX_SEED,
+            ctx.accounts.developer.key().as_ref(),
+            program_hash.as_ref(),
+        ],
+        program_id,
+    );
+    require!(
+        deploy_request_info.key() == expected_deploy_request
+            && ctx.bumps.deploy_request == expected_bump,
+        ErrorCode::InvalidRequestId
+    );
     
     // Check if account exists and verify owner
     if !is_new_account {
@@ -106,8 +145,10 @@ pub fn create_deploy_request(
         // For new PDA accounts, we need to create them with the correct size from the start
         // Use System Program's create_account instruction with PDA seeds as signer
         // This creates the account with correct size and assigns ownership in one step
+        let developer_key = ctx.accounts.developer.key();
         let deploy_request_seeds = &[
             DeployRequest::PREFIX_SEED,
+            developer_key.as_ref(),
             program_hash.as_ref(),
             &[ctx.bumps.deploy_request],
         ];
@@ -198,22 +239,46 @@ pub fn create_deploy_request(
                 status: DeployRequestStatus::PendingDeployment,
                 created_at: 0,
                 bump: ctx.bumps.deploy_request,
+                prepaid_balance: 0,
+                last_fee_accrual: 0,
+                funding_locked: false,
+                initial_months: 0,
+                renewal_balance: 0,
+                reserved_amount: 0,
+                deployed_at: None,
+                original_developer: None,
+                credited_months: 0,
             }
         }
     };
     
     let is_new_deploy_request =
         deploy_request.request_id == [0u8; 32] && deploy_request.developer == Pubkey::default();
+    // A retry of this developer's open request reuses its session instead of opening another
+    let holds_session = !is_new_deploy_request
+        && deploy_request.developer == ctx.accounts.developer.key()
+        && matches!(
+            deploy_request.status,
+            DeployRequestStatus::PendingDeployment | DeployRequestStatus::Active
+        );
 
     // Assign bump
     deploy_request.bump = ctx.bumps.deploy_request;
 
     // Validation
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
-    require!(service_fee > 0, ErrorCode::InvalidAmount);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPLOY), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.deployments_paused, ErrorCode::DeploymentsPaused);
+    require!(
+        !Blacklist::is_active(&ctx.accounts.blacklist.to_account_info()),
+        ErrorCode::DeveloperBlacklisted
+    );
+    require!(service_fee >= min_service_fee, ErrorCode::InvalidAmount);
+    // With monthly_fee_bps set the fee tracks deployment_cost instead of the passed value
+    let monthly_fee = TreasuryPool::monthly_fee_for(treasury_pool.monthly_fee_bps, deployment_cost, monthly_fee)?;
     require!(monthly_fee > 0, ErrorCode::InvalidAmount);
     require!(initial_months > 0, ErrorCode::InvalidAmount);
     require!(deployment_cost > 0, ErrorCode::InvalidAmount);
+    treasury_pool.check_deployment_cost(deployment_cost)?;
 
     // Note: Deployment cost funding will be handled by fund_temporary_wallet
     // We don't check pool balances here as funding comes from Admin/Reward Pool
@@ -224,32 +289,46 @@ pub fn create_deploy_request(
         user_stats.active_sessions = 0;
         user_stats.daily_deploys = 0;
         user_stats.total_deploys = 0;
+        user_stats.failed_deploys = 0;
         user_stats.last_reset = current_time;
         user_stats.bump = ctx.bumps.user_stats;
     }
 
     // Reset daily counter if new day
-    if current_time - user_stats.last_reset > 86400 {
-        user_stats.daily_deploys = 0;
-        user_stats.last_reset = current_time;
+    if let Some(previous_daily_deploys) = user_stats.reset_daily_if_due(current_time) {
+        emit!(DailyStatsReset {
+            user: user_stats.user,
+            previous_daily_deploys,
+            last_reset: user_stats.last_reset,
+            total_deploys: user_stats.total_deploys,
+        });
     }
 
+    require!(
+        user_stats.daily_deploys < treasury_pool.max_daily_deploys,
+        ErrorCode::DailyDeployLimitExceeded
+    );
+
+    // Each open session has treasury funds committed to it; cap them per developer
+    require!(
+        holds_session || user_stats.active_sessions < max_concurrent_sessions,
+        ErrorCode::MaxConcurrentSessionsExceeded
+    );
+
     // Calculate total payment and fee breakdown
     // Payment structure:
     // - monthlyFee (1% monthly) + serviceFee → RewardPool
     // - deploymentPlatformFee (0.1% platform) → PlatformPool
-    let monthly_fee_total = monthly_fee
-        .checked_mul(initial_months as u64)
-        .ok_or(ErrorCode::CalculationOverflow)?;
-    let reward_fee_amount = monthly_fee_total
-        .checked_add(service_fee)
-        .ok_or(ErrorCode::CalculationOverflow)?; // Monthly fee + service fee → RewardPool
-    let platform_fee_amount = deployment_cost
-        .checked_div(1000)
-        .ok_or(ErrorCode::CalculationOverflow)?; // 0.1% of deployment_cost → PlatformPool
-    let total_payment = reward_fee_amount
-        .checked_add(platform_fee_amount)
-        .ok_or(ErrorCode::CalculationOverflow)?;
+    // reward_split_bps re-divides serviceFee + deploymentPlatformFee between the two pools
+    // Same breakdown estimate_deploy_cost previews
+    let DeployPayment {
+        monthly_fee_total,
+        reward_share,
+        platform_fee_amount,
+        reward_split_bps,
+        total_payment,
+        ..
+    } = treasury_pool.deploy_payment(service_fee, monthly_fee, initial_months, deployment_cost, reward_split_bps)?;
 
     // Initialize deploy request with PendingDeployment status
     if is_new_deploy_request {
@@ -257,13 +336,18 @@ pub fn create_deploy_request(
         deploy_request.developer = ctx.accounts.developer.key();
         deploy_request.program_hash = program_hash;
         deploy_request.created_at = current_time;
+        deploy_request.last_fee_accrual = current_time;
     } else {
+        // Never rewrite a request while its deployment funds are in flight
+        require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
+
         // Ensure this PDA corresponds to the provided hash/developer
         let hash_matches = deploy_request.program_hash == program_hash;
         let developer_matches = deploy_request.developer == ctx.accounts.developer.key();
         
         if hash_matches && !developer_matches {
-            // Conflict handling (same as before)
+            // Conflict handling - fallback only: the PDA is seeded by developer, so another
+            // developer's request for the same hash lives at a different address
             let can_reset = matches!(
                 deploy_request.status,
                 DeployRequestStatus::Failed
@@ -282,7 +366,22 @@ pub fn create_deploy_request(
                 can_reset,
                 ErrorCode::InvalidRequestId
             );
+            // A transferred request lives under its original developer's seeds; that developer
+            // no longer owns it and must not reclaim it from the new owner
+            require!(
+                deploy_request.original_developer.is_none(),
+                ErrorCode::InvalidRequestId
+            );
+            // Auto-renewal top-ups belong to the previous developer
+            require!(
+                deploy_request.renewal_balance == 0,
+                ErrorCode::RenewalBalanceOutstanding
+            );
             
+            // Previous developer's unaccrued escrow goes to backers, as on any terminal transition
+            let leftover_escrow = deploy_request.prepaid_balance;
+            treasury_pool.accrue_prepaid_fees(&mut deploy_request, leftover_escrow, current_time)?;
+
             // Reset the deploy_request for new developer
             deploy_request.request_id = program_hash;
             deploy_request.developer = ctx.accounts.developer.key();
@@ -290,6 +389,8 @@ pub fn create_deploy_request(
             deploy_request.created_at = current_time;
             deploy_request.ephemeral_key = None;
             deploy_request.deployed_program_id = None;
+            deploy_request.deployed_at = None;
+            deploy_request.last_fee_accrual = current_time;
         } else if !hash_matches {
             require!(
                 hash_matches,
@@ -300,7 +401,7 @@ pub fn create_deploy_request(
             // Allow retry for:
             // 1. Failed/Cancelled/Closed deployments (obvious retry cases)
             // 2. PendingDeployment without ephemeral_key (initial request, can retry)
-            // 3. PendingDeployment with ephemeral_key (deployment in progress but can retry if needed)
+            // 3. PendingDeployment with ephemeral_key once the funding lock is released
             // 4. Active status (same developer/hash, can update subscription or retry deployment)
             let can_retry = matches!(
                 deploy_request.status,
@@ -322,18 +423,28 @@ pub fn create_deploy_request(
         }
     }
 
-    deploy_request.service_fee = service_fee;
+    // Refunds debit the Reward Pool by service_fee, so record what the split actually credited there
+    deploy_request.service_fee = reward_share;
     deploy_request.monthly_fee = monthly_fee;
     deploy_request.deployment_cost = deployment_cost;
-    deploy_request.borrowed_amount = 0; // Will be set when temporary wallet is funded (equals deployment_cost)
+    deploy_request.borrowed_amount = 0; // Set by fund_temporary_wallet (= deployment_cost), required by confirm_deployment_success
     deploy_request.subscription_paid_until =
         current_time + (initial_months as i64 * 30 * 24 * 60 * 60);
+    deploy_request.initial_months = initial_months;
     deploy_request.ephemeral_key = None; // Will be set when backend funds temporary wallet
     deploy_request.deployed_program_id = None; // Will be set after backend deploys
+    deploy_request.deployed_at = None;
     deploy_request.status = DeployRequestStatus::PendingDeployment;
 
+    // Reserve deployment_cost against liquid_balance not already held for other requests,
+    // as request_deployment_funds does. A retry swaps its previous reservation for the new cost
+    treasury_pool.release_deployment(&mut deploy_request);
+    treasury_pool.reserve_deployment(&mut deploy_request, deployment_cost)?;
+
     // Update user stats
-    user_stats.active_sessions += 1;
+    if !holds_session {
+        user_stats.active_sessions += 1;
+    }
     user_stats.daily_deploys += 1;
     user_stats.total_deploys += 1;
 
@@ -342,30 +453,21 @@ pub fn create_deploy_request(
     // - monthlyFee (1% monthly) + serviceFee → RewardPool
     // - deploymentPlatformFee (0.1% platform) → PlatformPool
     // We just need to update the state to track the balances
+    // Prepaid months stay in escrow and reach backers through accrue_monthly_fee
     
     // Credit fees to respective pools
-    treasury_pool.credit_reward_pool(reward_fee_amount as u128)?;
-    treasury_pool.credit_platform_pool(platform_fee_amount as u128)?;
-    
-    // Update reward_per_share if there are deposits
-    if treasury_pool.total_deposited > 0 {
-        // Only update reward_per_share for reward fees (not platform fees)
-        let reward_per_share_increment = (reward_fee_amount as u128)
-            .checked_mul(TreasuryPool::PRECISION)
-            .and_then(|x| x.checked_div(treasury_pool.total_deposited as u128))
-            .ok_or(ErrorCode::CalculationOverflow)?;
-        treasury_pool.reward_per_share = treasury_pool
-            .reward_per_share
-            .checked_add(reward_per_share_increment)
-            .ok_or(ErrorCode::CalculationOverflow)?;
-    }
+    // Same accounting as credit_fee_to_pool: reward_per_share (dust to undistributed_dust),
+    // or unallocated_rewards for the next first depositor while the pool is empty
+    treasury_pool.credit_fee_to_pool(reward_share, platform_fee_amount)?;
+    treasury_pool.record_fee_credit(reward_share, current_time)?;
+    treasury_pool.escrow_prepaid_fees(&mut deploy_request, monthly_fee_total)?;
     
     // Verify pools have received the payments
     // This is a safety check - the actual transfers happened off-chain
     let reward_pool_lamports = ctx.accounts.reward_pool.lamports();
     let platform_pool_lamports = ctx.accounts.platform_pool.lamports();
     require!(
-        reward_pool_lamports >= treasury_pool.reward_pool_balance,
+        treasury_pool.reward_pool_shortfall(reward_pool_lamports)? == 0,
         ErrorCode::InsufficientTreasuryFunds
     );
     require!(
@@ -386,6 +488,9 @@ pub fn create_deploy_request(
         deployment_cost,
         total_payment,
         requested_at: current_time,
+        reward_split_bps,
+        reward_fee_amount: reward_share,
+        platform_fee_amount,
     });
 
     Ok(())
@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::events::RewardCredited;
-use crate::states::TreasuryPool;
+use crate::states::{ProcessedFee, RewardHistory, TreasuryPool};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
@@ -10,12 +10,15 @@ use anchor_lang::system_program;
 /// Updates reward_per_share accumulator.
 ///
 /// SECURITY: Developer (fee_payer) pays the fees, not admin
+/// Idempotent per fee_id: the ProcessedFee PDA records it, so a backend retry cannot double-credit
 #[derive(Accounts)]
+#[instruction(fee_id: [u8; 32])]
 pub struct CreditFeeToPool<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
 
@@ -45,19 +48,42 @@ pub struct CreditFeeToPool<'info> {
     #[account(mut)]
     pub fee_payer: Signer<'info>,
 
+    /// Optional reward_per_share history; a snapshot is recorded when passed
+    #[account(
+        mut,
+        seeds = [RewardHistory::PREFIX_SEED],
+        bump = reward_history.bump
+    )]
+    pub reward_history: Option<Account<'info, RewardHistory>>,
+
+    /// Record of this fee_id (created on first use; already stamped on a retry)
+    #[account(
+        init_if_needed,
+        payer = fee_payer,
+        space = 8 + ProcessedFee::INIT_SPACE,
+        seeds = [ProcessedFee::PREFIX_SEED, fee_id.as_ref()],
+        bump
+    )]
+    pub processed_fee: Account<'info, ProcessedFee>,
+
     pub system_program: Program<'info, System>,
 }
 
 /// Credit fees to pools and update reward_per_share
 ///
 /// SECURITY FIX Flow:
+/// 0. Reject a fee_id whose ProcessedFee record is already stamped (DuplicateFee)
 /// 1. Developer (fee_payer) transfers fees to RewardPool and PlatformPool PDAs
 /// 2. Admin authorizes the fee credit operation
 /// 3. Call treasury_pool.credit_fee_to_pool() which updates reward_per_share
+/// 4. Verify the Reward Pool PDA covers reward_pool_balance + prepaid_fees_balance + undistributed_dust
+/// 5. Record a reward_per_share snapshot if the RewardHistory account is passed
+/// 6. Stamp the ProcessedFee record for fee_id
 ///
 /// IMPORTANT: Developer (fee_payer) pays the fees, NOT admin
 pub fn credit_fee_to_pool(
     ctx: Context<CreditFeeToPool>,
+    fee_id: [u8; 32],
     fee_reward: u64,
     fee_platform: u64,
 ) -> Result<()> {
@@ -65,6 +91,7 @@ pub fn credit_fee_to_pool(
 
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
     require!(fee_reward > 0 || fee_platform > 0, ErrorCode::InvalidAmount);
+    require!(ctx.accounts.processed_fee.processed_at == 0, ErrorCode::DuplicateFee);
 
     // SECURITY FIX: Check fee_payer (developer) has enough lamports, not admin
     let fee_payer_lamports = ctx.accounts.fee_payer.lamports();
@@ -105,12 +132,33 @@ pub fn credit_fee_to_pool(
     // This is the key function that updates the accumulator
     treasury_pool.credit_fee_to_pool(fee_reward, fee_platform)?;
 
+    // Refuse to grow reward_per_share past what the Reward Pool PDA actually holds
+    // Prepaid escrow is owed to developers and cannot back the new rewards
+    require!(
+        treasury_pool.reward_pool_shortfall(ctx.accounts.reward_pool.lamports())? == 0,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+    let current_time = Clock::get()?.unix_timestamp;
+    treasury_pool.record_fee_credit(fee_reward, current_time)?;
+
+    if let Some(reward_history) = ctx.accounts.reward_history.as_mut() {
+        reward_history.record(current_time, treasury_pool.reward_per_share, treasury_pool.total_deposited);
+    }
+
+    let processed_fee = &mut ctx.accounts.processed_fee;
+    processed_fee.fee_id = fee_id;
+    processed_fee.fee_reward = fee_reward;
+    processed_fee.fee_platform = fee_platform;
+    processed_fee.processed_at = current_time;
+    processed_fee.bump = ctx.bumps.processed_fee;
+
     emit!(RewardCredited {
+        fee_id,
         fee_reward,
         fee_platform,
         reward_per_share: treasury_pool.reward_per_share,
         total_deposited: treasury_pool.total_deposited,
-        credited_at: Clock::get()?.unix_timestamp,
+        credited_at: current_time,
     });
 
     Ok(())
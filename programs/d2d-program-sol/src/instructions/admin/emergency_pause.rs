@@ -8,7 +8,8 @@ pub struct EmergencyPause<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     #[account(mut)]
@@ -24,6 +25,8 @@ pub fn emergency_pause(ctx: Context<EmergencyPause>, pause: bool) -> Result<()>
     );
 
     treasury_pool.emergency_pause = pause;
+    // emergency_pause is "all bits set"; unpausing clears every per-operation pause too
+    treasury_pool.paused_ops = if pause { TreasuryPool::PAUSE_ALL } else { 0 };
 
     emit!(EmergencyPauseToggled {
         paused: pause,
@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::events::EmergencyKeysRotated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Emergency rotation of admin and reward admin in one instruction
+///
+/// Authorized by the current admin or the recovery authority (multisig).
+/// Skips the two-step handoff on purpose - for key-compromise incidents only.
+/// Allowed while paused.
+#[derive(Accounts)]
+pub struct EmergencyRotateKeys<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = authority.key() == treasury_pool.admin
+            || Some(authority.key()) == treasury_pool.recovery_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn emergency_rotate_keys(
+    ctx: Context<EmergencyRotateKeys>,
+    new_admin: Pubkey,
+    new_reward_admin: Pubkey,
+) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    require!(new_admin != Pubkey::default(), ErrorCode::InvalidAdmin);
+    require!(new_reward_admin != Pubkey::default(), ErrorCode::InvalidAdmin);
+
+    let old_admin = treasury_pool.admin;
+    let old_reward_admin = treasury_pool.effective_reward_admin();
+
+    treasury_pool.admin = new_admin;
+    treasury_pool.reward_admin = new_reward_admin;
+    // Any in-flight handoff may have been set up by the compromised key
+    treasury_pool.pending_admin = None;
+
+    msg!("[ROTATE] Admin: {} -> {}", old_admin, new_admin);
+    msg!("[ROTATE] Reward admin: {} -> {}", old_reward_admin, new_reward_admin);
+
+    emit!(EmergencyKeysRotated {
+        authority: ctx.accounts.authority.key(),
+        old_admin,
+        new_admin,
+        old_reward_admin,
+        new_reward_admin,
+        rotated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
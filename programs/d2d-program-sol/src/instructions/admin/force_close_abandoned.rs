@@ -0,0 +1,117 @@
+use crate::errors::ErrorCode;
+use crate::events::ProgramClosed;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Force-close a zombie deploy request and return its recovered lamports to liquid_balance
+/// Forced counterpart of close_program_and_refund for developers who stopped paying and vanished
+///
+/// Only allowed once subscription_paid_until is more than abandoned_after_days (and the pool's
+/// grace period) in the past; nothing is refunded to the developer since no paid months remain.
+#[derive(Accounts)]
+#[instruction(request_id: [u8; 32])]
+pub struct ForceCloseAbandoned<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), request_id.as_ref()],
+        bump = deploy_request.bump,
+        constraint = matches!(
+            deploy_request.status,
+            DeployRequestStatus::Active
+                | DeployRequestStatus::SubscriptionExpired
+                | DeployRequestStatus::Suspended
+        ) @ ErrorCode::InvalidDeploymentStatus
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, deploy_request.developer.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Account that will send recovered lamports (could be program account or ephemeral key)
+    #[account(mut)]
+    pub refund_source: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn force_close_abandoned(
+    ctx: Context<ForceCloseAbandoned>,
+    _request_id: [u8; 32],
+    recovered_lamports: u64,
+    abandoned_after_days: u32,
+) -> Result<()> {
+    let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(recovered_lamports > 0, ErrorCode::InvalidAmount);
+    require!(
+        deploy_request.is_abandoned(current_time, treasury_pool.grace_period_secs, abandoned_after_days),
+        ErrorCode::RequestNotAbandoned
+    );
+
+    // Transfer recovered lamports directly to Treasury Pool PDA
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.refund_source.to_account_info(),
+            to: treasury_pool_info,
+        },
+    );
+    system_program::transfer(cpi_context, recovered_lamports)?;
+
+    // Recovered capital is available to backers and new deployments again
+    treasury_pool.liquid_balance = treasury_pool
+        .liquid_balance
+        .checked_add(recovered_lamports)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!(
+        "[FORCE_CLOSE] Abandoned since {} ({} days threshold), recovered {} lamports",
+        deploy_request.subscription_paid_until,
+        abandoned_after_days,
+        recovered_lamports
+    );
+
+    user_stats.active_sessions = user_stats.active_sessions.saturating_sub(1);
+
+    deploy_request.status = DeployRequestStatus::Closed;
+
+    // Any escrow left over was never accrued; it goes to backers
+    let leftover_escrow = deploy_request.prepaid_balance;
+    treasury_pool.accrue_prepaid_fees(deploy_request, leftover_escrow, current_time)?;
+
+    emit!(ProgramClosed {
+        request_id: deploy_request.request_id,
+        program_id: deploy_request.deployed_program_id.unwrap_or_default(),
+        developer: deploy_request.developer,
+        recovered_lamports,
+        refunded_amount: 0,
+        closed_at: current_time,
+    });
+
+    Ok(())
+}
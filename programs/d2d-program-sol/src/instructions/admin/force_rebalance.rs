@@ -39,6 +39,7 @@ pub fn force_rebalance(ctx: Context<ForceRebalance>) -> Result<()> {
     let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
     let mut treasury_pool = TreasuryPool::try_deserialize(&mut &treasury_pool_info.data.borrow()[..])
         .map_err(|_| ErrorCode::InvalidAccountData)?;
+    require!(treasury_pool.is_current_layout(), ErrorCode::StaleLayout);
 
     let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
 
@@ -0,0 +1,163 @@
+use crate::errors::ErrorCode;
+use crate::events::DeploymentFailed;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Admin recover a hung deployment whose confirm_deployment_* never arrived
+/// After ephemeral_recovery_timeout_secs since created_at, sweeps the ephemeral key back to the
+/// Treasury PDA and fails the request with the same developer refund as confirm_deployment_failure
+#[derive(Accounts)]
+pub struct ForceRecoverEphemeral<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, deploy_request.developer.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// Ephemeral key that received the deployment funds (signs the sweep)
+    #[account(
+        mut,
+        constraint = deploy_request.ephemeral_key == Some(ephemeral_key.key()) @ ErrorCode::InvalidEphemeralKey
+    )]
+    pub ephemeral_key: Signer<'info>,
+
+    /// CHECK: Developer wallet receiving the failure refund
+    #[account(
+        mut,
+        constraint = developer_wallet.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub developer_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Treasury Pool PDA (receives the swept lamports)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (source of the developer refund)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn force_recover_ephemeral(ctx: Context<ForceRecoverEphemeral>, request_id: [u8; 32]) -> Result<()> {
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let developer_wallet_info = ctx.accounts.developer_wallet.to_account_info();
+    let ephemeral_key_info = ctx.accounts.ephemeral_key.to_account_info();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(
+        deploy_request.status == DeployRequestStatus::PendingDeployment,
+        ErrorCode::InvalidRequestStatus
+    );
+    let recoverable_at = deploy_request
+        .created_at
+        .checked_add(treasury_pool.ephemeral_recovery_timeout_secs)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    require!(current_time >= recoverable_at, ErrorCode::SessionNotExpired);
+
+    // Sweep whatever the deployment left behind
+    let swept = ephemeral_key_info.lamports();
+    if swept > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ephemeral_key_info,
+                to: ctx.accounts.treasury_pda.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, swept)?;
+
+        treasury_pool.liquid_balance = treasury_pool
+            .liquid_balance
+            .checked_add(swept)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    // Deployment is no longer in flight
+    treasury_pool.total_borrowed = treasury_pool
+        .total_borrowed
+        .saturating_sub(deploy_request.borrowed_amount);
+    treasury_pool.release_deployment(deploy_request);
+    user_stats.active_sessions = user_stats.active_sessions.saturating_sub(1);
+
+    deploy_request.status = DeployRequestStatus::Failed;
+    deploy_request.funding_locked = false;
+
+    // Same refund as confirm_deployment_failure: service fee + all prepaid months, escrow first
+    let service_refund = deploy_request.service_fee;
+    let monthly_refund = deploy_request
+        .monthly_fee
+        .checked_mul(deploy_request.prepaid_months())
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let refund_amount = treasury_pool.refund_fees(deploy_request, service_refund, monthly_refund)?;
+    let leftover_escrow = deploy_request.prepaid_balance;
+    treasury_pool.accrue_prepaid_fees(deploy_request, leftover_escrow, current_time)?;
+
+    require!(
+        reward_pool_info.lamports() >= refund_amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+    {
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut developer_lamports = developer_wallet_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(refund_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **developer_lamports = (**developer_lamports)
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    msg!("[RECOVER] Swept {} lamports from hung deployment, refunded {} lamports", swept, refund_amount);
+
+    emit!(DeploymentFailed {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        failure_reason: "timeout recovery".to_string(),
+        refund_amount,
+        slashed_amount: 0,
+        deployment_cost_returned: swept,
+        failed_at: current_time,
+    });
+
+    Ok(())
+}
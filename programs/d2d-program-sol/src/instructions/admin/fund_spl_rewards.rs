@@ -0,0 +1,74 @@
+use crate::errors::ErrorCode;
+use crate::events::SplRewardsFunded;
+use crate::states::{TokenTreasury, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Admin fund SPL rewards for a token treasury
+/// Tokens move from the admin's token account into the vault and are credited via reward_per_share
+#[derive(Accounts)]
+pub struct FundSplRewards<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [TokenTreasury::PREFIX_SEED, mint.key().as_ref()],
+        bump = token_treasury.bump
+    )]
+    pub token_treasury: Account<'info, TokenTreasury>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = token_treasury.vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = admin_token_account.mint == token_treasury.mint @ ErrorCode::InvalidMint
+    )]
+    pub admin_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn fund_spl_rewards(ctx: Context<FundSplRewards>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let token_treasury = &mut ctx.accounts.token_treasury;
+    token_treasury.credit_rewards(amount)?;
+
+    let transfer_cpi = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.admin_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.admin.to_account_info(),
+        },
+    );
+    token::transfer_checked(transfer_cpi, amount, token_treasury.decimals)?;
+
+    msg!("[ADMIN] Funded {} SPL rewards for mint {}, reward_per_share: {}", amount, token_treasury.mint, token_treasury.reward_per_share);
+
+    emit!(SplRewardsFunded {
+        mint: token_treasury.mint,
+        amount,
+        reward_per_share: token_treasury.reward_per_share,
+        funded_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -14,13 +14,14 @@ pub struct FundTemporaryWallet<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
 
     #[account(
         mut,
-        seeds = [DeployRequest::PREFIX_SEED, request_id.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), request_id.as_ref()],
         bump = deploy_request.bump,
         constraint = deploy_request.status == DeployRequestStatus::PendingDeployment @ ErrorCode::InvalidDeploymentStatus
     )]
@@ -48,24 +49,34 @@ pub struct FundTemporaryWallet<'info> {
 /// Fund temporary wallet for deployment
 /// 
 /// Flow:
-/// 1. Check TreasuryPool.liquid_balance >= deployment_cost
+/// 1. Release the request's escrow and check liquid_balance - escrowed_amount >= deployment_cost
 /// 2. Transfer from Treasury Pool PDA -> temporary wallet (via lamport mutation)
 /// 3. Update liquid_balance in TreasuryPool state
+/// 4. Lock the deploy request until the deployment is confirmed or failed
 /// 
 /// NOTE: Funds sourced from TreasuryPool.liquid_balance (NOT RewardPool or PlatformPool)
 /// RewardPool is used exclusively for paying rewards to backers
 /// PlatformPool is used exclusively for admin operations (0.1% developer fees)
+///
+/// When reward_pool_isolated is set, requests for Reward Pool funding
+/// (use_admin_pool = false) are rejected regardless of the funding path
 pub fn fund_temporary_wallet(
     ctx: Context<FundTemporaryWallet>,
     _request_id: [u8; 32],
     amount: u64,
-    _use_admin_pool: bool, // Unused: always uses TreasuryPool.liquid_balance
+    use_admin_pool: bool, // Funding always uses TreasuryPool.liquid_balance; only checked for isolation
 ) -> Result<()> {
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request = &mut ctx.accounts.deploy_request;
 
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPLOY), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.deployments_paused, ErrorCode::DeploymentsPaused);
+    require!(
+        use_admin_pool || !treasury_pool.reward_pool_isolated,
+        ErrorCode::RewardPoolIsolated
+    );
     require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
 
     // Verify that the requested amount matches the deployment cost in deploy_request
     require!(
@@ -73,16 +84,34 @@ pub fn fund_temporary_wallet(
         ErrorCode::InvalidAmount
     );
 
+    fund_deployment(
+        treasury_pool,
+        deploy_request,
+        &ctx.accounts.treasury_pda.to_account_info(),
+        &ctx.accounts.temporary_wallet.to_account_info(),
+        amount,
+    )
+}
+
+/// Move `amount` from liquid_balance to a deployment's temporary wallet and lock the request
+/// Shared by fund_temporary_wallet and fund_temporary_wallets_batch; the caller checks pauses and status
+pub(crate) fn fund_deployment(
+    treasury_pool: &mut TreasuryPool,
+    deploy_request: &mut DeployRequest,
+    treasury_pda_info: &AccountInfo,
+    temporary_wallet_info: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
     // IMPORTANT: Use liquid_balance from Treasury PDA (not from pools)
     // This ensures withdrawals work correctly when funds are used for deployments
+    // The request's own reservation is released here and becomes total_borrowed below;
+    // liquidity reserved by other pending requests is not available
+    treasury_pool.release_deployment(deploy_request);
     require!(
-        treasury_pool.liquid_balance >= amount,
+        amount <= treasury_pool.available_liquidity(),
         ErrorCode::InsufficientLiquidBalance
     );
 
-    let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
-    let temporary_wallet_info = ctx.accounts.temporary_wallet.to_account_info();
-
     // Verify Treasury PDA has enough lamports
     require!(
         treasury_pda_info.lamports() >= amount,
@@ -113,9 +142,17 @@ pub fn fund_temporary_wallet(
         .checked_sub(amount)
         .ok_or(ErrorCode::CalculationOverflow)?;
 
+    // Track funds out on deployment until confirm_deployment_* resolves them
+    treasury_pool.total_borrowed = treasury_pool
+        .total_borrowed
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
     // Store temporary wallet address and borrowed amount in deploy_request
     deploy_request.ephemeral_key = Some(temporary_wallet_info.key());
     deploy_request.borrowed_amount = amount; // Track borrowed amount for fee calculation (1% monthly)
+    deploy_request.last_fee_accrual = Clock::get()?.unix_timestamp; // Borrow fee accrues from here
+    deploy_request.funding_locked = true; // Released by confirm_deployment_success / confirm_deployment_failure
 
     emit!(TemporaryWalletFunded {
         request_id: deploy_request.request_id,
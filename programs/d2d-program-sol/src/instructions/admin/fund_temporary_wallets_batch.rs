@@ -0,0 +1,103 @@
+use crate::errors::ErrorCode;
+use crate::instructions::admin::fund_temporary_wallet::fund_deployment;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Maximum deployments funded per call (keeps the instruction under the compute budget)
+pub const MAX_FUND_BATCH: usize = 8;
+
+/// Admin fund several deployments' temporary wallets in one instruction
+///
+/// `remaining_accounts` are passed in pairs: [deploy_request (writable), temporary wallet (writable)].
+/// Each request is funded with its deployment_cost exactly as fund_temporary_wallet would.
+/// Unlike claim_rewards_batch nothing is skipped: an invalid pair, or a request the remaining
+/// liquid_balance cannot cover, fails the whole batch.
+#[derive(Accounts)]
+pub struct FundTemporaryWalletsBatch<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Treasury Pool PDA (holds deposits, source of liquid_balance)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+}
+
+// usize::is_multiple_of is newer than the platform-tools rustc used for SBF builds
+#[allow(clippy::manual_is_multiple_of)]
+pub fn fund_temporary_wallets_batch(
+    ctx: Context<FundTemporaryWalletsBatch>,
+    use_admin_pool: bool, // Same meaning as in fund_temporary_wallet
+) -> Result<()> {
+    let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPLOY), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.deployments_paused, ErrorCode::DeploymentsPaused);
+    require!(
+        use_admin_pool || !treasury_pool.reward_pool_isolated,
+        ErrorCode::RewardPoolIsolated
+    );
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 2 == 0,
+        ErrorCode::InvalidAmount
+    );
+    require!(
+        ctx.remaining_accounts.len() / 2 <= MAX_FUND_BATCH,
+        ErrorCode::BatchTooLarge
+    );
+
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (request_info, wallet_info) = (&pair[0], &pair[1]);
+
+        require!(
+            request_info.owner == ctx.program_id && request_info.is_writable && wallet_info.is_writable,
+            ErrorCode::InvalidAccountOwner
+        );
+        // Discriminator check happens in try_deserialize
+        let mut deploy_request = DeployRequest::try_deserialize(&mut &request_info.data.borrow()[..])?;
+        let expected_key = Pubkey::create_program_address(
+            &[
+                DeployRequest::PREFIX_SEED,
+                deploy_request.seed_developer().as_ref(),
+                deploy_request.program_hash.as_ref(),
+                &[deploy_request.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidRequestId))?;
+        require!(request_info.key() == expected_key, ErrorCode::InvalidRequestId);
+
+        require!(
+            deploy_request.status == DeployRequestStatus::PendingDeployment,
+            ErrorCode::InvalidDeploymentStatus
+        );
+        require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
+        let amount = deploy_request.deployment_cost;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        fund_deployment(treasury_pool, &mut deploy_request, &treasury_pda_info, wallet_info, amount)?;
+
+        // Written back before the next pair so a repeated request hits funding_locked
+        let mut data = request_info.try_borrow_mut_data()?;
+        deploy_request.try_serialize(&mut &mut data[..])?;
+    }
+
+    msg!("[FUND_BATCH] Funded {} deployments", ctx.remaining_accounts.len() / 2);
+
+    Ok(())
+}
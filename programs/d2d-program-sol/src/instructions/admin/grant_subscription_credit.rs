@@ -0,0 +1,86 @@
+use crate::errors::ErrorCode;
+use crate::events::SubscriptionCredited;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Admin grant free subscription months (support goodwill, e.g. after an outage)
+/// Extends subscription_paid_until without any lamport transfer; credited months are
+/// tracked in credited_months and never refunded or escrowed
+#[derive(Accounts)]
+#[instruction(request_id: [u8; 32])]
+pub struct GrantSubscriptionCredit<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), request_id.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// An expired request that comes out paid up is Active again; Suspended requests keep their
+/// status (pay_subscription reinstates them)
+pub fn grant_subscription_credit(
+    ctx: Context<GrantSubscriptionCredit>,
+    request_id: [u8; 32],
+    months: u32,
+) -> Result<()> {
+    let deploy_request = &mut ctx.accounts.deploy_request;
+
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(months > 0, ErrorCode::InvalidAmount);
+    require!(
+        months <= TreasuryPool::MAX_SUBSCRIPTION_MONTHS,
+        ErrorCode::SubscriptionTooLong
+    );
+    require!(
+        !matches!(
+            deploy_request.status,
+            DeployRequestStatus::Closed | DeployRequestStatus::Failed | DeployRequestStatus::Cancelled
+        ),
+        ErrorCode::InvalidRequestStatus
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    deploy_request.extend_subscription(months);
+    deploy_request.credited_months = deploy_request
+        .credited_months
+        .checked_add(months)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    if deploy_request.status == DeployRequestStatus::SubscriptionExpired
+        && current_time <= deploy_request.subscription_paid_until
+    {
+        deploy_request.status = DeployRequestStatus::Active;
+    }
+
+    msg!("[SUBSCRIPTION_CREDIT] {} free months granted ({} total), valid until {}",
+         months, deploy_request.credited_months, deploy_request.subscription_paid_until);
+
+    emit!(SubscriptionCredited {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        admin: ctx.accounts.admin.key(),
+        months,
+        credited_months: deploy_request.credited_months,
+        subscription_valid_until: deploy_request.subscription_paid_until,
+        credited_at: current_time,
+    });
+
+    Ok(())
+}
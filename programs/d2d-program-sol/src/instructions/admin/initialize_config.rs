@@ -0,0 +1,66 @@
+use crate::errors::ErrorCode;
+use crate::events::ConfigInitialized;
+use crate::states::{D2DConfig, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Admin create the D2DConfig account
+/// Once it exists, pass it to create_deploy_request / request_deployment_funds to apply
+/// its fee_rate (minimum service fee) and max_concurrent_per_user instead of the TreasuryPool defaults
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + D2DConfig::INIT_SPACE,
+        seeds = [D2DConfig::PREFIX_SEED],
+        bump
+    )]
+    pub config: Account<'info, D2DConfig>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    fee_rate: u64,
+    max_concurrent_per_user: u32,
+) -> Result<()> {
+    require!(max_concurrent_per_user > 0, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let config = &mut ctx.accounts.config;
+
+    config.admin = treasury_pool.admin;
+    config.treasury = treasury_pool.treasury_wallet;
+    config.fee_rate = fee_rate;
+    config.max_concurrent_per_user = max_concurrent_per_user;
+    config.total_deploys = 0;
+    config.total_fees_collected = 0;
+    config.is_paused = false;
+    config.bump = ctx.bumps.config;
+
+    msg!("[ADMIN] Config initialized: fee_rate {} lamports, max {} concurrent per user", fee_rate, max_concurrent_per_user);
+
+    emit!(ConfigInitialized {
+        admin: config.admin,
+        treasury: config.treasury,
+        fee_rate,
+        max_concurrent_per_user,
+        initialized_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
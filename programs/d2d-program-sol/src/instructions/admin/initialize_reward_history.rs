@@ -0,0 +1,57 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardHistoryInitialized;
+use crate::states::{RewardHistory, RewardSnapshot, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Admin create the reward_per_share history ring buffer
+/// Once it exists, pass it to credit_fee_to_pool to record a snapshot per fee credit
+#[derive(Accounts)]
+pub struct InitializeRewardHistory<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RewardHistory::INIT_SPACE,
+        seeds = [RewardHistory::PREFIX_SEED],
+        bump
+    )]
+    pub reward_history: Account<'info, RewardHistory>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_reward_history(ctx: Context<InitializeRewardHistory>) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let reward_history = &mut ctx.accounts.reward_history;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    reward_history.entries = [RewardSnapshot::default(); RewardHistory::CAPACITY];
+    reward_history.next_index = 0;
+    reward_history.len = 0;
+    reward_history.bump = ctx.bumps.reward_history;
+
+    // Baseline so the first fee credit already yields a period
+    reward_history.record(current_time, treasury_pool.reward_per_share, treasury_pool.total_deposited);
+
+    msg!("[ADMIN] Reward history initialized at reward_per_share {}", treasury_pool.reward_per_share);
+
+    emit!(RewardHistoryInitialized {
+        reward_per_share: treasury_pool.reward_per_share,
+        total_deposited: treasury_pool.total_deposited,
+        initialized_at: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::errors::ErrorCode;
+use crate::events::TokenTreasuryInitialized;
+use crate::states::{TokenTreasury, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Admin open SPL token deposits for a mint (e.g. USDC)
+/// Creates the TokenTreasury for `mint` and its vault (Treasury Pool PDA's associated token account)
+#[derive(Accounts)]
+pub struct InitializeTokenTreasury<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + TokenTreasury::INIT_SPACE,
+        seeds = [TokenTreasury::PREFIX_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub token_treasury: Account<'info, TokenTreasury>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = treasury_pool
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_token_treasury(ctx: Context<InitializeTokenTreasury>) -> Result<()> {
+    let token_treasury = &mut ctx.accounts.token_treasury;
+
+    token_treasury.mint = ctx.accounts.mint.key();
+    token_treasury.vault = ctx.accounts.vault.key();
+    token_treasury.decimals = ctx.accounts.mint.decimals;
+    token_treasury.total_deposited = 0;
+    token_treasury.reward_per_share = 0;
+    token_treasury.reward_pool_balance = 0;
+    token_treasury.bump = ctx.bumps.token_treasury;
+
+    msg!("[ADMIN] Token treasury initialized for mint {} (vault {})", token_treasury.mint, token_treasury.vault);
+
+    emit!(TokenTreasuryInitialized {
+        mint: token_treasury.mint,
+        vault: token_treasury.vault,
+        initialized_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
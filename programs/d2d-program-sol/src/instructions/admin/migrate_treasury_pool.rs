@@ -1,11 +1,16 @@
+use crate::errors::ErrorCode;
+use crate::events::TreasuryPoolMigrated;
 use crate::states::TreasuryPool;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::system_program;
 
-/// Migrate Treasury Pool to new layout (removed withdrawal_pool_balance)
-/// Admin-only instruction to migrate existing pool to new struct layout
-/// 
-/// This preserves all existing data and removes withdrawal_pool_balance field
+/// Migrate Treasury Pool to the current layout, preserving live balances
+/// Admin-only instruction to upgrade an existing pool in place
+///
+/// Unlike close_treasury_pool + reinitialize_treasury_pool (a reset that zeroes everything),
+/// every existing field is carried over: deposits, pool balances, reward_per_share, admin and bumps.
+/// Runs once per layout: rejected when layout_version is already LAYOUT_VERSION.
 #[derive(Accounts)]
 pub struct MigrateTreasuryPool<'info> {
     /// CHECK: Treasury Pool PDA - will be resized and migrated
@@ -13,153 +18,98 @@ pub struct MigrateTreasuryPool<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidAccountOwner
     )]
     pub treasury_pool: UncheckedAccount<'info>,
-    
+
+    /// Pays the rent for the added bytes
     #[account(mut)]
     pub admin: Signer<'info>,
-    
+
     pub system_program: Program<'info, System>,
 }
 
-/// Migrate treasury pool to new layout (removed withdrawal_pool_balance)
-/// 
+/// Migrate treasury pool to the current layout
+///
 /// This instruction:
-/// 1. Resizes the account if needed
-/// 2. Reads existing data from old layout
-/// 3. Writes to new layout without withdrawal_pool_balance
+/// 1. Reads the old layout (fields are append-only; missing trailing fields decode as zero)
+/// 2. Verifies the admin and that layout_version is behind LAYOUT_VERSION
+/// 3. Tops up rent from the admin and resizes to 8 + INIT_SPACE, zero-filling new bytes
+/// 4. Writes the preserved fields back with layout_version = LAYOUT_VERSION
 pub fn migrate_treasury_pool(ctx: Context<MigrateTreasuryPool>) -> Result<()> {
     let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
     let required_space = 8 + TreasuryPool::INIT_SPACE;
-    
-    // Check current account size
     let current_space = treasury_pool_info.data_len();
+
     msg!("[MIGRATE] Current account size: {} bytes", current_space);
     msg!("[MIGRATE] Required size: {} bytes", required_space);
-    
-    // If account is already the correct size, check if it needs migration
-    if current_space >= required_space {
-        // Try to deserialize with new layout
-        match TreasuryPool::try_deserialize(&mut &treasury_pool_info.data.borrow()[..]) {
-            Ok(_pool) => {
-                // Account already has correct size (no withdrawal_pool_balance)
-                msg!("[MIGRATE] Account already migrated");
-                return Ok(()); // Already migrated
-            }
-            Err(_) => {
-                // Can't deserialize, need to resize and migrate
-                msg!("[MIGRATE] Cannot deserialize account, will resize and migrate...");
-            }
-        }
-    }
-    
-    // Read existing data before resize
-    let old_data = treasury_pool_info.data.borrow();
-    let mut old_pool_data = vec![0u8; old_data.len()];
-    old_pool_data.copy_from_slice(&old_data);
-    
-    // Calculate old layout size (may have withdrawal_pool_balance)
-    // Old layout: all fields including withdrawal_pool_balance (8 bytes for u64)
-    let old_layout_size = current_space.min(required_space - 8);
-    
-    // Resize account if needed
+
+    let mut pool = TreasuryPool::from_legacy_bytes(&treasury_pool_info.data.borrow())?;
+    require!(
+        pool.admin == ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(
+        pool.layout_version < TreasuryPool::LAYOUT_VERSION,
+        ErrorCode::TreasuryPoolAlreadyMigrated
+    );
+    let old_layout_version = pool.layout_version;
+
     if current_space < required_space {
+        let rent = Rent::get()?;
+        let additional_lamports_needed = rent
+            .minimum_balance(required_space)
+            .saturating_sub(treasury_pool_info.lamports());
+
+        msg!("[MIGRATE] Additional lamports needed: {}", additional_lamports_needed);
+
+        if additional_lamports_needed > 0 {
+            let transfer_cpi = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: treasury_pool_info.clone(),
+                },
+            );
+            system_program::transfer(transfer_cpi, additional_lamports_needed)?;
+        }
+
         msg!("[MIGRATE] Resizing account from {} to {} bytes", current_space, required_space);
-        treasury_pool_info.realloc(required_space, false)?;
+        // Using realloc for now (deprecated but still works); new bytes are zero-filled
+        #[allow(deprecated)]
+        treasury_pool_info.realloc(required_space, true)?;
     }
-    
-    // Get mutable data after resize
-    let mut data = treasury_pool_info.try_borrow_mut_data()?;
-    
-    // Try to deserialize old layout manually
-    // We'll read the fields we know exist in old layout
-    let mut new_pool = TreasuryPool {
-        reward_per_share: 0,
-        total_deposited: 0,
-        liquid_balance: 0,
-        reward_pool_balance: 0,
-        platform_pool_balance: 0,
-        reward_fee_bps: TreasuryPool::REWARD_FEE_BPS,
-        platform_fee_bps: TreasuryPool::PLATFORM_FEE_BPS,
-        admin: ctx.accounts.admin.key(),
-        dev_wallet: Pubkey::default(),
-        emergency_pause: false,
-        reward_pool_bump: 0,
-        platform_pool_bump: 0,
-        bump: ctx.bumps.treasury_pool,
-        // Legacy fields
-        backer_total_staked: 0,
-        backer_stake_pool_bump: 0,
-        total_rewards_distributed: 0,
-        admin_pool_balance: 0,
-        admin_pool_bump: 0,
-        current_apy_bps: 0,
-        last_apy_update_ts: 0,
-        last_distribution_time: 0,
-        total_staked: 0,
-        total_fees_collected: 0,
-        current_apy: 0,
-        treasury_wallet: Pubkey::default(),
-    };
-    
-    // Try to read from old data if possible
-    // We'll use a safe approach: try to deserialize old layout, if fails use defaults
-    if old_pool_data.len() >= 8 {
-        // Skip discriminator (8 bytes)
-        let mut cursor = 8;
-        
-        // Try to read known fields (be careful with offsets)
-        // This is a simplified migration - in production, you'd want to be more careful
-        // For now, we'll try to deserialize the old struct and copy fields
-        
-        // Try Anchor's deserialize with error handling
-        if let Ok(old_pool) = TreasuryPool::try_deserialize(&mut &old_pool_data[..]) {
-            // Successfully deserialized old layout
-            new_pool.reward_per_share = old_pool.reward_per_share;
-            new_pool.total_deposited = old_pool.total_deposited;
-            new_pool.liquid_balance = old_pool.liquid_balance;
-            new_pool.reward_pool_balance = old_pool.reward_pool_balance;
-            new_pool.platform_pool_balance = old_pool.platform_pool_balance;
-            new_pool.reward_fee_bps = old_pool.reward_fee_bps;
-            new_pool.platform_fee_bps = old_pool.platform_fee_bps;
-            new_pool.admin = old_pool.admin;
-            new_pool.dev_wallet = old_pool.dev_wallet;
-            new_pool.emergency_pause = old_pool.emergency_pause;
-            new_pool.reward_pool_bump = old_pool.reward_pool_bump;
-            new_pool.platform_pool_bump = old_pool.platform_pool_bump;
-            new_pool.bump = old_pool.bump;
-            // Copy legacy fields
-            new_pool.backer_total_staked = old_pool.backer_total_staked;
-            new_pool.backer_stake_pool_bump = old_pool.backer_stake_pool_bump;
-            new_pool.total_rewards_distributed = old_pool.total_rewards_distributed;
-            new_pool.admin_pool_balance = old_pool.admin_pool_balance;
-            new_pool.admin_pool_bump = old_pool.admin_pool_bump;
-            new_pool.current_apy_bps = old_pool.current_apy_bps;
-            new_pool.last_apy_update_ts = old_pool.last_apy_update_ts;
-            new_pool.last_distribution_time = old_pool.last_distribution_time;
-            new_pool.total_staked = old_pool.total_staked;
-            new_pool.total_fees_collected = old_pool.total_fees_collected;
-            new_pool.current_apy = old_pool.current_apy;
-            new_pool.treasury_wallet = old_pool.treasury_wallet;
-            
-            msg!("[MIGRATE] Successfully read old pool data");
-        } else {
-            msg!("[MIGRATE] Could not deserialize old layout, using current account data");
-            // Try to read from current account (might already be partially migrated)
-            if let Ok(current_pool) = TreasuryPool::try_deserialize(&mut &treasury_pool_info.data.borrow()[..]) {
-                new_pool = current_pool;
-            }
-        }
+
+    // Fields added since the old layout that must not start at zero
+    if old_layout_version < 3 {
+        pool.max_reward_split_bps = 10000;
+    }
+    if old_layout_version < 5 {
+        // Unclaimed rewards cannot be summed on-chain: protect the whole tracked balance
+        pool.total_claimable = pool.reward_pool_balance;
+    }
+
+    // Write preserved fields back in the current layout
+    pool.layout_version = TreasuryPool::LAYOUT_VERSION;
+    {
+        let mut data = treasury_pool_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..];
+        pool.try_serialize(&mut writer)?;
     }
-    
-    // Serialize new layout
-    new_pool.try_serialize(&mut &mut data[..])?;
-    
-    msg!("[MIGRATE] Migration completed successfully");
-    msg!("[MIGRATE] liquid_balance: {} lamports", new_pool.liquid_balance);
-    msg!("[MIGRATE] total_deposited: {} lamports", new_pool.total_deposited);
-    
+
+    msg!("[MIGRATE] Migration completed: layout {} -> {}", old_layout_version, pool.layout_version);
+    msg!("[MIGRATE] liquid_balance: {} lamports", pool.liquid_balance);
+    msg!("[MIGRATE] total_deposited: {} lamports", pool.total_deposited);
+
+    emit!(TreasuryPoolMigrated {
+        old_size: current_space as u64,
+        new_size: treasury_pool_info.data_len() as u64,
+        old_layout_version,
+        layout_version: pool.layout_version,
+        total_deposited: pool.total_deposited,
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
-
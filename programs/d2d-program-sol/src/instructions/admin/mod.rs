@@ -1,33 +1,121 @@
 pub mod admin_withdraw;
 pub mod admin_withdraw_reward_pool;
+pub mod backstop_reward_pool;
+pub mod blacklist_developer;
+pub mod claim_rewards_batch;
+pub mod clear_undercollateralization;
+pub mod close_deploy_request;
 pub mod close_program_and_refund;
 pub mod close_treasury_pool;
 pub mod confirm_deployment;
 pub mod create_deploy_request;
 pub mod credit_fee_to_pool;
 pub mod emergency_pause;
+pub mod emergency_rotate_keys;
+pub mod force_close_abandoned;
+pub mod force_recover_ephemeral;
+pub mod fund_spl_rewards;
 pub mod fund_temporary_wallet;
+pub mod fund_temporary_wallets_batch;
+pub mod grant_subscription_credit;
+pub mod initialize_config;
+pub mod initialize_reward_history;
+pub mod initialize_token_treasury;
 pub mod migrate_treasury_pool;
+pub mod move_platform_to_reward;
+pub mod reconcile_pools;
 pub mod reinitialize_treasury_pool;
 pub mod reset_treasury_pool;
+pub mod set_claim_cooldown;
+pub mod set_deploy_limits;
+pub mod set_deployment_cost_cap;
+pub mod set_deployments_paused;
+pub mod set_emergency_withdraw_fee_bps;
+pub mod set_ephemeral_recovery_timeout;
+pub mod set_failure_slash;
+pub mod set_fee_rates;
+pub mod set_grace_period;
+pub mod set_max_lapse;
+pub mod set_deposit_cap;
+pub mod set_min_claim;
+pub mod set_min_deposit;
+pub mod set_monthly_fee_bps;
+pub mod set_outflow_breaker;
+pub mod set_pause_flags;
+pub mod set_penalty_bps;
+pub mod set_pool_cap;
+pub mod set_recovery_authority;
+pub mod set_recovery_platform_bps;
+pub mod set_reward_admin;
+pub mod set_reward_pool_isolated;
+pub mod set_reward_split_bounds;
 pub mod suspend_expired_programs;
+pub mod sweep_dust;
 pub mod sync_liquid_balance;
+pub mod transfer_admin;
+pub mod unblacklist_developer;
 pub mod update_apy;
+pub mod update_config;
+pub mod withdraw_platform_pool;
 pub mod force_rebalance;
 
 pub use admin_withdraw::*;
 pub use admin_withdraw_reward_pool::*;
+pub use backstop_reward_pool::*;
+pub use blacklist_developer::*;
+pub use claim_rewards_batch::*;
+pub use clear_undercollateralization::*;
+pub use close_deploy_request::*;
 pub use close_program_and_refund::*;
 pub use close_treasury_pool::*;
 pub use confirm_deployment::*;
 pub use create_deploy_request::*;
 pub use credit_fee_to_pool::*;
 pub use emergency_pause::*;
+pub use emergency_rotate_keys::*;
+pub use force_close_abandoned::*;
+pub use force_recover_ephemeral::*;
+pub use fund_spl_rewards::*;
 pub use fund_temporary_wallet::*;
+pub use fund_temporary_wallets_batch::*;
+pub use grant_subscription_credit::*;
+pub use initialize_config::*;
+pub use initialize_reward_history::*;
+pub use initialize_token_treasury::*;
 pub use migrate_treasury_pool::*;
+pub use move_platform_to_reward::*;
+pub use reconcile_pools::*;
 pub use reinitialize_treasury_pool::*;
 pub use reset_treasury_pool::*;
+pub use set_claim_cooldown::*;
+pub use set_deploy_limits::*;
+pub use set_deployment_cost_cap::*;
+pub use set_deployments_paused::*;
+pub use set_emergency_withdraw_fee_bps::*;
+pub use set_ephemeral_recovery_timeout::*;
+pub use set_failure_slash::*;
+pub use set_fee_rates::*;
+pub use set_grace_period::*;
+pub use set_max_lapse::*;
+pub use set_deposit_cap::*;
+pub use set_min_claim::*;
+pub use set_min_deposit::*;
+pub use set_monthly_fee_bps::*;
+pub use set_outflow_breaker::*;
+pub use set_pause_flags::*;
+pub use set_penalty_bps::*;
+pub use set_pool_cap::*;
+pub use set_recovery_authority::*;
+pub use set_recovery_platform_bps::*;
+pub use set_reward_admin::*;
+pub use set_reward_pool_isolated::*;
+pub use set_reward_split_bounds::*;
 pub use suspend_expired_programs::*;
+pub use sweep_dust::*;
 pub use sync_liquid_balance::*;
+pub use transfer_admin::*;
+pub use unblacklist_developer::*;
 pub use update_apy::*;
+pub use update_config::*;
+pub use withdraw_platform_pool::*;
 pub use force_rebalance::*;
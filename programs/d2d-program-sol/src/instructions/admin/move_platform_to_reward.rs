@@ -0,0 +1,100 @@
+use crate::errors::ErrorCode;
+use crate::events::AdminMovedToRewardPool;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin move surplus platform fees to the backers
+///
+/// Platform Pool PDA -> Reward Pool PDA, credited through reward_per_share
+/// exactly like a reward fee in credit_fee_to_pool.
+#[derive(Accounts)]
+pub struct MovePlatformToReward<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Platform Pool PDA (program-owned, holds 0.1% fees)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (receives the moved funds)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Move platform fees to the reward pool
+///
+/// Flow:
+/// 1. Check platform_pool_balance and Platform Pool PDA lamports (above rent) cover the amount
+/// 2. Transfer Platform Pool PDA -> Reward Pool PDA via lamport mutation
+/// 3. platform_pool_balance -= amount
+/// 4. credit_fee_to_pool(amount, 0): reward_pool_balance += amount (less truncation dust),
+///    reward_per_share += amount * PRECISION / total_deposited
+pub fn move_platform_to_reward(ctx: Context<MovePlatformToReward>, amount: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let platform_pool_info = ctx.accounts.platform_pool.to_account_info();
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    // Nobody to distribute to - funds would sit in the reward pool unclaimable
+    require!(treasury_pool.total_deposited > 0, ErrorCode::DivisionByZero);
+    require!(
+        treasury_pool.platform_pool_balance >= amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    // Keep the Platform Pool PDA rent-exempt
+    let rent_exemption = Rent::get()?.minimum_balance(platform_pool_info.data_len());
+    require!(
+        platform_pool_info.lamports().saturating_sub(rent_exemption) >= amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    {
+        let mut platform_pool_lamports = platform_pool_info.try_borrow_mut_lamports()?;
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+
+        **platform_pool_lamports = (**platform_pool_lamports)
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    treasury_pool.platform_pool_balance = treasury_pool
+        .platform_pool_balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    // Not recorded in the fee-velocity window: a one-off redistribution, not recurring fee income
+    treasury_pool.credit_fee_to_pool(amount, 0)?;
+
+    msg!("[ADMIN_MOVE_PLATFORM] Moved {} lamports from Platform Pool to Reward Pool", amount);
+    msg!("[ADMIN_MOVE_PLATFORM] reward_per_share: {}", treasury_pool.reward_per_share);
+
+    emit!(AdminMovedToRewardPool {
+        admin: ctx.accounts.admin.key(),
+        amount,
+        moved_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::errors::ErrorCode;
+use crate::events::PoolsReconciled;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin compare tracked pool balances against the lamports actually held by each PDA
+///
+/// - Treasury PDA: liquid_balance
+/// - Reward Pool PDA: reward_pool_balance + prepaid_fees_balance (escrow lives in the Reward Pool)
+/// - Platform Pool PDA: platform_pool_balance
+///
+/// Without `force`, any pool drifting by more than `tolerance` lamports fails with PoolBalanceMismatch.
+/// With `force`, the tracked balances are reset to match the lamports (minus rent).
+#[derive(Accounts)]
+pub struct ReconcilePools<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (lamports read only)
+    #[account(
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Platform Pool PDA (lamports read only)
+    #[account(
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Lamports above the rent-exempt minimum
+fn available_lamports(account: &AccountInfo, rent: &Rent) -> u64 {
+    account
+        .lamports()
+        .saturating_sub(rent.minimum_balance(account.data_len()))
+}
+
+pub fn reconcile_pools(ctx: Context<ReconcilePools>, tolerance: u64, force: bool) -> Result<()> {
+    let rent = Rent::get()?;
+    let treasury_available = available_lamports(&ctx.accounts.treasury_pool.to_account_info(), &rent);
+    let reward_available = available_lamports(&ctx.accounts.reward_pool.to_account_info(), &rent);
+    let platform_available = available_lamports(&ctx.accounts.platform_pool.to_account_info(), &rent);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let liquid_before = treasury_pool.liquid_balance;
+    let reward_before = treasury_pool.reward_pool_balance;
+    let platform_before = treasury_pool.platform_pool_balance;
+
+    let reward_tracked = reward_before
+        .checked_add(treasury_pool.prepaid_fees_balance)
+        .and_then(|x| x.checked_add(treasury_pool.undistributed_dust))
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("[RECONCILE] Treasury: tracked {} / actual {}", liquid_before, treasury_available);
+    msg!("[RECONCILE] Reward Pool: tracked {} / actual {}", reward_tracked, reward_available);
+    msg!("[RECONCILE] Platform Pool: tracked {} / actual {}", platform_before, platform_available);
+
+    if force {
+        // Same guard as sync_liquid_balance: never reset liquid_balance mid-deployment
+        require!(treasury_pool.total_borrowed == 0, ErrorCode::BorrowedFundsOutstanding);
+        treasury_pool.liquid_balance = treasury_available;
+        // Escrow is owed to developers and dust awaits sweep_dust; only the backer share absorbs the drift
+        treasury_pool.reward_pool_balance = reward_available
+            .saturating_sub(treasury_pool.prepaid_fees_balance)
+            .saturating_sub(treasury_pool.undistributed_dust);
+        treasury_pool.platform_pool_balance = platform_available;
+        msg!("[RECONCILE] Tracked balances reset to match lamports");
+    } else {
+        require!(
+            liquid_before.abs_diff(treasury_available) <= tolerance
+                && reward_tracked.abs_diff(reward_available) <= tolerance
+                && platform_before.abs_diff(platform_available) <= tolerance,
+            ErrorCode::PoolBalanceMismatch
+        );
+    }
+
+    emit!(PoolsReconciled {
+        liquid_before,
+        liquid_after: treasury_pool.liquid_balance,
+        reward_pool_before: reward_before,
+        reward_pool_after: treasury_pool.reward_pool_balance,
+        platform_pool_before: platform_before,
+        platform_pool_after: treasury_pool.platform_pool_balance,
+        forced: force,
+        reconciled_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
+use crate::errors::ErrorCode;
 use crate::events::TreasuryInitialized;
 use crate::states::TreasuryPool;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 
 /// Reinitialize Treasury Pool (Admin only)
 /// 
@@ -8,6 +10,7 @@ use anchor_lang::prelude::*;
 /// It works even if the account has old layout or is rent-exempt.
 /// 
 /// This is used after closing the old account to migrate to new layout.
+/// reserved_padding over-allocates the account so future fields fit without another migration.
 #[derive(Accounts)]
 pub struct ReinitializeTreasuryPool<'info> {
     /// CHECK: Treasury Pool PDA - will be reinitialized
@@ -53,15 +56,35 @@ pub fn reinitialize_treasury_pool(
     ctx: Context<ReinitializeTreasuryPool>,
     _initial_apy: u64, // Legacy parameter, not used in new model
     dev_wallet: Pubkey,
+    reserved_padding: u32,
 ) -> Result<()> {
+    require!(
+        reserved_padding <= TreasuryPool::MAX_RESERVED_PADDING,
+        ErrorCode::InvalidAmount
+    );
+
     let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
-    let required_space = 8 + TreasuryPool::INIT_SPACE;
+    let required_space = TreasuryPool::space_with_padding(reserved_padding);
     
     // Resize account if needed
     let current_space = treasury_pool_info.data_len();
     if current_space < required_space {
         msg!("[REINIT] Resizing account from {} to {} bytes", current_space, required_space);
         treasury_pool_info.realloc(required_space, false)?;
+
+        // Top up rent for the larger account from admin
+        let rent_required = Rent::get()?.minimum_balance(required_space);
+        let current_lamports = treasury_pool_info.lamports();
+        if current_lamports < rent_required {
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: treasury_pool_info.clone(),
+                },
+            );
+            system_program::transfer(cpi_context, rent_required - current_lamports)?;
+        }
     }
     
     // Zero out the account data to ensure clean initialization
@@ -96,10 +119,88 @@ pub fn reinitialize_treasury_pool(
         total_fees_collected: 0,
         current_apy: 0,
         treasury_wallet: Pubkey::default(),
+        // Solvency tracking
+        total_borrowed: 0,
+        undercollateralized: false,
+        // Governance
+        proposal_count: 0,
+        // Admin transfer
+        pending_admin: None,
+        // Key management
+        reward_admin: TreasuryPool::DEFAULT_REWARD_ADMIN,
+        recovery_authority: None,
+        // Deposit limits
+        max_deposit_per_backer: 0,
+        min_deposit: TreasuryPool::DEFAULT_MIN_DEPOSIT,
+        // Fee velocity (estimates only)
+        fee_window_start: 0,
+        fees_credited_current_window: 0,
+        fees_credited_last_window: 0,
+        last_window_seconds: 0,
+        // Lock-period staking
+        penalty_bps: TreasuryPool::DEFAULT_PENALTY_BPS,
+        // Deployment halt
+        deployments_paused: false,
+        // Reward pool isolation
+        reward_pool_isolated: false,
+        // Monthly fee accrual
+        prepaid_fees_balance: 0,
+        // Insurance backstop
+        emergency_withdraw_fee_bps: 0,
+        insurance_pool_balance: 0,
+        // Per-operation pause
+        paused_ops: 0,
+        // Claim limits
+        min_claim_amount: 0,
+        // Deploy session limits
+        max_concurrent_sessions: TreasuryPool::DEFAULT_MAX_CONCURRENT_SESSIONS,
+        max_daily_deploys: TreasuryPool::DEFAULT_MAX_DAILY_DEPLOYS,
+        // Subscription grace period
+        grace_period_secs: TreasuryPool::DEFAULT_GRACE_PERIOD_SECS,
+        // Stuck deployment recovery
+        ephemeral_recovery_timeout_secs: TreasuryPool::DEFAULT_EPHEMERAL_RECOVERY_TIMEOUT_SECS,
+        // Recovery split
+        recovery_platform_bps: 0,
+        // Pool cap
+        max_total_deposited: 0,
+        // Failure slashing
+        failure_slash_threshold: 0,
+        failure_slash_bps: 0,
+        // Deployment escrow
+        escrowed_amount: 0,
+        // Reward dust
+        undistributed_dust: 0,
+        // Reward statistics
+        total_rewards_claimed: 0,
+        // Subscription lapse
+        max_lapse_secs: 0,
+        // Deployment losses
+        total_deployment_losses: 0,
+        // Layout
+        layout_version: TreasuryPool::LAYOUT_VERSION,
+        // Monthly fee
+        monthly_fee_bps: 0,
+        // Unallocated rewards
+        unallocated_rewards: 0,
+        // Per-request fee split bounds
+        min_reward_split_bps: 0,
+        max_reward_split_bps: 10000,
+        // Claim cooldown
+        claim_cooldown_secs: 0,
+        // Reward accounting
+        total_claimable: 0,
+        // Deployment cost ceiling
+        max_deployment_cost: 0,
+        max_deployment_bps: 0,
+        // Reward outflow circuit breaker
+        max_outflow_per_window: 0,
+        outflow_window_secs: 0,
+        outflow_window_start: 0,
+        outflow_in_window: 0,
     };
 
     msg!("[REINIT] Reinitializing Treasury Pool with new layout");
-    msg!("[REINIT] Account size: {} bytes", required_space);
+    msg!("[REINIT] Account size: {} bytes (reserved padding: {})", treasury_pool_info.data_len(), reserved_padding);
     msg!("[REINIT] Admin: {}", ctx.accounts.admin.key());
     msg!("[REINIT] Dev wallet: {}", dev_wallet);
     msg!("[REINIT] Bumps - treasury: {}, reward: {}, platform: {}", 
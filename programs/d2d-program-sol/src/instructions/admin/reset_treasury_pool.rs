@@ -17,7 +17,8 @@ pub struct ResetTreasuryPool<'info> {
         mut,
         close = admin, // Close old account and send lamports to admin
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
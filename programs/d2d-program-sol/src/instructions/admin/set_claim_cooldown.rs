@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::events::ClaimCooldownUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set how long after a stake_sol deposit the backer must wait before claiming (seconds)
+/// Bounded by TreasuryPool::MAX_CLAIM_COOLDOWN_SECS; 0 turns the cooldown off
+#[derive(Accounts)]
+pub struct SetClaimCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_claim_cooldown(ctx: Context<SetClaimCooldown>, claim_cooldown_secs: i64) -> Result<()> {
+    require!(
+        (0..=TreasuryPool::MAX_CLAIM_COOLDOWN_SECS).contains(&claim_cooldown_secs),
+        ErrorCode::InvalidAmount
+    );
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_claim_cooldown_secs = treasury_pool.claim_cooldown_secs;
+    treasury_pool.claim_cooldown_secs = claim_cooldown_secs;
+
+    msg!("[ADMIN] Claim cooldown updated: {} -> {} seconds", old_claim_cooldown_secs, claim_cooldown_secs);
+
+    emit!(ClaimCooldownUpdated {
+        old_claim_cooldown_secs,
+        new_claim_cooldown_secs: claim_cooldown_secs,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
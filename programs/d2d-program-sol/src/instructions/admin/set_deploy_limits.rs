@@ -0,0 +1,50 @@
+use crate::errors::ErrorCode;
+use crate::events::DeployLimitsUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the per-developer deploy limits
+/// Both limits must be non-zero (0 would block every developer)
+#[derive(Accounts)]
+pub struct SetDeployLimits<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_deploy_limits(
+    ctx: Context<SetDeployLimits>,
+    max_concurrent_sessions: u32,
+    max_daily_deploys: u32,
+) -> Result<()> {
+    require!(max_concurrent_sessions > 0, ErrorCode::InvalidAmount);
+    require!(max_daily_deploys > 0, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_max_concurrent_sessions = treasury_pool.max_concurrent_sessions;
+    let old_max_daily_deploys = treasury_pool.max_daily_deploys;
+    treasury_pool.max_concurrent_sessions = max_concurrent_sessions;
+    treasury_pool.max_daily_deploys = max_daily_deploys;
+
+    msg!("[ADMIN] Max concurrent sessions updated: {} -> {}", old_max_concurrent_sessions, max_concurrent_sessions);
+    msg!("[ADMIN] Max daily deploys updated: {} -> {}", old_max_daily_deploys, max_daily_deploys);
+
+    emit!(DeployLimitsUpdated {
+        old_max_concurrent_sessions,
+        new_max_concurrent_sessions: max_concurrent_sessions,
+        old_max_daily_deploys,
+        new_max_daily_deploys: max_daily_deploys,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::errors::ErrorCode;
+use crate::events::DeploymentCostCapUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the ceiling on a single request's deployment_cost
+/// max_deployment_cost in lamports, max_deployment_bps as a share of liquid_balance; 0 disables either
+#[derive(Accounts)]
+pub struct SetDeploymentCostCap<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_deployment_cost_cap(
+    ctx: Context<SetDeploymentCostCap>,
+    max_deployment_cost: u64,
+    max_deployment_bps: u64,
+) -> Result<()> {
+    require!(max_deployment_bps <= 10000, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_max_deployment_cost = treasury_pool.max_deployment_cost;
+    let old_max_deployment_bps = treasury_pool.max_deployment_bps;
+    treasury_pool.max_deployment_cost = max_deployment_cost;
+    treasury_pool.max_deployment_bps = max_deployment_bps;
+
+    msg!("[ADMIN] Max deployment cost updated: {} -> {} lamports", old_max_deployment_cost, max_deployment_cost);
+    msg!("[ADMIN] Max deployment bps updated: {} -> {}", old_max_deployment_bps, max_deployment_bps);
+
+    emit!(DeploymentCostCapUpdated {
+        old_max_deployment_cost,
+        new_max_deployment_cost: max_deployment_cost,
+        old_max_deployment_bps,
+        new_max_deployment_bps: max_deployment_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
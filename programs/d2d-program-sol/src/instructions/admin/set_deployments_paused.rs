@@ -0,0 +1,36 @@
+use crate::errors::ErrorCode;
+use crate::events::DeploymentsPauseToggled;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin halt/resume new deployments
+/// While halted, developers with unfunded pending requests can refund_on_halt
+#[derive(Accounts)]
+pub struct SetDeploymentsPaused<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_deployments_paused(ctx: Context<SetDeploymentsPaused>, paused: bool) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    treasury_pool.deployments_paused = paused;
+
+    msg!("[ADMIN] Deployments paused: {}", paused);
+
+    emit!(DeploymentsPauseToggled {
+        paused,
+        toggled_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
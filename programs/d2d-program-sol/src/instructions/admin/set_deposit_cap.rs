@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::events::DepositCapUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the maximum deposited_amount per backer (0 = unlimited)
+/// Lowering the cap does not unwind existing deposits, it only blocks new ones
+#[derive(Accounts)]
+pub struct SetDepositCap<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_deposit_cap(ctx: Context<SetDepositCap>, max_deposit_per_backer: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_cap = treasury_pool.max_deposit_per_backer;
+    treasury_pool.max_deposit_per_backer = max_deposit_per_backer;
+
+    msg!("[ADMIN] Deposit cap updated: {} -> {} lamports", old_cap, max_deposit_per_backer);
+
+    emit!(DepositCapUpdated {
+        old_cap,
+        new_cap: max_deposit_per_backer,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::events::EmergencyWithdrawFeeUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the emergency withdrawal fee (basis points of the payout)
+/// Applies only to emergency_unstake, bounded by TreasuryPool::MAX_EMERGENCY_WITHDRAW_FEE_BPS
+#[derive(Accounts)]
+pub struct SetEmergencyWithdrawFeeBps<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_emergency_withdraw_fee_bps(ctx: Context<SetEmergencyWithdrawFeeBps>, fee_bps: u64) -> Result<()> {
+    require!(
+        fee_bps <= TreasuryPool::MAX_EMERGENCY_WITHDRAW_FEE_BPS,
+        ErrorCode::InvalidAmount
+    );
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_fee_bps = treasury_pool.emergency_withdraw_fee_bps;
+    treasury_pool.emergency_withdraw_fee_bps = fee_bps;
+
+    msg!("[ADMIN] Emergency withdrawal fee updated: {} -> {} bps", old_fee_bps, fee_bps);
+
+    emit!(EmergencyWithdrawFeeUpdated {
+        old_fee_bps,
+        new_fee_bps: fee_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
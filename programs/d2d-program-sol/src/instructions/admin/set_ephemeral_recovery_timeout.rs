@@ -0,0 +1,42 @@
+use crate::errors::ErrorCode;
+use crate::events::EphemeralRecoveryTimeoutUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set how long after created_at a hung deployment can be swept by force_recover_ephemeral (seconds)
+#[derive(Accounts)]
+pub struct SetEphemeralRecoveryTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_ephemeral_recovery_timeout(
+    ctx: Context<SetEphemeralRecoveryTimeout>,
+    timeout_secs: i64,
+) -> Result<()> {
+    require!(timeout_secs > 0, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_timeout_secs = treasury_pool.ephemeral_recovery_timeout_secs;
+    treasury_pool.ephemeral_recovery_timeout_secs = timeout_secs;
+
+    msg!("[ADMIN] Ephemeral recovery timeout updated: {} -> {} seconds", old_timeout_secs, timeout_secs);
+
+    emit!(EphemeralRecoveryTimeoutUpdated {
+        old_timeout_secs,
+        new_timeout_secs: timeout_secs,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::errors::ErrorCode;
+use crate::events::FailureSlashUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the repeated-failure slashing policy
+/// Once a developer's failed_deploys exceeds `threshold`, confirm_deployment_failure keeps
+/// `slash_bps` of the refund in the Platform Pool (bounded by MAX_FAILURE_SLASH_BPS, 0 = off)
+#[derive(Accounts)]
+pub struct SetFailureSlash<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_failure_slash(ctx: Context<SetFailureSlash>, threshold: u32, slash_bps: u64) -> Result<()> {
+    require!(
+        slash_bps <= TreasuryPool::MAX_FAILURE_SLASH_BPS,
+        ErrorCode::InvalidAmount
+    );
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_threshold = treasury_pool.failure_slash_threshold;
+    let old_slash_bps = treasury_pool.failure_slash_bps;
+    treasury_pool.failure_slash_threshold = threshold;
+    treasury_pool.failure_slash_bps = slash_bps;
+
+    msg!("[ADMIN] Failure slash updated: threshold {} -> {}, {} -> {} bps", old_threshold, threshold, old_slash_bps, slash_bps);
+
+    emit!(FailureSlashUpdated {
+        old_threshold,
+        new_threshold: threshold,
+        old_slash_bps,
+        new_slash_bps: slash_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
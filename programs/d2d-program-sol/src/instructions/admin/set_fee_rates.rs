@@ -0,0 +1,50 @@
+use crate::errors::ErrorCode;
+use crate::events::FeeRatesUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the reward and platform fee rates (basis points)
+/// Each rate is bounded by TreasuryPool::MAX_FEE_BPS
+#[derive(Accounts)]
+pub struct SetFeeRates<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_fee_rates(
+    ctx: Context<SetFeeRates>,
+    reward_fee_bps: u64,
+    platform_fee_bps: u64,
+) -> Result<()> {
+    require!(reward_fee_bps <= TreasuryPool::MAX_FEE_BPS, ErrorCode::InvalidAmount);
+    require!(platform_fee_bps <= TreasuryPool::MAX_FEE_BPS, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_reward_fee_bps = treasury_pool.reward_fee_bps;
+    let old_platform_fee_bps = treasury_pool.platform_fee_bps;
+    treasury_pool.reward_fee_bps = reward_fee_bps;
+    treasury_pool.platform_fee_bps = platform_fee_bps;
+
+    msg!("[ADMIN] Reward fee updated: {} -> {} bps", old_reward_fee_bps, reward_fee_bps);
+    msg!("[ADMIN] Platform fee updated: {} -> {} bps", old_platform_fee_bps, platform_fee_bps);
+
+    emit!(FeeRatesUpdated {
+        old_reward_fee_bps,
+        new_reward_fee_bps: reward_fee_bps,
+        old_platform_fee_bps,
+        new_platform_fee_bps: platform_fee_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
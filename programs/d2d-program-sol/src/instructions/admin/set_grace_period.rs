@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::events::GracePeriodUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the grace period between subscription expiry and suspension (seconds)
+/// Bounded by TreasuryPool::MAX_GRACE_PERIOD_SECS; 0 suspends as soon as a subscription expires
+#[derive(Accounts)]
+pub struct SetGracePeriod<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_grace_period(ctx: Context<SetGracePeriod>, grace_period_secs: i64) -> Result<()> {
+    require!(
+        (0..=TreasuryPool::MAX_GRACE_PERIOD_SECS).contains(&grace_period_secs),
+        ErrorCode::InvalidAmount
+    );
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_grace_period_secs = treasury_pool.grace_period_secs;
+    treasury_pool.grace_period_secs = grace_period_secs;
+
+    msg!("[ADMIN] Grace period updated: {} -> {} seconds", old_grace_period_secs, grace_period_secs);
+
+    emit!(GracePeriodUpdated {
+        old_grace_period_secs,
+        new_grace_period_secs: grace_period_secs,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
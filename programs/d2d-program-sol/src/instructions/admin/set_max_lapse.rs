@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::events::MaxLapseUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the window after subscription expiry in which a Suspended request can be reinstated (seconds)
+/// Bounded by TreasuryPool::MAX_LAPSE_SECS; 0 requires a fresh deployment once suspended
+#[derive(Accounts)]
+pub struct SetMaxLapse<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_max_lapse(ctx: Context<SetMaxLapse>, max_lapse_secs: i64) -> Result<()> {
+    require!(
+        (0..=TreasuryPool::MAX_LAPSE_SECS).contains(&max_lapse_secs),
+        ErrorCode::InvalidAmount
+    );
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_max_lapse_secs = treasury_pool.max_lapse_secs;
+    treasury_pool.max_lapse_secs = max_lapse_secs;
+
+    msg!("[ADMIN] Max lapse updated: {} -> {} seconds", old_max_lapse_secs, max_lapse_secs);
+
+    emit!(MaxLapseUpdated {
+        old_max_lapse_secs,
+        new_max_lapse_secs: max_lapse_secs,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
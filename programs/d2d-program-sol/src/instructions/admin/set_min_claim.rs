@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::events::MinClaimUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the minimum claim_rewards amount (lamports)
+/// 0 restores the default (any non-zero claim); compound_rewards is not affected
+#[derive(Accounts)]
+pub struct SetMinClaim<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_min_claim(ctx: Context<SetMinClaim>, min_claim_amount: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_min_claim_amount = treasury_pool.min_claim_amount;
+    treasury_pool.min_claim_amount = min_claim_amount;
+
+    msg!("[ADMIN] Minimum claim updated: {} -> {} lamports", old_min_claim_amount, min_claim_amount);
+
+    emit!(MinClaimUpdated {
+        old_min_claim_amount,
+        new_min_claim_amount: min_claim_amount,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::events::MinDepositUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the minimum stake_sol deposit (lamports)
+/// Existing deposits below the new minimum are unaffected
+#[derive(Accounts)]
+pub struct SetMinDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_min_deposit(ctx: Context<SetMinDeposit>, min_deposit: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_min_deposit = treasury_pool.min_deposit;
+    treasury_pool.min_deposit = min_deposit;
+
+    msg!("[ADMIN] Minimum deposit updated: {} -> {} lamports", old_min_deposit, min_deposit);
+
+    emit!(MinDepositUpdated {
+        old_min_deposit,
+        new_min_deposit: min_deposit,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::errors::ErrorCode;
+use crate::events::MonthlyFeeBpsUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the monthly subscription fee as bps of deployment_cost
+/// create_deploy_request / request_deployment_funds then compute monthly_fee on-chain;
+/// 0 restores the legacy path where the caller passes monthly_fee explicitly
+#[derive(Accounts)]
+pub struct SetMonthlyFeeBps<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_monthly_fee_bps(ctx: Context<SetMonthlyFeeBps>, monthly_fee_bps: u64) -> Result<()> {
+    require!(monthly_fee_bps <= TreasuryPool::MAX_FEE_BPS, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_monthly_fee_bps = treasury_pool.monthly_fee_bps;
+    treasury_pool.monthly_fee_bps = monthly_fee_bps;
+
+    msg!("[ADMIN] Monthly fee updated: {} -> {} bps", old_monthly_fee_bps, monthly_fee_bps);
+
+    emit!(MonthlyFeeBpsUpdated {
+        old_monthly_fee_bps,
+        new_monthly_fee_bps: monthly_fee_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::events::OutflowBreakerUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin configure the Reward Pool outflow circuit breaker
+/// Claims and reward admin withdrawals beyond max_outflow_per_window within outflow_window_secs
+/// trip emergency_pause; 0 for either disables it. Changing the settings starts a fresh window.
+#[derive(Accounts)]
+pub struct SetOutflowBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_outflow_breaker(
+    ctx: Context<SetOutflowBreaker>,
+    max_outflow_per_window: u64,
+    outflow_window_secs: i64,
+) -> Result<()> {
+    require!(outflow_window_secs >= 0, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_max_outflow_per_window = treasury_pool.max_outflow_per_window;
+    let old_outflow_window_secs = treasury_pool.outflow_window_secs;
+    treasury_pool.max_outflow_per_window = max_outflow_per_window;
+    treasury_pool.outflow_window_secs = outflow_window_secs;
+    treasury_pool.outflow_window_start = 0;
+    treasury_pool.outflow_in_window = 0;
+
+    msg!("[ADMIN] Max outflow per window updated: {} -> {} lamports", old_max_outflow_per_window, max_outflow_per_window);
+    msg!("[ADMIN] Outflow window updated: {} -> {} seconds", old_outflow_window_secs, outflow_window_secs);
+
+    emit!(OutflowBreakerUpdated {
+        old_max_outflow_per_window,
+        new_max_outflow_per_window: max_outflow_per_window,
+        old_outflow_window_secs,
+        new_outflow_window_secs: outflow_window_secs,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::events::PauseFlagsUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin pause individual operations
+///
+/// paused_ops is a bitmask of TreasuryPool::PAUSE_* (deposit, withdraw, claim, deploy, pay_subscription).
+/// Setting every bit is equivalent to emergency_pause(true); any other value clears emergency_pause.
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_pause_flags(ctx: Context<SetPauseFlags>, paused_ops: u8) -> Result<()> {
+    require!(paused_ops & !TreasuryPool::PAUSE_ALL == 0, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_paused_ops = treasury_pool.paused_ops;
+    treasury_pool.paused_ops = paused_ops;
+    treasury_pool.emergency_pause = paused_ops == TreasuryPool::PAUSE_ALL;
+
+    msg!("[ADMIN] Pause flags updated: {:#07b} -> {:#07b}", old_paused_ops, paused_ops);
+
+    emit!(PauseFlagsUpdated {
+        old_paused_ops,
+        new_paused_ops: paused_ops,
+        emergency_pause: treasury_pool.emergency_pause,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::errors::ErrorCode;
+use crate::events::PenaltyBpsUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the early-unstake penalty (basis points of the payout)
+/// Bounded by TreasuryPool::MAX_PENALTY_BPS
+#[derive(Accounts)]
+pub struct SetPenaltyBps<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_penalty_bps(ctx: Context<SetPenaltyBps>, penalty_bps: u64) -> Result<()> {
+    require!(penalty_bps <= TreasuryPool::MAX_PENALTY_BPS, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_penalty_bps = treasury_pool.penalty_bps;
+    treasury_pool.penalty_bps = penalty_bps;
+
+    msg!("[ADMIN] Early-unstake penalty updated: {} -> {} bps", old_penalty_bps, penalty_bps);
+
+    emit!(PenaltyBpsUpdated {
+        old_penalty_bps,
+        new_penalty_bps: penalty_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::events::PoolCapUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the maximum total_deposited across all backers (0 = unlimited)
+/// Lowering the cap below total_deposited does not unwind deposits, it only blocks new ones
+#[derive(Accounts)]
+pub struct SetPoolCap<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_pool_cap(ctx: Context<SetPoolCap>, max_total_deposited: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_cap = treasury_pool.max_total_deposited;
+    treasury_pool.max_total_deposited = max_total_deposited;
+
+    msg!("[ADMIN] Pool cap updated: {} -> {} lamports", old_cap, max_total_deposited);
+
+    emit!(PoolCapUpdated {
+        old_cap,
+        new_cap: max_total_deposited,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::errors::ErrorCode;
+use crate::events::RecoveryAuthorityUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set (or clear) the recovery authority allowed to call emergency_rotate_keys
+#[derive(Accounts)]
+pub struct SetRecoveryAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_recovery_authority(
+    ctx: Context<SetRecoveryAuthority>,
+    recovery_authority: Option<Pubkey>,
+) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_authority = treasury_pool.recovery_authority;
+    treasury_pool.recovery_authority = recovery_authority;
+
+    emit!(RecoveryAuthorityUpdated {
+        old_authority,
+        new_authority: recovery_authority,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::events::RecoveryPlatformBpsUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the share of recovered deployment funds routed to the Platform Pool (basis points)
+/// Bounded by TreasuryPool::MAX_RECOVERY_PLATFORM_BPS; 0 returns everything to liquid_balance
+#[derive(Accounts)]
+pub struct SetRecoveryPlatformBps<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_recovery_platform_bps(ctx: Context<SetRecoveryPlatformBps>, recovery_platform_bps: u64) -> Result<()> {
+    require!(
+        recovery_platform_bps <= TreasuryPool::MAX_RECOVERY_PLATFORM_BPS,
+        ErrorCode::InvalidAmount
+    );
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_recovery_platform_bps = treasury_pool.recovery_platform_bps;
+    treasury_pool.recovery_platform_bps = recovery_platform_bps;
+
+    msg!("[ADMIN] Recovery platform share updated: {} -> {} bps", old_recovery_platform_bps, recovery_platform_bps);
+
+    emit!(RecoveryPlatformBpsUpdated {
+        old_recovery_platform_bps,
+        new_recovery_platform_bps: recovery_platform_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
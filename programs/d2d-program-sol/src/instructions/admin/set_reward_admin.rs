@@ -0,0 +1,41 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardAdminUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin rotate the reward admin allowed to call admin_withdraw_reward_pool
+/// On-chain rotation: no redeploy needed to replace DEFAULT_REWARD_ADMIN
+#[derive(Accounts)]
+pub struct SetRewardAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_reward_admin(ctx: Context<SetRewardAdmin>, new_reward_admin: Pubkey) -> Result<()> {
+    // Pubkey::default() would silently fall back to DEFAULT_REWARD_ADMIN
+    require!(new_reward_admin != Pubkey::default(), ErrorCode::InvalidAdmin);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_reward_admin = treasury_pool.effective_reward_admin();
+    treasury_pool.reward_admin = new_reward_admin;
+
+    msg!("[ADMIN] Reward admin updated: {} -> {}", old_reward_admin, new_reward_admin);
+
+    emit!(RewardAdminUpdated {
+        old_reward_admin,
+        new_reward_admin,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardPoolIsolationUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin toggle reward pool isolation
+/// While isolated, fund_temporary_wallet rejects Reward Pool funding requests
+#[derive(Accounts)]
+pub struct SetRewardPoolIsolated<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_reward_pool_isolated(ctx: Context<SetRewardPoolIsolated>, isolated: bool) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    treasury_pool.reward_pool_isolated = isolated;
+
+    msg!("[ADMIN] Reward pool isolated: {}", isolated);
+
+    emit!(RewardPoolIsolationUpdated {
+        isolated,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
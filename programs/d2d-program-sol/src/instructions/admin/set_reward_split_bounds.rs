@@ -0,0 +1,50 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardSplitBoundsUpdated;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin set the bounds a per-request reward_split_bps override is clamped to
+/// Both are basis points of the request's up-front fee (service_fee + platform fee)
+#[derive(Accounts)]
+pub struct SetRewardSplitBounds<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn set_reward_split_bounds(
+    ctx: Context<SetRewardSplitBounds>,
+    min_reward_split_bps: u64,
+    max_reward_split_bps: u64,
+) -> Result<()> {
+    require!(max_reward_split_bps <= 10000, ErrorCode::InvalidAmount);
+    require!(min_reward_split_bps <= max_reward_split_bps, ErrorCode::InvalidAmount);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let old_min_reward_split_bps = treasury_pool.min_reward_split_bps;
+    let old_max_reward_split_bps = treasury_pool.max_reward_split_bps;
+    treasury_pool.min_reward_split_bps = min_reward_split_bps;
+    treasury_pool.max_reward_split_bps = max_reward_split_bps;
+
+    msg!("[ADMIN] Reward split bounds updated: {}-{} -> {}-{} bps",
+         old_min_reward_split_bps, old_max_reward_split_bps, min_reward_split_bps, max_reward_split_bps);
+
+    emit!(RewardSplitBoundsUpdated {
+        old_min_reward_split_bps,
+        new_min_reward_split_bps: min_reward_split_bps,
+        old_max_reward_split_bps,
+        new_max_reward_split_bps: max_reward_split_bps,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,25 @@
 use crate::errors::ErrorCode;
 use crate::events::ProgramsSuspended;
-use crate::states::TreasuryPool;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
 use anchor_lang::prelude::*;
 
+/// Maximum deploy requests processed per call (keeps the instruction under the compute budget)
+pub const MAX_SUSPEND_BATCH: usize = 20;
+
+/// Admin suspend expired programs
+///
+/// DeployRequest accounts are passed as writable `remaining_accounts`.
+/// - Active and expired, within grace_period_secs -> SubscriptionExpired (pay_subscription recovers it)
+/// - Active or SubscriptionExpired past subscription_paid_until + grace_period_secs -> Suspended
+///
+/// Accounts that are not program-owned DeployRequests, not writable, or
+/// still within their subscription are skipped without error.
 #[derive(Accounts)]
 pub struct SuspendExpiredPrograms<'info> {
     #[account(
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     #[account(mut)]
@@ -24,12 +36,56 @@ pub fn suspend_expired_programs(ctx: Context<SuspendExpiredPrograms>) -> Result<
         ErrorCode::Unauthorized
     );
 
-    // This is a placeholder - in a real implementation, you would iterate through
-    // all DeployRequest accounts and suspend expired ones
-    // For now, we'll just emit an event
+    require!(
+        ctx.remaining_accounts.len() <= MAX_SUSPEND_BATCH,
+        ErrorCode::BatchTooLarge
+    );
+
+    let mut expired_count: u32 = 0;
+    let mut suspended_count: u32 = 0;
+    for account_info in ctx.remaining_accounts.iter() {
+        if account_info.owner != ctx.program_id || !account_info.is_writable {
+            msg!("[SUSPEND] Skipping {}: not a writable program account", account_info.key());
+            continue;
+        }
+
+        // Discriminator check happens in try_deserialize - skip anything else
+        let mut deploy_request = match DeployRequest::try_deserialize(&mut &account_info.data.borrow()[..]) {
+            Ok(deploy_request) => deploy_request,
+            Err(_) => {
+                msg!("[SUSPEND] Skipping {}: not a DeployRequest", account_info.key());
+                continue;
+            }
+        };
+
+        let was_active = deploy_request.status == DeployRequestStatus::Active;
+        if !was_active && deploy_request.status != DeployRequestStatus::SubscriptionExpired {
+            continue;
+        }
+
+        if deploy_request.is_past_grace(current_time, treasury_pool.grace_period_secs) {
+            deploy_request.status = DeployRequestStatus::Suspended;
+            msg!("[SUSPEND] Grace period elapsed, suspended: {}", account_info.key());
+            suspended_count = suspended_count
+                .checked_add(1)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        } else if was_active && deploy_request.is_in_grace(current_time, treasury_pool.grace_period_secs) {
+            deploy_request.status = DeployRequestStatus::SubscriptionExpired;
+            msg!("[SUSPEND] Subscription expired, in grace period: {}", account_info.key());
+            expired_count = expired_count
+                .checked_add(1)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        } else {
+            continue;
+        }
+
+        let mut data = account_info.try_borrow_mut_data()?;
+        deploy_request.try_serialize(&mut &mut data[..])?;
+    }
 
     emit!(ProgramsSuspended {
-        suspended_count: 0, // Would be calculated in real implementation
+        expired_count,
+        suspended_count,
         suspended_at: current_time,
     });
 
@@ -0,0 +1,88 @@
+use crate::errors::ErrorCode;
+use crate::events::DustSwept;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin reclaim reward_per_share truncation dust
+///
+/// Reward Pool PDA -> Platform Pool PDA for the lamports tracked in undistributed_dust,
+/// which no backer can claim.
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (holds the dust)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Platform Pool PDA (receives the dust)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+/// Sweep accumulated dust to the platform pool
+///
+/// Flow:
+/// 1. Check undistributed_dust > 0 and the Reward Pool PDA (above rent) covers it
+/// 2. Transfer Reward Pool PDA -> Platform Pool PDA via lamport mutation
+/// 3. platform_pool_balance += dust, undistributed_dust = 0
+pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let platform_pool_info = ctx.accounts.platform_pool.to_account_info();
+
+    let amount = treasury_pool.undistributed_dust;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // Keep the Reward Pool PDA rent-exempt
+    let rent_exemption = Rent::get()?.minimum_balance(reward_pool_info.data_len());
+    require!(
+        reward_pool_info.lamports().saturating_sub(rent_exemption) >= amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    {
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut platform_pool_lamports = platform_pool_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **platform_pool_lamports = (**platform_pool_lamports)
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    treasury_pool.undistributed_dust = 0;
+    treasury_pool.credit_platform_pool(amount as u128)?;
+
+    msg!("[ADMIN_SWEEP_DUST] Moved {} lamports of dust from Reward Pool to Platform Pool", amount);
+
+    emit!(DustSwept {
+        admin: ctx.accounts.admin.key(),
+        amount,
+        platform_pool_balance: treasury_pool.platform_pool_balance,
+        swept_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::errors::ErrorCode;
+use crate::events::LiquidBalanceSynced;
 use crate::states::TreasuryPool;
 use anchor_lang::prelude::*;
 
@@ -30,6 +31,10 @@ pub struct SyncLiquidBalance<'info> {
 /// 3. Updates liquid_balance to match (account_balance - rent_exemption)
 /// 
 /// This ensures liquid_balance reflects the actual available SOL in the account
+///
+/// Funds dispatched to ephemeral keys (total_borrowed) have already left the Treasury PDA, and
+/// confirm/fail/recover return them through liquid_balance. Syncing while any are in flight would
+/// race those returns, so the sync is refused until total_borrowed is back to zero.
 pub fn sync_liquid_balance(ctx: Context<SyncLiquidBalance>) -> Result<()> {
     // Verify treasury pool PDA manually
     let (expected_treasury_pool, _bump) = Pubkey::find_program_address(
@@ -49,6 +54,7 @@ pub fn sync_liquid_balance(ctx: Context<SyncLiquidBalance>) -> Result<()> {
     let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
     let mut treasury_pool = TreasuryPool::try_deserialize(&mut &treasury_pool_info.data.borrow()[..])
         .map_err(|_| ErrorCode::InvalidAccountData)?;
+    require!(treasury_pool.is_current_layout(), ErrorCode::StaleLayout);
 
     let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
 
@@ -59,6 +65,7 @@ pub fn sync_liquid_balance(ctx: Context<SyncLiquidBalance>) -> Result<()> {
     );
 
     require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(treasury_pool.total_borrowed == 0, ErrorCode::BorrowedFundsOutstanding);
 
     // Get actual account balance
     let actual_account_balance = treasury_pda_info.lamports();
@@ -73,6 +80,7 @@ pub fn sync_liquid_balance(ctx: Context<SyncLiquidBalance>) -> Result<()> {
         .ok_or(ErrorCode::CalculationOverflow)?;
     
     // Update liquid_balance to match available balance
+    let old_liquid_balance = treasury_pool.liquid_balance;
     treasury_pool.liquid_balance = available_balance;
 
     msg!("[SYNC] Synced liquid_balance with account balance");
@@ -85,5 +93,13 @@ pub fn sync_liquid_balance(ctx: Context<SyncLiquidBalance>) -> Result<()> {
     let mut data = treasury_pool_info.try_borrow_mut_data()?;
     treasury_pool.try_serialize(&mut &mut data[..])?;
 
+    emit!(LiquidBalanceSynced {
+        old_liquid_balance,
+        new_liquid_balance: available_balance,
+        account_lamports: actual_account_balance,
+        rent_exemption,
+        synced_at: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
\ No newline at end of file
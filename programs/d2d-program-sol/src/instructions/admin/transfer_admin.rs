@@ -0,0 +1,94 @@
+use crate::errors::ErrorCode;
+use crate::events::{AdminTransferCancelled, AdminTransferProposed, AdminTransferred};
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Two-step admin transfer
+///
+/// 1. Current admin proposes a new key (propose_admin)
+/// 2. The proposed key signs accept_admin to take over
+///
+/// A wrong key can never take control because it must sign to accept.
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    pub new_admin: Signer<'info>,
+}
+
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    require!(new_admin != Pubkey::default(), ErrorCode::InvalidAdmin);
+    require!(new_admin != treasury_pool.admin, ErrorCode::InvalidAdmin);
+
+    treasury_pool.pending_admin = Some(new_admin);
+    msg!("[ADMIN] Admin transfer proposed: {} -> {}", treasury_pool.admin, new_admin);
+
+    emit!(AdminTransferProposed {
+        current_admin: treasury_pool.admin,
+        pending_admin: new_admin,
+        proposed_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_admin_transfer(ctx: Context<ProposeAdmin>) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    let pending_admin = treasury_pool
+        .pending_admin
+        .take()
+        .ok_or(ErrorCode::NoPendingAdmin)?;
+    msg!("[ADMIN] Admin transfer to {} cancelled", pending_admin);
+
+    emit!(AdminTransferCancelled {
+        admin: treasury_pool.admin,
+        cancelled_admin: pending_admin,
+        cancelled_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let new_admin = ctx.accounts.new_admin.key();
+
+    let pending_admin = treasury_pool.pending_admin.ok_or(ErrorCode::NoPendingAdmin)?;
+    require!(new_admin == pending_admin, ErrorCode::Unauthorized);
+
+    let old_admin = treasury_pool.admin;
+    treasury_pool.admin = new_admin;
+    treasury_pool.pending_admin = None;
+    msg!("[ADMIN] Admin transferred: {} -> {}", old_admin, new_admin);
+
+    emit!(AdminTransferred {
+        old_admin,
+        new_admin,
+        transferred_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
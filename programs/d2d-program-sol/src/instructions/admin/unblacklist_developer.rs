@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode;
+use crate::events::DeveloperBlacklistUpdated;
+use crate::states::{Blacklist, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Admin remove a developer from the blacklist (closes the Blacklist PDA, rent back to admin)
+#[derive(Accounts)]
+pub struct UnblacklistDeveloper<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [Blacklist::PREFIX_SEED, blacklist.developer.as_ref()],
+        bump = blacklist.bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn unblacklist_developer(ctx: Context<UnblacklistDeveloper>) -> Result<()> {
+    let developer = ctx.accounts.blacklist.developer;
+
+    msg!("[ADMIN] Developer removed from blacklist: {}", developer);
+
+    emit!(DeveloperBlacklistUpdated {
+        developer,
+        blacklisted: false,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
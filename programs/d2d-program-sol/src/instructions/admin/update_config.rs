@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+use crate::states::{D2DConfig, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Admin update the D2DConfig tunables (fee_rate, max_concurrent_per_user, is_paused)
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [D2DConfig::PREFIX_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, D2DConfig>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+pub fn update_config(
+    ctx: Context<UpdateConfig>,
+    fee_rate: u64,
+    max_concurrent_per_user: u32,
+    is_paused: bool,
+) -> Result<()> {
+    require!(max_concurrent_per_user > 0, ErrorCode::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+
+    let old_fee_rate = config.fee_rate;
+    let old_max_concurrent_per_user = config.max_concurrent_per_user;
+    config.fee_rate = fee_rate;
+    config.max_concurrent_per_user = max_concurrent_per_user;
+    config.is_paused = is_paused;
+
+    msg!("[ADMIN] Config updated: fee_rate {} -> {}, max concurrent {} -> {}, paused: {}",
+         old_fee_rate, fee_rate, old_max_concurrent_per_user, max_concurrent_per_user, is_paused);
+
+    emit!(ConfigUpdated {
+        old_fee_rate,
+        new_fee_rate: fee_rate,
+        old_max_concurrent_per_user,
+        new_max_concurrent_per_user: max_concurrent_per_user,
+        is_paused,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
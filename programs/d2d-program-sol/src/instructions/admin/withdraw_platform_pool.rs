@@ -0,0 +1,99 @@
+use crate::errors::ErrorCode;
+use crate::events::AdminWithdrew;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Admin withdraw platform fees from Platform Pool
+///
+/// Checks and debits platform_pool_balance, which is what credit_fee_to_pool credits
+/// (admin_withdraw is the same withdrawal through the ADMIN_POOL_SEED alias).
+#[derive(Accounts)]
+pub struct WithdrawPlatformPool<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Platform Pool PDA (program-owned, holds 0.1% fees)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Destination wallet for withdrawal
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+/// Admin withdraw from Platform Pool
+///
+/// Flow:
+/// 1. Check platform_pool_balance covers the amount
+/// 2. Check Platform Pool PDA has the lamports above its rent exemption
+/// 3. Transfer from Platform Pool PDA -> destination (lamport mutation, PDA is program-owned)
+/// 4. Decrement platform_pool_balance
+pub fn withdraw_platform_pool(
+    ctx: Context<WithdrawPlatformPool>,
+    amount: u64,
+    reason: String,
+) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let platform_pool_info = ctx.accounts.platform_pool.to_account_info();
+    let destination_info = ctx.accounts.destination.to_account_info();
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        treasury_pool.platform_pool_balance >= amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    // Keep the PDA rent-exempt
+    let rent_exemption = Rent::get()?.minimum_balance(platform_pool_info.data_len());
+    require!(
+        platform_pool_info.lamports().saturating_sub(rent_exemption) >= amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    {
+        let mut platform_pool_lamports = platform_pool_info.try_borrow_mut_lamports()?;
+        let mut destination_lamports = destination_info.try_borrow_mut_lamports()?;
+
+        **platform_pool_lamports = (**platform_pool_lamports)
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **destination_lamports = (**destination_lamports)
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    treasury_pool.platform_pool_balance = treasury_pool
+        .platform_pool_balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("[ADMIN_WITHDRAW_PLATFORM] Admin {} withdrew {} lamports from Platform Pool",
+         ctx.accounts.admin.key(), amount);
+    msg!("[ADMIN_WITHDRAW_PLATFORM] Reason: {}", reason);
+    msg!("[ADMIN_WITHDRAW_PLATFORM] Remaining balance: {} lamports", treasury_pool.platform_pool_balance);
+
+    emit!(AdminWithdrew {
+        admin: ctx.accounts.admin.key(),
+        amount,
+        destination: destination_info.key(),
+        reason,
+        withdrawn_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
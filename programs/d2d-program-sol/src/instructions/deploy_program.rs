@@ -1,5 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::events::ProgramDeployed;
+use crate::events::{DailyStatsReset, ProgramDeployed};
 use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
@@ -9,14 +9,15 @@ use anchor_lang::system_program;
 pub struct DeployProgram<'info> {
     #[account(
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     #[account(
         init,
         payer = developer,
         space = 8 + DeployRequest::INIT_SPACE,
-        seeds = [DeployRequest::PREFIX_SEED, program_hash.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, developer.key().as_ref(), program_hash.as_ref()],
         bump
     )]
     pub deploy_request: Account<'info, DeployRequest>,
@@ -60,7 +61,8 @@ pub fn deploy_program(
     let user_stats = &mut ctx.accounts.user_stats;
     let current_time = Clock::get()?.unix_timestamp;
 
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPLOY), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.deployments_paused, ErrorCode::DeploymentsPaused);
     require!(service_fee > 0, ErrorCode::InvalidAmount);
     require!(monthly_fee > 0, ErrorCode::InvalidAmount);
     require!(initial_months > 0, ErrorCode::InvalidAmount);
@@ -78,14 +80,19 @@ pub fn deploy_program(
         user_stats.active_sessions = 0;
         user_stats.daily_deploys = 0;
         user_stats.total_deploys = 0;
+        user_stats.failed_deploys = 0;
         user_stats.last_reset = current_time;
         user_stats.bump = ctx.bumps.user_stats;
     }
 
     // Reset daily counter if new day
-    if current_time - user_stats.last_reset > 86400 {
-        user_stats.daily_deploys = 0;
-        user_stats.last_reset = current_time;
+    if let Some(previous_daily_deploys) = user_stats.reset_daily_if_due(current_time) {
+        emit!(DailyStatsReset {
+            user: user_stats.user,
+            previous_daily_deploys,
+            last_reset: user_stats.last_reset,
+            total_deploys: user_stats.total_deploys,
+        });
     }
 
     // Calculate total payment
@@ -100,11 +107,15 @@ pub fn deploy_program(
     deploy_request.deployment_cost = deployment_cost;
     deploy_request.subscription_paid_until =
         current_time + (initial_months as i64 * 30 * 24 * 60 * 60);
+    deploy_request.initial_months = initial_months;
     deploy_request.ephemeral_key = Some(ctx.accounts.ephemeral_key.key());
     deploy_request.deployed_program_id = None; // Will be set after actual deployment
+    deploy_request.deployed_at = None;
     deploy_request.status = DeployRequestStatus::PendingDeployment;
     deploy_request.created_at = current_time;
+    deploy_request.last_fee_accrual = current_time;
     deploy_request.bump = ctx.bumps.deploy_request;
+    deploy_request.funding_locked = true; // Funded below; released on confirm/fail
 
     // Update user stats
     user_stats.active_sessions += 1;
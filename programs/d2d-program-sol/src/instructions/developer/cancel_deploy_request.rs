@@ -0,0 +1,131 @@
+use crate::errors::ErrorCode;
+use crate::events::DeployRequestCancelled;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+
+/// Cancel a deploy request before deployment funds are dispatched
+/// Only the developer who owns the request can cancel it
+#[derive(Accounts)]
+pub struct CancelDeployRequest<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (holds developer payments, source of the refund)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, developer.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        mut,
+        constraint = developer.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub developer: Signer<'info>,
+}
+
+/// Cancel deploy request and refund service fee + unused prepaid months
+///
+/// Flow:
+/// 1. Verify request is PendingDeployment and no ephemeral key has been funded
+/// 2. Refund service_fee + monthly_fee * months_remaining from Reward Pool PDA -> developer
+///    (monthly portion from the request's escrow first, the rest from reward_pool_balance)
+/// 3. Accrue any escrow left for consumed months to backers and mark request Cancelled
+pub fn cancel_deploy_request(
+    ctx: Context<CancelDeployRequest>,
+    request_id: [u8; 32],
+) -> Result<()> {
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let developer_info = ctx.accounts.developer.to_account_info();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(
+        deploy_request.status == DeployRequestStatus::PendingDeployment,
+        ErrorCode::InvalidRequestStatus
+    );
+    require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
+    // Once fund_temporary_wallet has run, the deployment is in flight and must be
+    // resolved through confirm_deployment_success/failure instead
+    require!(
+        deploy_request.ephemeral_key.is_none(),
+        ErrorCode::DeploymentAlreadyFunded
+    );
+
+    // Calculate refund: service fee + unused prepaid months
+    let months_remaining = deploy_request.months_remaining(current_time);
+    let monthly_refund = deploy_request
+        .monthly_fee
+        .checked_mul(months_remaining)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let service_refund = deploy_request.service_fee;
+    let refund_amount = treasury_pool.refund_fees(deploy_request, service_refund, monthly_refund)?;
+    let leftover_escrow = deploy_request.prepaid_balance;
+    treasury_pool.accrue_prepaid_fees(deploy_request, leftover_escrow, current_time)?;
+
+    msg!("[CANCEL] Request cancelled by developer: {}", developer_info.key());
+    msg!("[CANCEL] Months remaining: {}, refund: {} lamports", months_remaining, refund_amount);
+
+    // Check Reward Pool has enough lamports for refund
+    require!(
+        reward_pool_info.lamports() >= refund_amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    // Refund developer from Reward Pool PDA via direct lamport manipulation
+    {
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut developer_lamports = developer_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(refund_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **developer_lamports = (**developer_lamports)
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    user_stats.active_sessions = user_stats.active_sessions.saturating_sub(1);
+    treasury_pool.release_deployment(deploy_request);
+
+    deploy_request.status = DeployRequestStatus::Cancelled;
+    deploy_request.subscription_paid_until = current_time;
+
+    emit!(DeployRequestCancelled {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        refund_amount,
+        months_refunded: months_remaining,
+        cancelled_at: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::errors::ErrorCode;
+use crate::states::{DeployRequest, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Maximum deploy requests per call; the bitmap (MAX / 8 bytes) stays far below the 1024-byte return data limit
+pub const MAX_SUBSCRIPTION_BATCH: usize = 256;
+
+/// Check subscription validity for many deploy requests at once (no state mutation)
+///
+/// DeployRequest accounts are passed as read-only `remaining_accounts`. Returned via
+/// return data so indexers can simulate it instead of re-implementing the grace-period
+/// math per account. Bit i of `valid_bitmap` (byte i / 8, bit i % 8) is set when
+/// remaining_accounts[i] is paid or still within grace_period_secs. Accounts that are
+/// not program-owned DeployRequests read as invalid.
+#[derive(Accounts)]
+pub struct CheckSubscriptionsBatch<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+}
+
+/// Return buffer of check_subscriptions_batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SubscriptionBatch {
+    pub checked_at: i64,       // Clock used for every account
+    pub count: u16,            // Number of accounts checked
+    pub valid_bitmap: Vec<u8>, // One bit per account, little-endian within each byte
+}
+
+pub fn check_subscriptions_batch(ctx: Context<CheckSubscriptionsBatch>) -> Result<SubscriptionBatch> {
+    let grace_period_secs = ctx.accounts.treasury_pool.grace_period_secs;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        ctx.remaining_accounts.len() <= MAX_SUBSCRIPTION_BATCH,
+        ErrorCode::BatchTooLarge
+    );
+
+    let count = ctx.remaining_accounts.len();
+    let mut valid_bitmap = vec![0u8; count.div_ceil(8)];
+    for (index, account_info) in ctx.remaining_accounts.iter().enumerate() {
+        if account_info.owner != ctx.program_id {
+            continue;
+        }
+        // Discriminator check happens in try_deserialize - anything else reads as invalid
+        let deploy_request = match DeployRequest::try_deserialize(&mut &account_info.data.borrow()[..]) {
+            Ok(deploy_request) => deploy_request,
+            Err(_) => continue,
+        };
+        if deploy_request.is_subscription_valid_at(current_time, grace_period_secs) {
+            valid_bitmap[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    msg!("[SUBSCRIPTIONS] Checked {} deploy requests at {}", count, current_time);
+
+    Ok(SubscriptionBatch {
+        checked_at: current_time,
+        count: count as u16,
+        valid_bitmap,
+    })
+}
@@ -0,0 +1,67 @@
+use crate::errors::ErrorCode;
+use crate::states::{D2DConfig, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Preview what a developer pays before create_deploy_request (no state mutation)
+///
+/// Returned via return data (`program.methods.estimateDeployCost(...).view()`) and computed with
+/// TreasuryPool::deploy_payment, the same arithmetic create_deploy_request credits, so front-ends
+/// do not re-derive monthly_fee_bps or the reward split.
+#[derive(Accounts)]
+pub struct EstimateDeployCost<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// Optional D2DConfig; when passed its fee_rate applies, as in create_deploy_request
+    #[account(
+        seeds = [D2DConfig::PREFIX_SEED],
+        bump = config.bump
+    )]
+    pub config: Option<Account<'info, D2DConfig>>,
+}
+
+/// Return buffer of estimate_deploy_cost
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DeployCostEstimate {
+    pub monthly_fee: u64,         // Effective monthly fee (monthly_fee_bps of deployment_cost when set)
+    pub reward_split_bps: u64,    // Effective split of the up-front fee (after clamping)
+    pub reward_fee_amount: u64,   // Transfer to the Reward Pool: prepaid months + reward share
+    pub platform_fee_amount: u64, // Transfer to the Platform Pool
+    pub total_payment: u64,       // reward_fee_amount + platform_fee_amount
+}
+
+pub fn estimate_deploy_cost(
+    ctx: Context<EstimateDeployCost>,
+    monthly_fee: u64,
+    initial_months: u32,
+    service_fee: u64,
+    deployment_cost: u64,
+    reward_split_bps: Option<u64>,
+) -> Result<DeployCostEstimate> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let min_service_fee = ctx.accounts.config.as_ref().map_or(1, |c| c.fee_rate.max(1));
+
+    // Same argument checks as create_deploy_request
+    require!(service_fee >= min_service_fee, ErrorCode::InvalidAmount);
+    let monthly_fee = TreasuryPool::monthly_fee_for(treasury_pool.monthly_fee_bps, deployment_cost, monthly_fee)?;
+    require!(monthly_fee > 0, ErrorCode::InvalidAmount);
+    require!(initial_months > 0, ErrorCode::InvalidAmount);
+    require!(deployment_cost > 0, ErrorCode::InvalidAmount);
+
+    let payment = treasury_pool.deploy_payment(service_fee, monthly_fee, initial_months, deployment_cost, reward_split_bps)?;
+
+    msg!("[ESTIMATE] Reward Pool: {}, Platform Pool: {}, total: {} lamports",
+         payment.reward_fee_amount, payment.platform_fee_amount, payment.total_payment);
+
+    Ok(DeployCostEstimate {
+        monthly_fee,
+        reward_split_bps: payment.reward_split_bps,
+        reward_fee_amount: payment.reward_fee_amount,
+        platform_fee_amount: payment.platform_fee_amount,
+        total_payment: payment.total_payment,
+    })
+}
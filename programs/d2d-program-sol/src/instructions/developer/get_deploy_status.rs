@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Read a deploy request's status and subscription state (no state mutation)
+///
+/// Returned via return data so front-ends can call it through simulateTransaction
+/// (`program.methods.getDeployStatus(hash).view()`) instead of parsing the DeployRequest
+/// layout and re-implementing the grace-period check. The Treasury Pool is read only
+/// for grace_period_secs.
+#[derive(Accounts)]
+#[instruction(program_hash: [u8; 32])]
+pub struct GetDeployStatus<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+}
+
+/// Return buffer of get_deploy_status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DeployStatus {
+    pub status: DeployRequestStatus,         // Current status
+    pub deployed_program_id: Option<Pubkey>, // Deployed program ID (None until confirmed)
+    pub subscription_paid_until: i64,        // Subscription valid until timestamp
+    pub subscription_valid: bool,            // Paid, or expired but still within the grace period
+    pub created_at: i64,                     // Creation timestamp
+    pub deployed_at: Option<i64>,            // confirm_deployment_success timestamp (None until deployed)
+    pub deploy_latency_secs: Option<i64>,    // deployed_at - created_at
+}
+
+pub fn get_deploy_status(ctx: Context<GetDeployStatus>, _program_hash: [u8; 32]) -> Result<DeployStatus> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let deploy_request = &ctx.accounts.deploy_request;
+
+    let subscription_valid = deploy_request.is_subscription_valid(treasury_pool.grace_period_secs)?;
+
+    msg!("[DEPLOY_STATUS] Developer: {}", deploy_request.developer);
+    msg!("[DEPLOY_STATUS] Paid until: {}, valid: {}", deploy_request.subscription_paid_until, subscription_valid);
+
+    Ok(DeployStatus {
+        status: deploy_request.status.clone(),
+        deployed_program_id: deploy_request.deployed_program_id,
+        subscription_paid_until: deploy_request.subscription_paid_until,
+        subscription_valid,
+        created_at: deploy_request.created_at,
+        deployed_at: deploy_request.deployed_at,
+        deploy_latency_secs: deploy_request.deploy_latency_secs(),
+    })
+}
@@ -0,0 +1,56 @@
+use crate::errors::ErrorCode;
+use crate::states::{TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+
+/// Read a developer's deploy counters (no state mutation)
+///
+/// Returned via return data (`program.methods.getUserStats().view()`) so a UI can show a
+/// developer's portfolio at a glance. Only the UserDeployStats PDA is required; pass the
+/// Treasury Pool to also get deploys_remaining_today against max_daily_deploys.
+#[derive(Accounts)]
+pub struct GetUserStats<'info> {
+    #[account(
+        seeds = [UserDeployStats::PREFIX_SEED, user_stats.user.as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Option<Account<'info, TreasuryPool>>,
+}
+
+/// Return buffer of get_user_stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UserStats {
+    pub active_sessions: u32,                 // Deploy requests currently holding a session
+    pub daily_deploys: u32,                   // Deploys counted today (0 if the daily window has rolled over)
+    pub total_deploys: u64,                   // Lifetime deploys (never reset)
+    pub last_reset: i64,                      // Start of the stored daily window
+    pub deploys_remaining_today: Option<u32>, // max_daily_deploys - daily_deploys (None without the Treasury Pool)
+}
+
+pub fn get_user_stats(ctx: Context<GetUserStats>) -> Result<UserStats> {
+    let user_stats = &ctx.accounts.user_stats;
+
+    let daily_deploys = user_stats.daily_deploys_at(Clock::get()?.unix_timestamp);
+    let deploys_remaining_today = ctx
+        .accounts
+        .treasury_pool
+        .as_ref()
+        .map(|pool| pool.max_daily_deploys.saturating_sub(daily_deploys));
+
+    msg!("[USER_STATS] User: {}, active: {}, today: {}, remaining today: {:?}",
+         user_stats.user, user_stats.active_sessions, daily_deploys, deploys_remaining_today);
+
+    Ok(UserStats {
+        active_sessions: user_stats.active_sessions,
+        daily_deploys,
+        total_deploys: user_stats.total_deploys,
+        last_reset: user_stats.last_reset,
+        deploys_remaining_today,
+    })
+}
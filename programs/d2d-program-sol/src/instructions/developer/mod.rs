@@ -1,3 +1,21 @@
+pub mod cancel_deploy_request;
+pub mod check_subscriptions_batch;
+pub mod estimate_deploy_cost;
+pub mod get_deploy_status;
+pub mod get_user_stats;
 pub mod pay_subscription;
+pub mod refund_on_halt;
+pub mod retry_deployment;
+pub mod top_up_prepaid;
+pub mod transfer_deploy_ownership;
 
+pub use cancel_deploy_request::*;
+pub use check_subscriptions_batch::*;
+pub use estimate_deploy_cost::*;
+pub use get_deploy_status::*;
+pub use get_user_stats::*;
 pub use pay_subscription::*;
+pub use refund_on_halt::*;
+pub use retry_deployment::*;
+pub use top_up_prepaid::*;
+pub use transfer_deploy_ownership::*;
@@ -4,27 +4,32 @@ use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
+/// Developer extend a subscription (or reinstate a Suspended one within max_lapse_secs)
+/// Payment is escrowed in the Reward Pool and accrues to backers via accrue_monthly_fee
 #[derive(Accounts)]
 pub struct PaySubscription<'info> {
     #[account(
+        mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     #[account(
         mut,
-        seeds = [DeployRequest::PREFIX_SEED, deploy_request.program_hash.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
         bump = deploy_request.bump
     )]
     pub deploy_request: Account<'info, DeployRequest>,
     #[account(mut)]
     pub developer: Signer<'info>,
-    /// CHECK: Treasury wallet address - validated against treasury_pool
+    /// CHECK: Reward Pool PDA (holds prepaid subscription fees in escrow)
     #[account(
         mut,
-        constraint = treasury_wallet.key() == treasury_pool.treasury_wallet @ ErrorCode::InvalidTreasuryWallet
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
     )]
-    pub treasury_wallet: UncheckedAccount<'info>,
+    pub reward_pool: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -36,7 +41,7 @@ pub fn pay_subscription(
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request = &mut ctx.accounts.deploy_request;
 
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_PAY_SUBSCRIPTION), ErrorCode::ProgramPaused);
     require!(
         deploy_request.request_id == request_id,
         ErrorCode::InvalidRequestId
@@ -46,30 +51,61 @@ pub fn pay_subscription(
         ErrorCode::Unauthorized
     );
     require!(months > 0, ErrorCode::InvalidAmount);
+    require!(
+        months <= TreasuryPool::MAX_SUBSCRIPTION_MONTHS,
+        ErrorCode::SubscriptionTooLong
+    );
     require!(
         deploy_request.status == DeployRequestStatus::Active
-            || deploy_request.status == DeployRequestStatus::SubscriptionExpired,
+            || deploy_request.status == DeployRequestStatus::SubscriptionExpired
+            || deploy_request.status == DeployRequestStatus::Suspended,
         ErrorCode::InvalidRequestStatus
     );
 
-    // Calculate payment amount
-    let payment_amount = deploy_request.monthly_fee * months as u64;
+    let current_time = Clock::get()?.unix_timestamp;
+    let reinstating = deploy_request.status == DeployRequestStatus::Suspended;
+    if reinstating {
+        // Suspended requests can be reinstated only within max_lapse_secs, then must be redeployed
+        require!(
+            !deploy_request.is_past_lapse(current_time, treasury_pool.max_lapse_secs),
+            ErrorCode::LapseWindowElapsed
+        );
+    } else {
+        // Past the grace period the program is due for suspension
+        require!(
+            !deploy_request.is_past_grace(current_time, treasury_pool.grace_period_secs),
+            ErrorCode::GracePeriodElapsed
+        );
+    }
+
+    // Calculate payment amount (longer prepay commitments are discounted)
+    let payment_amount = TreasuryPool::subscription_payment(deploy_request.monthly_fee, months)?;
+    msg!("[SUBSCRIPTION] {} months, discount {} bps, payment {} lamports",
+         months, TreasuryPool::subscription_discount_bps(months), payment_amount);
 
     // Extend subscription
     deploy_request.extend_subscription(months);
 
+    // Lapsed months are paid too: a reinstated request must come out fully paid up
+    if reinstating {
+        require!(
+            current_time <= deploy_request.subscription_paid_until,
+            ErrorCode::InvalidAmount
+        );
+    }
+
     // Update status to active
     deploy_request.status = DeployRequestStatus::Active;
 
-    // Update treasury pool
-    treasury_pool.distribute_fees(payment_amount)?;
+    // Escrow prepaid months (accrued to backers as the borrow fee comes due)
+    treasury_pool.escrow_prepaid_fees(deploy_request, payment_amount)?;
 
-    // Transfer payment to treasury
+    // Transfer payment to Reward Pool PDA
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         system_program::Transfer {
             from: ctx.accounts.developer.to_account_info(),
-            to: ctx.accounts.treasury_wallet.to_account_info(),
+            to: ctx.accounts.reward_pool.to_account_info(),
         },
     );
     system_program::transfer(cpi_context, payment_amount)?;
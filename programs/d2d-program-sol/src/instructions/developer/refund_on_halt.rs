@@ -0,0 +1,129 @@
+use crate::errors::ErrorCode;
+use crate::events::HaltRefund;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+
+/// Refund a pending deploy request while deployments are halted
+/// Only the developer who owns the request can claim the refund
+#[derive(Accounts)]
+pub struct RefundOnHalt<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (holds developer payments, source of the refund)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, developer.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        mut,
+        constraint = developer.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub developer: Signer<'info>,
+}
+
+/// Refund the full paid fees of a request stranded by a deployment halt
+///
+/// Flow:
+/// 1. Verify deployments_paused is set and the request is PendingDeployment and unfunded
+/// 2. Refund service_fee + monthly_fee * months_paid from Reward Pool PDA -> developer
+///    (the subscription never started, so no months are consumed)
+/// 3. Release the escrow / debit reward_pool_balance, release the developer's active session,
+///    mark request Cancelled
+///
+/// Not gated by emergency_pause: a halt must never hold developer payments hostage
+pub fn refund_on_halt(ctx: Context<RefundOnHalt>, request_id: [u8; 32]) -> Result<()> {
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let developer_info = ctx.accounts.developer.to_account_info();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(treasury_pool.deployments_paused, ErrorCode::DeploymentsNotPaused);
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(
+        deploy_request.status == DeployRequestStatus::PendingDeployment,
+        ErrorCode::InvalidRequestStatus
+    );
+    require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
+    // Funded deployments are in flight and resolve through confirm_deployment_success/failure
+    require!(
+        deploy_request.ephemeral_key.is_none(),
+        ErrorCode::DeploymentAlreadyFunded
+    );
+
+    // Nothing was borrowed, so the whole escrow goes back to the developer
+    let months_paid = deploy_request.months_paid();
+    let monthly_refund = deploy_request
+        .monthly_fee
+        .checked_mul(months_paid)
+        .ok_or(ErrorCode::CalculationOverflow)?
+        .max(deploy_request.prepaid_balance);
+    let service_refund = deploy_request.service_fee;
+    let refund_amount = treasury_pool.refund_fees(deploy_request, service_refund, monthly_refund)?;
+
+    msg!("[HALT_REFUND] Refunding developer: {}", developer_info.key());
+    msg!("[HALT_REFUND] Months paid: {}, refund: {} lamports", months_paid, refund_amount);
+
+    // Check Reward Pool has enough lamports for refund
+    require!(
+        reward_pool_info.lamports() >= refund_amount,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    // Refund developer from Reward Pool PDA via direct lamport manipulation
+    {
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut developer_lamports = developer_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(refund_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **developer_lamports = (**developer_lamports)
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    user_stats.active_sessions = user_stats.active_sessions.saturating_sub(1);
+    treasury_pool.release_deployment(deploy_request);
+
+    deploy_request.status = DeployRequestStatus::Cancelled;
+    deploy_request.subscription_paid_until = current_time;
+
+    emit!(HaltRefund {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        refund_amount,
+        months_refunded: months_paid,
+        refunded_at: current_time,
+    });
+
+    Ok(())
+}
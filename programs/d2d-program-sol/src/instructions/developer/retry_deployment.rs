@@ -0,0 +1,173 @@
+use crate::errors::ErrorCode;
+use crate::events::{DailyStatsReset, DeploymentFundsRequested};
+use crate::states::{Blacklist, DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Developer retry a failed deployment on the same request (same program_hash PDA)
+/// Re-collects the service fee + prepaid months that confirm_deployment_failure refunded
+#[derive(Accounts)]
+pub struct RetryDeployment<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (receives the service fee and escrowed prepaid months)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, developer.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        mut,
+        constraint = developer.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub developer: Signer<'info>,
+
+    /// CHECK: Blacklist PDA for the developer - usually does not exist; rejected if it is a live Blacklist account
+    #[account(
+        seeds = [Blacklist::PREFIX_SEED, developer.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move a Failed request back to PendingDeployment
+///
+/// Flow:
+/// 1. Verify request is exactly Failed and the developer is within the session/daily limits
+/// 2. Transfer service_fee + monthly_fee * months from developer -> Reward Pool PDA
+///    (service fee credited to backers, prepaid months escrowed as on create_deploy_request)
+/// 3. Reserve deployment_cost, clear ephemeral_key/deployed_program_id and restart the subscription
+pub fn retry_deployment(ctx: Context<RetryDeployment>, request_id: [u8; 32]) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPLOY), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.deployments_paused, ErrorCode::DeploymentsPaused);
+    require!(
+        !Blacklist::is_active(&ctx.accounts.blacklist.to_account_info()),
+        ErrorCode::DeveloperBlacklisted
+    );
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(
+        deploy_request.status == DeployRequestStatus::Failed,
+        ErrorCode::InvalidRequestStatus
+    );
+    require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
+
+    // Reset daily counter if new day
+    if let Some(previous_daily_deploys) = user_stats.reset_daily_if_due(current_time) {
+        emit!(DailyStatsReset {
+            user: user_stats.user,
+            previous_daily_deploys,
+            last_reset: user_stats.last_reset,
+            total_deploys: user_stats.total_deploys,
+        });
+    }
+    require!(
+        user_stats.daily_deploys < treasury_pool.max_daily_deploys,
+        ErrorCode::DailyDeployLimitExceeded
+    );
+    // The failure released this request's session; the retry opens a new one
+    require!(
+        user_stats.active_sessions < treasury_pool.max_concurrent_sessions,
+        ErrorCode::MaxConcurrentSessionsExceeded
+    );
+
+    // Same prepay as the failed attempt
+    let months = deploy_request.prepaid_months().max(1);
+    let monthly_fee_total = deploy_request
+        .monthly_fee
+        .checked_mul(months)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    let service_fee = deploy_request.service_fee;
+    let total_payment = service_fee
+        .checked_add(monthly_fee_total)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.developer.to_account_info(),
+            to: ctx.accounts.reward_pool.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, total_payment)?;
+
+    // Service fee goes to backers now, prepaid months accrue via accrue_monthly_fee
+    treasury_pool.credit_fee_to_pool(service_fee, 0)?;
+    treasury_pool.record_fee_credit(service_fee, current_time)?;
+    treasury_pool.escrow_prepaid_fees(deploy_request, monthly_fee_total)?;
+
+    // The ceiling may have tightened (or liquid_balance shrunk) since the original request
+    let deployment_cost = deploy_request.deployment_cost;
+    treasury_pool.check_deployment_cost(deployment_cost)?;
+    treasury_pool.release_deployment(deploy_request);
+    treasury_pool.reserve_deployment(deploy_request, deployment_cost)?;
+
+    deploy_request.status = DeployRequestStatus::PendingDeployment;
+    deploy_request.ephemeral_key = None; // Will be set when backend funds temporary wallet
+    deploy_request.deployed_program_id = None; // Will be set after backend deploys
+    deploy_request.deployed_at = None;
+    deploy_request.borrowed_amount = 0; // Set by fund_temporary_wallet
+    deploy_request.initial_months = months as u32;
+    deploy_request.subscription_paid_until = current_time
+        .checked_add(
+            (months as i64)
+                .checked_mul(DeployRequest::SECONDS_PER_MONTH)
+                .ok_or(ErrorCode::CalculationOverflow)?,
+        )
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    deploy_request.last_fee_accrual = current_time;
+
+    user_stats.active_sessions += 1;
+    user_stats.daily_deploys += 1;
+    user_stats.total_deploys += 1;
+
+    msg!("[RETRY] Request back to PendingDeployment, re-collected {} lamports", total_payment);
+
+    emit!(DeploymentFundsRequested {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        program_hash: deploy_request.program_hash,
+        service_fee,
+        monthly_fee: deploy_request.monthly_fee,
+        initial_months: deploy_request.initial_months,
+        deployment_cost,
+        total_payment,
+        requested_at: current_time,
+        reward_split_bps: 10000,
+        reward_fee_amount: service_fee,
+        platform_fee_amount: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::errors::ErrorCode;
+use crate::events::PrepaidToppedUp;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Developer deposit SOL for automatic subscription renewal
+/// Held in the DeployRequest PDA (renewal_balance) until renew_from_prepaid spends it a month at a time
+#[derive(Accounts)]
+pub struct TopUpPrepaid<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+    #[account(mut)]
+    pub developer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn top_up_prepaid(ctx: Context<TopUpPrepaid>, request_id: [u8; 32], amount: u64) -> Result<()> {
+    let deploy_request = &mut ctx.accounts.deploy_request;
+
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(
+        deploy_request.developer == ctx.accounts.developer.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        deploy_request.status == DeployRequestStatus::Active
+            || deploy_request.status == DeployRequestStatus::SubscriptionExpired,
+        ErrorCode::InvalidRequestStatus
+    );
+    // renew_from_prepaid cannot spend top-ups past the grace period; don't take them
+    require!(
+        !deploy_request.is_past_grace(Clock::get()?.unix_timestamp, ctx.accounts.treasury_pool.grace_period_secs),
+        ErrorCode::GracePeriodElapsed
+    );
+
+    deploy_request.renewal_balance = deploy_request
+        .renewal_balance
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.developer.to_account_info(),
+            to: deploy_request.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, amount)?;
+
+    msg!("[TOP_UP] {} lamports added, renewal balance: {}", amount, deploy_request.renewal_balance);
+
+    emit!(PrepaidToppedUp {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        amount,
+        renewal_balance: deploy_request.renewal_balance,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use crate::errors::ErrorCode;
+use crate::events::DeployOwnershipTransferred;
+use crate::states::{Blacklist, DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use anchor_lang::prelude::*;
+
+/// Developer hand a deploy request to another developer (both must sign)
+/// The PDA keeps its original seeds (original_developer); the open session moves to the new owner's stats
+#[derive(Accounts)]
+pub struct TransferDeployOwnership<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, developer.key().as_ref()],
+        bump = old_user_stats.bump
+    )]
+    pub old_user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        init_if_needed,
+        payer = new_developer,
+        space = 8 + UserDeployStats::INIT_SPACE,
+        seeds = [UserDeployStats::PREFIX_SEED, new_developer.key().as_ref()],
+        bump
+    )]
+    pub new_user_stats: Account<'info, UserDeployStats>,
+
+    #[account(
+        constraint = developer.key() == deploy_request.developer @ ErrorCode::Unauthorized
+    )]
+    pub developer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = new_developer.key() != developer.key() @ ErrorCode::SameDeveloper
+    )]
+    pub new_developer: Signer<'info>,
+
+    /// CHECK: Blacklist PDA for the new developer - usually does not exist; rejected if it is a live Blacklist account
+    #[account(
+        seeds = [Blacklist::PREFIX_SEED, new_developer.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move a live request (PendingDeployment, Active, SubscriptionExpired, Suspended) to new_developer
+///
+/// Failed/Cancelled/Closed requests hold no session and are rejected, as are requests
+/// whose deployment funds are in flight. renewal_balance and prepaid escrow stay with the request.
+pub fn transfer_deploy_ownership(ctx: Context<TransferDeployOwnership>, request_id: [u8; 32]) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let old_user_stats = &mut ctx.accounts.old_user_stats;
+    let new_user_stats = &mut ctx.accounts.new_user_stats;
+    let new_developer = ctx.accounts.new_developer.key();
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        deploy_request.request_id == request_id,
+        ErrorCode::InvalidRequestId
+    );
+    require!(
+        matches!(
+            deploy_request.status,
+            DeployRequestStatus::PendingDeployment
+                | DeployRequestStatus::Active
+                | DeployRequestStatus::SubscriptionExpired
+                | DeployRequestStatus::Suspended
+        ),
+        ErrorCode::InvalidDeploymentStatus
+    );
+    require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
+    require!(
+        !Blacklist::is_active(&ctx.accounts.blacklist.to_account_info()),
+        ErrorCode::DeveloperBlacklisted
+    );
+
+    // Initialize the new owner's stats if first time
+    if new_user_stats.user == Pubkey::default() {
+        new_user_stats.user = new_developer;
+        new_user_stats.active_sessions = 0;
+        new_user_stats.daily_deploys = 0;
+        new_user_stats.total_deploys = 0;
+        new_user_stats.failed_deploys = 0;
+        new_user_stats.last_reset = current_time;
+        new_user_stats.bump = ctx.bumps.new_user_stats;
+    }
+    require!(
+        new_user_stats.active_sessions < treasury_pool.max_concurrent_sessions,
+        ErrorCode::MaxConcurrentSessionsExceeded
+    );
+
+    // The session follows the request
+    old_user_stats.active_sessions = old_user_stats.active_sessions.saturating_sub(1);
+    new_user_stats.active_sessions += 1;
+
+    let old_developer = deploy_request.developer;
+    if deploy_request.original_developer.is_none() {
+        deploy_request.original_developer = Some(old_developer);
+    }
+    deploy_request.developer = new_developer;
+
+    msg!("[TRANSFER_OWNERSHIP] Request owner: {} -> {}", old_developer, new_developer);
+
+    emit!(DeployOwnershipTransferred {
+        request_id,
+        old_developer,
+        new_developer,
+        original_developer: deploy_request.seed_developer(),
+        transferred_at: current_time,
+    });
+
+    Ok(())
+}
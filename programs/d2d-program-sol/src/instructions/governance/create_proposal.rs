@@ -0,0 +1,82 @@
+use crate::errors::ErrorCode;
+use crate::events::ProposalCreated;
+use crate::states::{Proposal, ProposalParam, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Create a deposit-weighted governance proposal (permissionless)
+/// Proposer pays rent for the Proposal PDA
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [Proposal::PREFIX_SEED, treasury_pool.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    param: ProposalParam,
+    new_value: u64,
+) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(treasury_pool.total_deposited > 0, ErrorCode::DivisionByZero);
+
+    // Fee parameters are bounded so a proposal cannot brick deployments
+    match param {
+        ProposalParam::RewardFeeBps | ProposalParam::PlatformFeeBps => {
+            require!(new_value <= Proposal::MAX_FEE_BPS, ErrorCode::InvalidProposalValue);
+        }
+    }
+
+    proposal.proposal_id = treasury_pool.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.param = param;
+    proposal.new_value = new_value;
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.total_deposited_snapshot = treasury_pool.total_deposited;
+    proposal.created_at = current_time;
+    proposal.voting_ends_at = current_time
+        .checked_add(Proposal::VOTING_PERIOD)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    treasury_pool.proposal_count = treasury_pool
+        .proposal_count
+        .checked_add(1)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("[GOVERNANCE] Proposal {} created, voting ends at {}", proposal.proposal_id, proposal.voting_ends_at);
+
+    emit!(ProposalCreated {
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        param,
+        new_value,
+        voting_ends_at: proposal.voting_ends_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::errors::ErrorCode;
+use crate::events::ProposalExecuted;
+use crate::states::{Proposal, ProposalParam, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Apply a passed proposal to the pool (permissionless, after the voting deadline)
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [Proposal::PREFIX_SEED, proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub executor: Signer<'info>,
+}
+
+pub fn execute_proposal(ctx: Context<ExecuteProposal>, _proposal_id: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+    require!(current_time > proposal.voting_ends_at, ErrorCode::VotingStillOpen);
+    require!(proposal.has_passed()?, ErrorCode::ProposalNotPassed);
+
+    let old_value = match proposal.param {
+        ProposalParam::RewardFeeBps => {
+            let old = treasury_pool.reward_fee_bps;
+            treasury_pool.reward_fee_bps = proposal.new_value;
+            old
+        }
+        ProposalParam::PlatformFeeBps => {
+            let old = treasury_pool.platform_fee_bps;
+            treasury_pool.platform_fee_bps = proposal.new_value;
+            old
+        }
+    };
+    proposal.executed = true;
+
+    msg!("[GOVERNANCE] Proposal {} executed: {} -> {}", proposal.proposal_id, old_value, proposal.new_value);
+
+    emit!(ProposalExecuted {
+        proposal_id: proposal.proposal_id,
+        param: proposal.param,
+        old_value,
+        new_value: proposal.new_value,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        executed_at: current_time,
+    });
+
+    Ok(())
+}
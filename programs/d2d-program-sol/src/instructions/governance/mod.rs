@@ -0,0 +1,7 @@
+pub mod create_proposal;
+pub mod execute_proposal;
+pub mod vote;
+
+pub use create_proposal::*;
+pub use execute_proposal::*;
+pub use vote::*;
@@ -0,0 +1,93 @@
+use crate::errors::ErrorCode;
+use crate::events::VoteCast;
+use crate::states::{BackerDeposit, Proposal, TreasuryPool, VoteRecord};
+use anchor_lang::prelude::*;
+
+/// Cast a deposit-weighted vote on a proposal
+/// VoteRecord PDA (one per backer per proposal) prevents double voting
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct Vote<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [Proposal::PREFIX_SEED, proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [BackerDeposit::PREFIX_SEED, voter.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [VoteRecord::PREFIX_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn vote(ctx: Context<Vote>, _proposal_id: u64, support: bool) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let lender_stake = &ctx.accounts.lender_stake;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!ctx.accounts.treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(current_time <= proposal.voting_ends_at, ErrorCode::VotingClosed);
+    require!(lender_stake.deposited_amount > 0, ErrorCode::InsufficientStake);
+
+    // Snapshot: stake added after the proposal was created carries no weight
+    require!(
+        lender_stake.last_deposit_at < proposal.created_at,
+        ErrorCode::StakeAfterProposal
+    );
+
+    let weight = lender_stake.deposited_amount;
+    if support {
+        proposal.votes_for = proposal
+            .votes_for
+            .checked_add(weight)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    } else {
+        proposal.votes_against = proposal
+            .votes_against
+            .checked_add(weight)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.proposal = proposal.key();
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.weight = weight;
+    vote_record.support = support;
+    vote_record.bump = ctx.bumps.vote_record;
+
+    msg!("[GOVERNANCE] Vote on proposal {}: support={}, weight={} lamports", proposal.proposal_id, support, weight);
+
+    emit!(VoteCast {
+        proposal_id: proposal.proposal_id,
+        voter: vote_record.voter,
+        support,
+        weight,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+    });
+
+    Ok(())
+}
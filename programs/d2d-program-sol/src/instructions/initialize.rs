@@ -1,13 +1,16 @@
+use crate::errors::ErrorCode;
 use crate::events::TreasuryInitialized;
 use crate::states::TreasuryPool;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(initial_apy: u64, dev_wallet: Pubkey, reserved_padding: u32)]
 pub struct Initialize<'info> {
+    /// Over-allocated by reserved_padding bytes so future fields fit without a migration
     #[account(
         init,
         payer = admin,
-        space = 8 + TreasuryPool::INIT_SPACE,
+        space = TreasuryPool::space_with_padding(reserved_padding),
         seeds = [TreasuryPool::PREFIX_SEED],
         bump
     )]
@@ -46,11 +49,17 @@ pub fn initialize(
     ctx: Context<Initialize>,
     _initial_apy: u64, // Legacy parameter, not used in new model
     dev_wallet: Pubkey,
+    reserved_padding: u32,
 ) -> Result<()> {
+    require!(
+        reserved_padding <= TreasuryPool::MAX_RESERVED_PADDING,
+        ErrorCode::InvalidAmount
+    );
+
     let treasury_pool = &mut ctx.accounts.treasury_pool;
 
     msg!("[INIT] Initializing Treasury Pool with new layout");
-    msg!("[INIT] Account size: {} bytes", 8 + TreasuryPool::INIT_SPACE);
+    msg!("[INIT] Account size: {} bytes (reserved padding: {})", TreasuryPool::space_with_padding(reserved_padding), reserved_padding);
     msg!("[INIT] Admin: {}", ctx.accounts.admin.key());
     msg!("[INIT] Dev wallet: {}", dev_wallet);
 
@@ -87,6 +96,116 @@ pub fn initialize(
     treasury_pool.total_fees_collected = 0;
     treasury_pool.current_apy = 0;
     treasury_pool.treasury_wallet = Pubkey::default();
+    
+    // Solvency tracking
+    treasury_pool.total_borrowed = 0;
+    treasury_pool.undercollateralized = false;
+    
+    // Governance
+    treasury_pool.proposal_count = 0;
+    
+    // Admin transfer
+    treasury_pool.pending_admin = None;
+    
+    // Key management
+    treasury_pool.reward_admin = TreasuryPool::DEFAULT_REWARD_ADMIN;
+    treasury_pool.recovery_authority = None;
+    
+    // Deposit limits
+    treasury_pool.max_deposit_per_backer = 0;
+    treasury_pool.min_deposit = TreasuryPool::DEFAULT_MIN_DEPOSIT;
+    
+    // Fee velocity (estimates only)
+    treasury_pool.fee_window_start = 0;
+    treasury_pool.fees_credited_current_window = 0;
+    treasury_pool.fees_credited_last_window = 0;
+    treasury_pool.last_window_seconds = 0;
+    
+    // Lock-period staking
+    treasury_pool.penalty_bps = TreasuryPool::DEFAULT_PENALTY_BPS;
+    
+    // Deployment halt
+    treasury_pool.deployments_paused = false;
+    
+    // Reward pool isolation
+    treasury_pool.reward_pool_isolated = false;
+    
+    // Monthly fee accrual
+    treasury_pool.prepaid_fees_balance = 0;
+    
+    // Insurance backstop
+    treasury_pool.emergency_withdraw_fee_bps = 0;
+    treasury_pool.insurance_pool_balance = 0;
+    
+    // Per-operation pause
+    treasury_pool.paused_ops = 0;
+    
+    // Claim limits
+    treasury_pool.min_claim_amount = 0;
+    
+    // Deploy session limits
+    treasury_pool.max_concurrent_sessions = TreasuryPool::DEFAULT_MAX_CONCURRENT_SESSIONS;
+    treasury_pool.max_daily_deploys = TreasuryPool::DEFAULT_MAX_DAILY_DEPLOYS;
+    
+    // Subscription grace period
+    treasury_pool.grace_period_secs = TreasuryPool::DEFAULT_GRACE_PERIOD_SECS;
+    
+    // Stuck deployment recovery
+    treasury_pool.ephemeral_recovery_timeout_secs = TreasuryPool::DEFAULT_EPHEMERAL_RECOVERY_TIMEOUT_SECS;
+    
+    // Recovery split
+    treasury_pool.recovery_platform_bps = 0;
+    
+    // Pool cap
+    treasury_pool.max_total_deposited = 0;
+    
+    // Failure slashing
+    treasury_pool.failure_slash_threshold = 0;
+    treasury_pool.failure_slash_bps = 0;
+    
+    // Deployment escrow
+    treasury_pool.escrowed_amount = 0;
+    
+    // Reward dust
+    treasury_pool.undistributed_dust = 0;
+    
+    // Reward statistics
+    treasury_pool.total_rewards_claimed = 0;
+    
+    // Subscription lapse
+    treasury_pool.max_lapse_secs = 0;
+    
+    // Deployment losses
+    treasury_pool.total_deployment_losses = 0;
+    
+    // Layout
+    treasury_pool.layout_version = TreasuryPool::LAYOUT_VERSION;
+    
+    // Monthly fee
+    treasury_pool.monthly_fee_bps = 0;
+    
+    // Unallocated rewards
+    treasury_pool.unallocated_rewards = 0;
+    
+    // Per-request fee split bounds
+    treasury_pool.min_reward_split_bps = 0;
+    treasury_pool.max_reward_split_bps = 10000;
+    
+    // Claim cooldown
+    treasury_pool.claim_cooldown_secs = 0;
+    
+    // Reward accounting
+    treasury_pool.total_claimable = 0;
+    
+    // Deployment cost ceiling
+    treasury_pool.max_deployment_cost = 0;
+    treasury_pool.max_deployment_bps = 0;
+    
+    // Reward outflow circuit breaker
+    treasury_pool.max_outflow_per_window = 0;
+    treasury_pool.outflow_window_secs = 0;
+    treasury_pool.outflow_window_start = 0;
+    treasury_pool.outflow_in_window = 0;
 
     msg!("[INIT] Treasury Pool initialized successfully");
     msg!("[INIT] reward_per_share: {}", treasury_pool.reward_per_share);
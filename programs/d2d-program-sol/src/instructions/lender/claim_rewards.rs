@@ -1,5 +1,5 @@
 use crate::errors::ErrorCode;
-use crate::events::RewardsClaimed;
+use crate::events::{EmergencyPauseToggled, RewardsClaimed};
 use crate::states::{LenderStake, TreasuryPool};
 use anchor_lang::prelude::*;
 
@@ -7,15 +7,18 @@ use anchor_lang::prelude::*;
 /// 
 /// Flow:
 /// 1. Calculate claimable = (deposited_amount * reward_per_share - reward_debt) / PRECISION
-/// 2. Verify reward_pool has enough lamports
-/// 3. Transfer from reward_pool PDA -> backer (via lamport mutation)
-/// 4. Update reward_debt and claimed_total
+/// 2. Claim amount = requested amount (must be <= claimable) or full claimable
+/// 3. Verify reward_pool has enough lamports
+///    and count the claim against the outflow circuit breaker (pauses and pays nothing if tripped)
+/// 4. Transfer from reward_pool PDA -> destination (via lamport mutation)
+/// 5. Update reward_debt and claimed_total
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
@@ -29,24 +32,39 @@ pub struct ClaimRewards<'info> {
     
     #[account(
         mut,
-        seeds = [LenderStake::PREFIX_SEED, lender.key().as_ref()],
+        seeds = [LenderStake::PREFIX_SEED, lender_stake.backer.as_ref()],
         bump = lender_stake.bump
     )]
     pub lender_stake: Account<'info, LenderStake>,
     
-    #[account(mut)]
+    /// Backer, or its claim_authority when one is set (see set_claim_authority)
+    #[account(
+        mut,
+        constraint = lender.key() == lender_stake.claim_signer() @ ErrorCode::Unauthorized
+    )]
     pub lender: Signer<'info>,
     
+    /// CHECK: Optional wallet receiving the rewards (defaults to the signer)
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+    
     pub system_program: Program<'info, System>,
 }
 
 /// Claim rewards (reward-per-share model)
-pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+///
+/// amount: None claims everything (pending_rewards cleared, reward_debt reset)
+///         Some(x) claims exactly x; the remainder keeps accruing as claimable
+pub fn claim_rewards(ctx: Context<ClaimRewards>, amount: Option<u64>) -> Result<()> {
     msg!("[CLAIM] Starting claim_rewards instruction");
     msg!("[CLAIM] Lender: {}", ctx.accounts.lender.key());
     
     // Get account info before mutable borrows
     let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let destination_info = match ctx.accounts.destination.as_ref() {
+        Some(destination) => destination.to_account_info(),
+        None => ctx.accounts.lender.to_account_info(),
+    };
     
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let lender_stake = &mut ctx.accounts.lender_stake;
@@ -56,7 +74,13 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     msg!("[CLAIM] Lender Stake - deposited_amount: {}, reward_debt: {}", 
          lender_stake.deposited_amount, lender_stake.reward_debt);
 
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_CLAIM), ErrorCode::ProgramPaused);
+    // Deposit-then-claim right around a large fee credit has to wait out the cooldown
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !lender_stake.claim_cooldown_active(treasury_pool.claim_cooldown_secs, current_time),
+        ErrorCode::ClaimCooldownActive
+    );
 
     // Allow claim even if inactive, as long as there are rewards to claim
     // This allows users to claim pending_rewards even after fully unstaking
@@ -68,63 +92,94 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     msg!("[CLAIM] - From reward_per_share: {} lamports", claimable_rewards - lender_stake.pending_rewards);
     require!(claimable_rewards > 0, ErrorCode::NoRewardsToClaim);
 
+    let claim_amount = match amount {
+        Some(requested) => {
+            require!(requested > 0, ErrorCode::InvalidAmount);
+            require!(requested <= claimable_rewards, ErrorCode::ClaimExceedsClaimable);
+            msg!("[CLAIM] Partial claim requested: {} lamports", requested);
+            requested
+        }
+        None => claimable_rewards,
+    };
+    // Dust claims waste transaction fees and churn reward_debt
+    require!(
+        claim_amount >= treasury_pool.min_claim_amount,
+        ErrorCode::ClaimBelowThreshold
+    );
+
     // Verify reward pool has enough balance
     require!(
-        treasury_pool.reward_pool_balance >= claimable_rewards,
+        treasury_pool.reward_pool_balance >= claim_amount,
         ErrorCode::InsufficientTreasuryFunds
     );
 
     // Check Reward Pool PDA has enough lamports
     let reward_pool_lamports = reward_pool_info.lamports();
     require!(
-        reward_pool_lamports >= claimable_rewards,
+        reward_pool_lamports >= claim_amount,
         ErrorCode::InsufficientTreasuryFunds
     );
 
+    // Circuit breaker: the claim that would exceed the outflow limit pauses the program instead of paying
+    if treasury_pool.record_reward_outflow(claim_amount, current_time)? {
+        msg!("[CLAIM] Reward outflow limit reached - program paused, nothing claimed");
+        emit!(EmergencyPauseToggled {
+            paused: true,
+            toggled_at: current_time,
+        });
+        return Ok(());
+    }
+
     // Update lender stake
     lender_stake.claimed_total = lender_stake
         .claimed_total
-        .checked_add(claimable_rewards)
+        .checked_add(claim_amount)
         .ok_or(ErrorCode::CalculationOverflow)?;
 
-    // Clear pending_rewards as they've been claimed
-    lender_stake.pending_rewards = 0;
+    if amount.is_some() {
+        // Partial claim: only advance reward_debt by what was taken
+        lender_stake.consume_claimable(claim_amount)?;
+    } else {
+        // Clear pending_rewards as they've been claimed
+        lender_stake.pending_rewards = 0;
 
-    // Update reward_debt to current accumulated value
-    lender_stake.update_reward_debt(treasury_pool.reward_per_share)?;
+        // Update reward_debt to current accumulated value
+        lender_stake.update_reward_debt(treasury_pool.reward_per_share)?;
+    }
 
-    // Debit reward pool balance
-    treasury_pool.debit_reward_pool(claimable_rewards)?;
+    // Debit reward pool balance (and count the payout pool-wide)
+    treasury_pool.pay_rewards(claim_amount)?;
 
-    // Transfer rewards from Reward Pool PDA -> lender
+    // Transfer rewards from Reward Pool PDA -> destination
     // CRITICAL: Use lamport mutation for program-owned accounts (not CPI System transfer)
     // Reward Pool PDA may have data, so we cannot use System Program transfer
     {
-        let lender_info = ctx.accounts.lender.to_account_info();
         let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
-        let mut lender_lamports = lender_info.try_borrow_mut_lamports()?;
+        let mut lender_lamports = destination_info.try_borrow_mut_lamports()?;
 
         **reward_pool_lamports = (**reward_pool_lamports)
-            .checked_sub(claimable_rewards)
+            .checked_sub(claim_amount)
             .ok_or(ErrorCode::CalculationOverflow)?;
         **lender_lamports = (**lender_lamports)
-            .checked_add(claimable_rewards)
+            .checked_add(claim_amount)
             .ok_or(ErrorCode::CalculationOverflow)?;
     }
 
     emit!(RewardsClaimed {
         lender: lender_stake.backer,
-        amount: claimable_rewards,
+        amount: claim_amount,
         total_claimed: lender_stake.claimed_total,
     });
     
     // Emit detailed claim event
     emit!(crate::events::Claimed {
         backer: lender_stake.backer,
-        amount: claimable_rewards,
+        amount: claim_amount,
         claimed_total: lender_stake.claimed_total,
         reward_per_share: treasury_pool.reward_per_share,
-        claimed_at: Clock::get()?.unix_timestamp,
+        claim_authority: lender_stake.claim_authority,
+        destination: destination_info.key(),
+        claimed_at: current_time,
     });
 
     Ok(())
@@ -0,0 +1,94 @@
+use crate::errors::ErrorCode;
+use crate::events::SplRewardsClaimed;
+use crate::states::{BackerDeposit, TokenTreasury, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Claim all accumulated SPL rewards (paid in the token treasury's mint from the vault)
+#[derive(Accounts)]
+pub struct ClaimRewardsSpl<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [TokenTreasury::PREFIX_SEED, mint.key().as_ref()],
+        bump = token_treasury.bump
+    )]
+    pub token_treasury: Account<'info, TokenTreasury>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = token_treasury.vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [TokenTreasury::STAKE_SEED, mint.key().as_ref(), lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.mint == token_treasury.mint @ ErrorCode::InvalidMint
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    pub lender: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_rewards_spl(ctx: Context<ClaimRewardsSpl>) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let token_treasury = &mut ctx.accounts.token_treasury;
+    let lender_stake = &mut ctx.accounts.lender_stake;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_CLAIM), ErrorCode::ProgramPaused);
+
+    let claim_amount = lender_stake.calculate_claimable_rewards(token_treasury.reward_per_share)?;
+    require!(claim_amount > 0, ErrorCode::NoRewardsToClaim);
+
+    token_treasury.debit_reward_pool(claim_amount)?;
+
+    lender_stake.claimed_total = lender_stake
+        .claimed_total
+        .checked_add(claim_amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    lender_stake.pending_rewards = 0;
+    lender_stake.update_reward_debt(token_treasury.reward_per_share)?;
+
+    // Vault authority is the Treasury Pool PDA
+    let treasury_seeds: &[&[u8]] = &[TreasuryPool::PREFIX_SEED, &[treasury_pool.bump]];
+    let signer_seeds = &[treasury_seeds];
+    let transfer_cpi = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.lender_token_account.to_account_info(),
+            authority: treasury_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer_checked(transfer_cpi, claim_amount, token_treasury.decimals)?;
+
+    msg!("[CLAIM_SPL] {} claimed {} of mint {}", lender_stake.backer, claim_amount, token_treasury.mint);
+
+    emit!(SplRewardsClaimed {
+        backer: lender_stake.backer,
+        mint: token_treasury.mint,
+        amount: claim_amount,
+        total_claimed: lender_stake.claimed_total,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::errors::ErrorCode;
+use crate::events::StakeClosed;
+use crate::states::{LenderStake, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Close a fully exited position and return its rent to the backer
+///
+/// Only once unstake_sol / exit_position has withdrawn everything: no principal, no claimable
+/// rewards and no deposit receipt. Staking again later re-creates the account.
+#[derive(Accounts)]
+pub struct CloseStake<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [LenderStake::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump,
+        close = lender
+    )]
+    pub lender_stake: Account<'info, LenderStake>,
+
+    #[account(mut)]
+    pub lender: Signer<'info>,
+}
+
+pub fn close_stake(ctx: Context<CloseStake>) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let lender_stake = &ctx.accounts.lender_stake;
+
+    require!(lender_stake.deposited_amount == 0, ErrorCode::StakeNotEmpty);
+    require!(lender_stake.receipt_mint.is_none(), ErrorCode::StakeNotEmpty);
+    // Closing would forfeit rewards still owed to the backer
+    require!(
+        lender_stake.calculate_claimable_rewards(treasury_pool.reward_per_share)? == 0,
+        ErrorCode::StakeNotEmpty
+    );
+
+    // Anchor `close` moves every lamport in the account to the lender
+    let recovered_rent = lender_stake.to_account_info().lamports();
+    msg!("[CLOSE_STAKE] Reclaiming {} lamports of rent", recovered_rent);
+
+    emit!(StakeClosed {
+        lender: ctx.accounts.lender.key(),
+        recovered_rent,
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::errors::ErrorCode;
+use crate::events::{EmergencyPauseToggled, RewardsCompounded};
+use crate::states::{LenderStake, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Restake claimable rewards into the backer's deposit (reward-per-share model)
+///
+/// Flow:
+/// 1. Calculate claimable = pending_rewards + (deposited_amount * reward_per_share - reward_debt) / PRECISION
+/// 2. Move claimable from Reward Pool PDA -> Treasury PDA (never leaves the program)
+/// 3. Credit deposited_amount, total_deposited and liquid_balance, debit reward_pool_balance
+/// 4. Reset reward_debt for the new deposited_amount
+#[derive(Accounts)]
+pub struct CompoundRewards<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Treasury Pool PDA (receives the compounded rewards as principal)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (holds reward fees)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [LenderStake::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, LenderStake>,
+
+    pub lender: Signer<'info>,
+}
+
+/// Compound rewards (claim + restake in one instruction)
+pub fn compound_rewards(ctx: Context<CompoundRewards>) -> Result<()> {
+    msg!("[COMPOUND] Lender: {}", ctx.accounts.lender.key());
+
+    // Get account info before mutable borrows
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let lender_stake = &mut ctx.accounts.lender_stake;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_CLAIM | TreasuryPool::PAUSE_DEPOSIT), ErrorCode::ProgramPaused);
+
+    let compounded = lender_stake.calculate_claimable_rewards(treasury_pool.reward_per_share)?;
+    msg!("[COMPOUND] Claimable rewards: {} lamports", compounded);
+    require!(compounded > 0, ErrorCode::NoRewardsToClaim);
+    // min_claim_amount deliberately not applied: nothing leaves the program, no dust transfer
+
+    // Verify reward pool has enough balance
+    require!(
+        treasury_pool.reward_pool_balance >= compounded,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+    require!(
+        reward_pool_info.lamports() >= compounded,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+
+    let new_deposited_amount = lender_stake
+        .deposited_amount
+        .checked_add(compounded)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Per-backer cap (0 = unlimited) applies to compounded principal as well
+    if treasury_pool.max_deposit_per_backer > 0 {
+        require!(
+            new_deposited_amount <= treasury_pool.max_deposit_per_backer,
+            ErrorCode::DepositCapExceeded
+        );
+    }
+
+    // Pool-wide cap (0 = unlimited) also applies to compounded principal
+    if treasury_pool.max_total_deposited > 0 {
+        let new_total_deposited = treasury_pool
+            .total_deposited
+            .checked_add(compounded)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        require!(
+            new_total_deposited <= treasury_pool.max_total_deposited,
+            ErrorCode::PoolCapReached
+        );
+    }
+
+    // Circuit breaker: compounding drains the Reward Pool like a claim does
+    let current_time = Clock::get()?.unix_timestamp;
+    if treasury_pool.record_reward_outflow(compounded, current_time)? {
+        msg!("[COMPOUND] Reward outflow limit reached - program paused, nothing compounded");
+        emit!(EmergencyPauseToggled {
+            paused: true,
+            toggled_at: current_time,
+        });
+        return Ok(());
+    }
+
+    // Rewards become principal: counted as claimed, pending cleared
+    lender_stake.claimed_total = lender_stake
+        .claimed_total
+        .checked_add(compounded)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    lender_stake.pending_rewards = 0;
+    lender_stake.update_time_weighted(new_deposited_amount, current_time)?;
+    lender_stake.deposited_amount = new_deposited_amount;
+    lender_stake.is_active = true;
+    // Snapshot for governance: compounded stake counts as a fresh deposit
+    lender_stake.last_deposit_at = current_time;
+
+    // reward_debt for the new deposited_amount (after the increment)
+    lender_stake.update_reward_debt(treasury_pool.reward_per_share)?;
+
+    treasury_pool.pay_rewards(compounded)?;
+    treasury_pool.total_deposited = treasury_pool
+        .total_deposited
+        .checked_add(compounded)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    treasury_pool.liquid_balance = treasury_pool
+        .liquid_balance
+        .checked_add(compounded)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Move rewards from Reward Pool PDA -> Treasury PDA via lamport mutation
+    {
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut treasury_lamports = treasury_pda_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(compounded)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **treasury_lamports = (**treasury_lamports)
+            .checked_add(compounded)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    msg!("[COMPOUND] New deposited amount: {} lamports", lender_stake.deposited_amount);
+
+    emit!(RewardsCompounded {
+        backer: lender_stake.backer,
+        amount: compounded,
+        deposited_amount: lender_stake.deposited_amount,
+        reward_per_share: treasury_pool.reward_per_share,
+        compounded_at: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use crate::errors::ErrorCode;
+use crate::events::EmergencyUnstaked;
+use crate::instructions::lender::unstake_sol::burn_deposit_receipt;
+use crate::states::{LenderStake, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Emergency exit while withdrawals are paused
+///
+/// The only lender exit that bypasses PAUSE_WITHDRAW / emergency_pause (and lock periods);
+/// emergency_withdraw is this instruction for the whole deposit.
+/// emergency_withdraw_fee_bps of the payout is routed to the Insurance Pool PDA
+/// to protect the backers who stay; a zero fee makes the exit free.
+/// A full exit thaws and burns the position's deposit receipt, as unstake_sol does.
+#[derive(Accounts)]
+pub struct EmergencyUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Treasury Pool PDA (holds deposits)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (assets for pro-rata withdrawals)
+    #[account(
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Insurance Pool PDA (program-owned, holds emergency withdrawal fees)
+    /// Created by the first emergency exit
+    #[account(
+        init_if_needed,
+        payer = lender,
+        space = 8, // Empty account, just holds lamports
+        seeds = [TreasuryPool::INSURANCE_POOL_SEED],
+        bump
+    )]
+    pub insurance_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [LenderStake::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, LenderStake>,
+
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Deposit receipt minted by stake_sol; required for a full exit when lender_stake.receipt_mint is set
+    #[account(
+        mut,
+        constraint = lender_stake.receipt_mint == Some(receipt_mint.key()) @ ErrorCode::InvalidMint
+    )]
+    pub receipt_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = lender
+    )]
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+/// Emergency unstake (withdraw principal while withdrawals are paused)
+///
+/// Same accounting as unstake_sol, including pro-rata mode while undercollateralized,
+/// except that the early-unstake penalty is replaced by the emergency withdrawal fee:
+/// - fee = payout * emergency_withdraw_fee_bps / 10000
+/// - Lender receives payout - fee, the fee moves Treasury PDA -> Insurance Pool PDA
+pub fn emergency_unstake(ctx: Context<EmergencyUnstake>, amount: u64) -> Result<()> {
+    msg!("[EMERGENCY_UNSTAKE] Lender: {}", ctx.accounts.lender.key());
+
+    // Get account info before mutable borrows
+    let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
+    let insurance_pool_info = ctx.accounts.insurance_pool.to_account_info();
+    let reward_pool_lamports = ctx.accounts.reward_pool.lamports();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let lender_stake = &mut ctx.accounts.lender_stake;
+
+    require!(
+        treasury_pool.is_paused(TreasuryPool::PAUSE_WITHDRAW),
+        ErrorCode::ProgramNotPaused
+    );
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        amount <= lender_stake.deposited_amount,
+        ErrorCode::InsufficientStake
+    );
+
+    // Settle pending rewards BEFORE updating deposited_amount
+    lender_stake.settle_pending_rewards(treasury_pool.reward_per_share)?;
+
+    // Available balance = actual balance - rent exemption
+    let rent_exemption = Rent::get()?.minimum_balance(treasury_pda_info.data_len());
+    let available_balance = treasury_pda_info
+        .lamports()
+        .checked_sub(rent_exemption)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Pro-rata mode: share the shortfall instead of paying early movers in full
+    let (payout, haircut) = if treasury_pool.undercollateralized {
+        let (assets, obligations) = treasury_pool
+            .collateral_position(available_balance, reward_pool_lamports)?;
+        msg!("[EMERGENCY_UNSTAKE] Pool undercollateralized - pro-rata mode. Assets: {} lamports, obligations: {} lamports", assets, obligations);
+        TreasuryPool::pro_rata_withdrawal(amount, assets, obligations)?
+    } else {
+        (amount, 0)
+    };
+
+    require!(available_balance >= payout, ErrorCode::InsufficientLiquidBalance);
+
+    let fee = (payout as u128)
+        .checked_mul(treasury_pool.emergency_withdraw_fee_bps as u128)
+        .ok_or(ErrorCode::CalculationOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::CalculationOverflow)? as u64;
+    let lender_payout = payout
+        .checked_sub(fee)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("[EMERGENCY_UNSTAKE] Payout: {} lamports, haircut: {} lamports, fee: {} lamports", payout, haircut, fee);
+
+    // Update backer deposit
+    // Full amount is debited even in pro-rata mode so the haircut cannot be withdrawn again
+    let new_deposited_amount = lender_stake
+        .deposited_amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    lender_stake.update_time_weighted(new_deposited_amount, Clock::get()?.unix_timestamp)?;
+    lender_stake.deposited_amount = new_deposited_amount;
+    if treasury_pool.undercollateralized {
+        lender_stake.record_pro_rata_withdrawal(payout, haircut)?;
+    }
+
+    if lender_stake.deposited_amount == 0 {
+        lender_stake.is_active = false;
+        lender_stake.reward_debt = 0;
+        // Keep pending_rewards intact - user can still claim them after the pause
+        burn_deposit_receipt(
+            lender_stake,
+            ctx.accounts.receipt_mint.as_ref(),
+            ctx.accounts.receipt_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            &treasury_pda_info,
+            treasury_pool.bump,
+            &ctx.accounts.lender.to_account_info(),
+            Clock::get()?.unix_timestamp,
+        )?;
+    } else {
+        lender_stake.update_reward_debt(treasury_pool.reward_per_share)?;
+    }
+
+    // Update treasury pool state
+    treasury_pool.total_deposited = treasury_pool
+        .total_deposited
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    treasury_pool.liquid_balance = treasury_pool.liquid_balance.saturating_sub(payout);
+    treasury_pool.insurance_pool_balance = treasury_pool
+        .insurance_pool_balance
+        .checked_add(fee)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Transfer principal from Treasury PDA -> lender (and fee -> Insurance Pool PDA) via lamport mutation
+    {
+        let lender_info = ctx.accounts.lender.to_account_info();
+        let mut treasury_lamports = treasury_pda_info.try_borrow_mut_lamports()?;
+        let mut lender_lamports = lender_info.try_borrow_mut_lamports()?;
+        let mut insurance_lamports = insurance_pool_info.try_borrow_mut_lamports()?;
+
+        **treasury_lamports = (**treasury_lamports)
+            .checked_sub(payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **lender_lamports = (**lender_lamports)
+            .checked_add(lender_payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **insurance_lamports = (**insurance_lamports)
+            .checked_add(fee)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    emit!(EmergencyUnstaked {
+        lender: lender_stake.backer,
+        amount: lender_payout,
+        remaining_staked: lender_stake.deposited_amount,
+        haircut,
+        fee,
+        insurance_pool_balance: treasury_pool.insurance_pool_balance,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::errors::ErrorCode;
+use crate::instructions::lender::emergency_unstake::{emergency_unstake, EmergencyUnstake};
+use anchor_lang::prelude::*;
+
+/// Emergency withdraw (full principal, emergency_pause only)
+///
+/// Full-amount emergency_unstake with the same accounts: pro-rata mode while undercollateralized,
+/// emergency_withdraw_fee_bps routed to the Insurance Pool PDA. Rewards stay in pending_rewards,
+/// claimable once the pause is lifted.
+pub fn emergency_withdraw(ctx: Context<EmergencyUnstake>) -> Result<()> {
+    msg!("[EMERGENCY_WITHDRAW] Lender: {}", ctx.accounts.lender.key());
+
+    require!(ctx.accounts.treasury_pool.emergency_pause, ErrorCode::ProgramNotPaused);
+    let amount = ctx.accounts.lender_stake.deposited_amount;
+    require!(amount > 0, ErrorCode::InsufficientStake);
+
+    emergency_unstake(ctx, amount)
+}
@@ -0,0 +1,187 @@
+use crate::errors::ErrorCode;
+use crate::events::{Claimed, EmergencyPauseToggled, SolUnstaked};
+use crate::states::{LenderStake, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Full exit in one transaction: claim all rewards, then withdraw the whole deposit
+///
+/// Same accounting as claim_rewards followed by unstake_sol(deposited_amount), including
+/// pro-rata mode while undercollateralized. Locked positions must use unstake_sol (accept_penalty)
+/// and backers with a claim_authority must claim through the delegate first. Positions holding a
+/// deposit receipt exit through unstake_sol, which burns it.
+#[derive(Accounts)]
+pub struct ExitPosition<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Treasury Pool PDA (holds deposits)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (pays the rewards, assets for pro-rata withdrawals)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [LenderStake::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, LenderStake>,
+
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn exit_position(ctx: Context<ExitPosition>) -> Result<()> {
+    msg!("[EXIT] Lender: {}", ctx.accounts.lender.key());
+
+    // Get account info before mutable borrows
+    let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let lender_info = ctx.accounts.lender.to_account_info();
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let lender_stake = &mut ctx.accounts.lender_stake;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_CLAIM), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_WITHDRAW), ErrorCode::ProgramPaused);
+    require!(lender_stake.claim_authority.is_none(), ErrorCode::Unauthorized);
+    require!(lender_stake.receipt_mint.is_none(), ErrorCode::DepositReceiptRequired);
+    require!(current_time >= lender_stake.locked_until, ErrorCode::StakeLocked);
+
+    let amount = lender_stake.deposited_amount;
+    require!(amount > 0, ErrorCode::InsufficientStake);
+
+    // 1. Claim everything (pending_rewards + reward_per_share), before the deposit changes
+    let claim_amount = lender_stake.calculate_claimable_rewards(treasury_pool.reward_per_share)?;
+    if claim_amount > 0 {
+        require!(
+            !lender_stake.claim_cooldown_active(treasury_pool.claim_cooldown_secs, current_time),
+            ErrorCode::ClaimCooldownActive
+        );
+        require!(
+            treasury_pool.reward_pool_balance >= claim_amount,
+            ErrorCode::InsufficientTreasuryFunds
+        );
+        require!(
+            reward_pool_info.lamports() >= claim_amount,
+            ErrorCode::InsufficientTreasuryFunds
+        );
+
+        // Circuit breaker: an exit whose claim would exceed the outflow limit pauses the program
+        // and pays nothing (rewards nor principal)
+        if treasury_pool.record_reward_outflow(claim_amount, current_time)? {
+            msg!("[EXIT] Reward outflow limit reached - program paused, nothing paid");
+            emit!(EmergencyPauseToggled {
+                paused: true,
+                toggled_at: current_time,
+            });
+            return Ok(());
+        }
+
+        lender_stake.claimed_total = lender_stake
+            .claimed_total
+            .checked_add(claim_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        treasury_pool.pay_rewards(claim_amount)?;
+    }
+    lender_stake.pending_rewards = 0;
+    msg!("[EXIT] Claimed: {} lamports", claim_amount);
+
+    // 2. Withdraw the whole deposit
+    // Available balance = actual balance - rent exemption
+    let rent_exemption = Rent::get()?.minimum_balance(treasury_pda_info.data_len());
+    let available_balance = treasury_pda_info
+        .lamports()
+        .checked_sub(rent_exemption)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Pro-rata mode: share the shortfall instead of paying early movers in full
+    // Rewards just paid out are no longer Reward Pool assets
+    let (payout, haircut) = if treasury_pool.undercollateralized {
+        let reward_pool_lamports = reward_pool_info
+            .lamports()
+            .checked_sub(claim_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let (assets, obligations) = treasury_pool
+            .collateral_position(available_balance, reward_pool_lamports)?;
+        msg!("[EXIT] Pool undercollateralized - pro-rata mode. Assets: {} lamports, obligations: {} lamports", assets, obligations);
+        TreasuryPool::pro_rata_withdrawal(amount, assets, obligations)?
+    } else {
+        (amount, 0)
+    };
+
+    require!(available_balance >= payout, ErrorCode::InsufficientLiquidBalance);
+
+    msg!("[EXIT] Payout: {} lamports, haircut: {} lamports", payout, haircut);
+
+    // Full amount is debited even in pro-rata mode so the haircut cannot be withdrawn again
+    lender_stake.update_time_weighted(0, current_time)?;
+    lender_stake.deposited_amount = 0;
+    if treasury_pool.undercollateralized {
+        lender_stake.record_pro_rata_withdrawal(payout, haircut)?;
+    }
+    lender_stake.is_active = false;
+    lender_stake.reward_debt = 0;
+
+    treasury_pool.total_deposited = treasury_pool
+        .total_deposited
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    treasury_pool.liquid_balance = treasury_pool.liquid_balance.saturating_sub(payout);
+
+    // Transfer rewards (Reward Pool PDA) and principal (Treasury PDA) -> lender via lamport mutation
+    {
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        let mut treasury_lamports = treasury_pda_info.try_borrow_mut_lamports()?;
+        let mut lender_lamports = lender_info.try_borrow_mut_lamports()?;
+
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_sub(claim_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **treasury_lamports = (**treasury_lamports)
+            .checked_sub(payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **lender_lamports = (**lender_lamports)
+            .checked_add(claim_amount)
+            .and_then(|x| x.checked_add(payout))
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    emit!(Claimed {
+        backer: lender_stake.backer,
+        amount: claim_amount,
+        claimed_total: lender_stake.claimed_total,
+        reward_per_share: treasury_pool.reward_per_share,
+        claim_authority: None,
+        destination: lender_info.key(),
+        claimed_at: current_time,
+    });
+
+    emit!(SolUnstaked {
+        lender: lender_stake.backer,
+        amount: payout,
+        remaining_staked: 0,
+        haircut,
+        penalty: 0,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::states::{BackerDeposit, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Read a backer's claimable rewards (no state mutation)
+///
+/// Returned via return data so wallets can call it through simulateTransaction
+/// (`program.methods.getClaimableRewards().view()`) instead of replicating
+/// (deposited_amount * reward_per_share - reward_debt) / PRECISION off-chain.
+#[derive(Accounts)]
+pub struct GetClaimableRewards<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        seeds = [BackerDeposit::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    /// CHECK: Backer whose rewards are read (no signature needed)
+    pub lender: UncheckedAccount<'info>,
+}
+
+/// Return buffer of get_claimable_rewards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClaimableRewards {
+    pub claimable: u64,          // pending_rewards + unsettled rewards (lamports)
+    pub reward_per_share: u128,  // Current accumulator (scaled by PRECISION)
+    pub deposited_amount: u64,   // Backer principal (lamports)
+    pub deposit_start_ts: i64,   // Start of the current position (0 = nothing deposited)
+    pub time_weighted_amount: u128, // Lamport-seconds held, brought up to now
+    pub loyalty_multiplier_bps: u64, // 10000 = 1x (informational, not applied to rewards yet)
+}
+
+pub fn get_claimable_rewards(ctx: Context<GetClaimableRewards>) -> Result<ClaimableRewards> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let lender_stake = &ctx.accounts.lender_stake;
+
+    let claimable = lender_stake.calculate_claimable_rewards(treasury_pool.reward_per_share)?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    msg!("[CLAIMABLE] Backer: {}", lender_stake.backer);
+    msg!("[CLAIMABLE] Claimable: {} lamports, deposited: {} lamports", claimable, lender_stake.deposited_amount);
+
+    Ok(ClaimableRewards {
+        claimable,
+        reward_per_share: treasury_pool.reward_per_share,
+        deposited_amount: lender_stake.deposited_amount,
+        deposit_start_ts: lender_stake.deposit_start_ts,
+        time_weighted_amount: lender_stake.time_weighted_amount_at(current_time)?,
+        loyalty_multiplier_bps: lender_stake.loyalty_multiplier_bps(current_time)?,
+    })
+}
@@ -0,0 +1,53 @@
+use crate::errors::ErrorCode;
+use crate::states::{BackerDeposit, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Read a backer's governance weight (no state mutation)
+///
+/// Returned via return data so a governance program can CPI into it (or a client can
+/// simulate it) and compute deposited_amount / total_deposited without trusting an indexer.
+#[derive(Accounts)]
+pub struct GetVotingPower<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        seeds = [BackerDeposit::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    /// CHECK: Backer whose voting power is read (no signature needed)
+    pub lender: UncheckedAccount<'info>,
+}
+
+/// Return buffer of get_voting_power
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VotingPower {
+    pub deposited_amount: u64,   // Backer principal (lamports)
+    pub total_deposited: u64,    // Denominator: all SOL principal in the pool (lamports)
+    pub is_locked: bool,         // Principal is still inside its lock period
+    pub locked_until: i64,       // Unlock timestamp (0 = never locked)
+}
+
+pub fn get_voting_power(ctx: Context<GetVotingPower>) -> Result<VotingPower> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let lender_stake = &ctx.accounts.lender_stake;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let is_locked = current_time < lender_stake.locked_until;
+
+    msg!("[VOTING_POWER] Backer: {}", lender_stake.backer);
+    msg!("[VOTING_POWER] Deposited: {} / {} lamports, locked: {}", lender_stake.deposited_amount, treasury_pool.total_deposited, is_locked);
+
+    Ok(VotingPower {
+        deposited_amount: lender_stake.deposited_amount,
+        total_deposited: treasury_pool.total_deposited,
+        is_locked,
+        locked_until: lender_stake.locked_until,
+    })
+}
@@ -1,7 +1,33 @@
 pub mod claim_rewards;
+pub mod claim_rewards_spl;
+pub mod close_stake;
+pub mod compound_rewards;
+pub mod emergency_unstake;
+pub mod emergency_withdraw;
+pub mod exit_position;
+pub mod get_claimable_rewards;
+pub mod get_voting_power;
+pub mod preview_exit;
+pub mod project_rewards;
+pub mod set_claim_authority;
 pub mod stake_sol;
+pub mod stake_spl;
 pub mod unstake_sol;
+pub mod unstake_spl;
 
 pub use claim_rewards::*;
+pub use claim_rewards_spl::*;
+pub use close_stake::*;
+pub use compound_rewards::*;
+pub use emergency_unstake::*;
+pub use emergency_withdraw::*;
+pub use exit_position::*;
+pub use get_claimable_rewards::*;
+pub use get_voting_power::*;
+pub use preview_exit::*;
+pub use project_rewards::*;
+pub use set_claim_authority::*;
 pub use stake_sol::*;
+pub use stake_spl::*;
 pub use unstake_sol::*;
+pub use unstake_spl::*;
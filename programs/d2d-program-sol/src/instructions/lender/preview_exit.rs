@@ -0,0 +1,92 @@
+use crate::errors::ErrorCode;
+use crate::events::ExitPreview;
+use crate::states::{BackerDeposit, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Quote the lamports a full exit would yield right now (read-only)
+///
+/// Mirrors unstake_sol(deposited_amount, accept_penalty = true) followed by claim_rewards:
+/// - Pro-rata payout while the pool is undercollateralized
+/// - Early-unstake penalty while the stake is locked
+/// - Claimable rewards (pending + unsettled)
+#[derive(Accounts)]
+pub struct PreviewExit<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Treasury Pool PDA (balance used for pro-rata mode)
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump
+    )]
+    pub treasury_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (balance used for pro-rata mode)
+    #[account(
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [BackerDeposit::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    /// CHECK: Backer whose exit is quoted (no signature needed)
+    pub lender: UncheckedAccount<'info>,
+}
+
+pub fn preview_exit(ctx: Context<PreviewExit>) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let lender_stake = &ctx.accounts.lender_stake;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let principal = lender_stake.deposited_amount;
+    let rewards = lender_stake.calculate_claimable_rewards(treasury_pool.reward_per_share)?;
+
+    // Same pro-rata computation as unstake_sol
+    let (payout, haircut) = if treasury_pool.undercollateralized {
+        let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
+        let rent_exemption = Rent::get()?.minimum_balance(treasury_pda_info.data_len());
+        let available_balance = treasury_pda_info
+            .lamports()
+            .checked_sub(rent_exemption)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let (assets, obligations) = treasury_pool
+            .collateral_position(available_balance, ctx.accounts.reward_pool.lamports())?;
+        TreasuryPool::pro_rata_withdrawal(principal, assets, obligations)?
+    } else {
+        (principal, 0)
+    };
+
+    let penalty = if current_time < lender_stake.locked_until {
+        treasury_pool.early_unstake_penalty(payout)?
+    } else {
+        0
+    };
+
+    let net = payout
+        .checked_sub(penalty)
+        .and_then(|x| x.checked_add(rewards))
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    msg!("[PREVIEW_EXIT] Principal: {}, haircut: {}, rewards: {}, penalty: {}, net: {} lamports",
+         principal, haircut, rewards, penalty, net);
+
+    emit!(ExitPreview {
+        lender: lender_stake.backer,
+        principal,
+        haircut,
+        rewards,
+        penalty,
+        net,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::errors::ErrorCode;
+use crate::events::ProjectedRewards;
+use crate::states::{BackerDeposit, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Project a backer's future rewards (read-only estimate)
+///
+/// ESTIMATE ONLY: extrapolates the recent reward-fee velocity
+/// (fees credited per second over the last window) to `horizon_seconds`.
+/// Future fees depend on deployments and are not guaranteed.
+#[derive(Accounts)]
+pub struct ProjectRewards<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        seeds = [BackerDeposit::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    /// CHECK: Backer whose position is projected (no signature needed)
+    pub lender: UncheckedAccount<'info>,
+}
+
+pub fn project_rewards(ctx: Context<ProjectRewards>, horizon_seconds: i64) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let lender_stake = &ctx.accounts.lender_stake;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(horizon_seconds > 0, ErrorCode::InvalidAmount);
+
+    let (window_fees, window_seconds) = treasury_pool.fee_velocity(current_time);
+    let projected_amount =
+        treasury_pool.project_rewards(lender_stake.deposited_amount, horizon_seconds, current_time)?;
+
+    msg!("[PROJECT] Estimate only - fee velocity: {} lamports / {} seconds", window_fees, window_seconds);
+    msg!("[PROJECT] Projected rewards over {} seconds: {} lamports", horizon_seconds, projected_amount);
+
+    emit!(ProjectedRewards {
+        lender: lender_stake.backer,
+        horizon_seconds,
+        projected_amount,
+        window_fees,
+        window_seconds,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::events::ClaimAuthorityUpdated;
+use crate::states::LenderStake;
+use anchor_lang::prelude::*;
+
+/// Backer delegate reward claims to another wallet (e.g. a hot wallet)
+///
+/// While set, only the claim_authority can call claim_rewards for this deposit.
+/// Principal withdrawals (unstake_sol) stay restricted to the backer.
+#[derive(Accounts)]
+pub struct SetClaimAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [LenderStake::PREFIX_SEED, lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, LenderStake>,
+
+    pub lender: Signer<'info>,
+}
+
+/// claim_authority: None returns claim rights to the backer
+pub fn set_claim_authority(ctx: Context<SetClaimAuthority>, claim_authority: Option<Pubkey>) -> Result<()> {
+    let lender_stake = &mut ctx.accounts.lender_stake;
+
+    let old_claim_authority = lender_stake.claim_authority;
+    lender_stake.claim_authority = claim_authority;
+
+    msg!("[CLAIM_AUTHORITY] Backer {}: {:?} -> {:?}", lender_stake.backer, old_claim_authority, claim_authority);
+
+    emit!(ClaimAuthorityUpdated {
+        backer: lender_stake.backer,
+        old_claim_authority,
+        new_claim_authority: claim_authority,
+        updated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -4,6 +4,8 @@ use crate::states::{BackerDeposit, TreasuryPool};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_lang::solana_program::rent::Rent;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, FreezeAccount, Mint, MintTo, Token, TokenAccount};
 
 /// Deposit SOL into the program (reward-per-share model)
 /// 
@@ -14,6 +16,7 @@ use anchor_lang::solana_program::rent::Rent;
 /// 4. Transfer fees to respective pools
 /// 5. Update total_deposited and liquid_balance
 /// 6. Update backer's deposited_amount and reward_debt
+/// 7. Optionally mint the backer's deposit receipt (see receipt_mint)
 #[derive(Accounts)]
 pub struct StakeSol<'info> {
     /// CHECK: Treasury Pool - will be migrated if needed
@@ -42,17 +45,42 @@ pub struct StakeSol<'info> {
     pub lender: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+
+    /// Optional deposit receipt: pass receipt_mint, receipt_token_account, token_program and
+    /// associated_token_program to opt in; omit them to stake without any token accounts.
+    /// 1 unit is minted to the backer's ATA once and frozen (non-transferable) until a full unstake_sol burns it
+    #[account(
+        init_if_needed,
+        payer = lender,
+        seeds = [BackerDeposit::RECEIPT_MINT_SEED, lender.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = treasury_pool,
+        mint::freeze_authority = treasury_pool
+    )]
+    pub receipt_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = lender,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = lender
+    )]
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
 }
 
 /// Deposit SOL (reward-per-share model)
 /// 
 /// Before updating deposited_amount, settle pending rewards by updating reward_debt
-pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64) -> Result<()> {
+pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, lock_period: i64) -> Result<()> {
     msg!("[STAKE] Starting stake_sol instruction");
     msg!("[STAKE] Deposit amount: {} lamports", deposit_amount);
     
     // Verify treasury pool PDA matches
-    let (expected_treasury_pool, _bump) = Pubkey::find_program_address(
+    let (expected_treasury_pool, treasury_pool_bump) = Pubkey::find_program_address(
         &[TreasuryPool::PREFIX_SEED],
         ctx.program_id,
     );
@@ -86,6 +114,15 @@ pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64)
             msg!("[STAKE] Please call migrate_treasury_pool() instruction first");
             anchor_lang::error!(crate::errors::ErrorCode::InvalidAccountData)
         })?;
+    require!(treasury_pool.is_current_layout(), ErrorCode::StaleLayout);
+
+    // Reject dust deposits before touching lender_stake
+    // Anchor runs init_if_needed before the handler, but a failed transaction rolls back
+    // the allocation, so rejected attempts never leave an orphaned BackerDeposit PDA
+    require!(
+        deposit_amount >= treasury_pool.min_deposit,
+        ErrorCode::DepositTooSmall
+    );
     
     let lender_stake = &mut ctx.accounts.lender_stake;
 
@@ -93,8 +130,13 @@ pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64)
          treasury_pool.reward_per_share, treasury_pool.total_deposited);
     msg!("[STAKE] Lender: {}", ctx.accounts.lender.key());
 
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPOSIT), ErrorCode::ProgramPaused);
     require!(deposit_amount > 0, ErrorCode::InvalidAmount);
+    require!(lock_period >= 0, ErrorCode::InvalidAmount);
+    require!(
+        lock_period <= TreasuryPool::MAX_LOCK_PERIOD,
+        ErrorCode::LockPeriodTooLong
+    );
 
     // Check lender has sufficient lamports
     // Need to account for:
@@ -147,6 +189,17 @@ pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64)
         lender_stake.reward_debt = 0;
         lender_stake.pending_rewards = 0;
         lender_stake.claimed_total = 0;
+        lender_stake.pro_rata_withdrawn = 0;
+        lender_stake.haircut_total = 0;
+        lender_stake.last_deposit_at = 0;
+        lender_stake.locked_until = 0;
+        lender_stake.layout_version = BackerDeposit::LAYOUT_VERSION;
+        lender_stake.claim_authority = None;
+        lender_stake.receipt_mint = None;
+        lender_stake.deposit_start_ts = 0;
+        lender_stake.time_weighted_amount = 0;
+        lender_stake.time_weighted_updated_at = 0;
+        lender_stake.last_action_ts = 0;
         lender_stake.is_active = true;
         lender_stake.bump = ctx.bumps.lender_stake;
     } else {
@@ -167,47 +220,67 @@ pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64)
     // NO FEES TAKEN FROM BACKER - 100% goes to TreasuryPool
     // Fees come from developers when they pay for deployments (borrowed_amount * 1% monthly)
 
-    // Handle excess rewards: If fees were credited before any deposits,
-    // we need to distribute those excess rewards proportionally to all backers
-    // This ensures backers receive 1-1.2% returns when their SOL is fully utilized
-    let total_deposited_before = treasury_pool.total_deposited;
-    if total_deposited_before == 0 && treasury_pool.reward_pool_balance > 0 {
-        // There are excess rewards (fees credited before any deposits)
-        // Distribute them proportionally based on the new total deposits after this stake
-        let excess_rewards = treasury_pool.reward_pool_balance;
-        let new_total_deposited = deposit_amount;
-        
-        // reward_per_share = excess_rewards * PRECISION / new_total_deposited
-        // This ensures the first backer(s) receive excess rewards proportionally
-        let excess_reward_per_share = (excess_rewards as u128)
-            .checked_mul(TreasuryPool::PRECISION)
-            .ok_or(ErrorCode::CalculationOverflow)?
-            .checked_div(new_total_deposited as u128)
-            .ok_or(ErrorCode::CalculationOverflow)?;
-        
-        msg!("[STAKE] Excess rewards detected: {} lamports", excess_rewards);
-        msg!("[STAKE] Calculating reward_per_share from excess: {}", excess_reward_per_share);
-        msg!("[STAKE] New total deposited: {} lamports", new_total_deposited);
-        
-        // Add excess reward_per_share to current reward_per_share
-        treasury_pool.reward_per_share = treasury_pool
-            .reward_per_share
-            .checked_add(excess_reward_per_share)
-            .ok_or(ErrorCode::CalculationOverflow)?;
-        
-        msg!("[STAKE] Updated reward_per_share to: {}", treasury_pool.reward_per_share);
-    } else if total_deposited_before > 0 && treasury_pool.reward_pool_balance > 0 {
-        // Check if there are still excess rewards (reward_pool_balance > total claimable)
-        // This can happen if fees were credited when total_deposited was lower
-        // For now, we let the normal credit_fee_to_pool logic handle this
-        // Future deposits will benefit from accumulated reward_per_share
+    // Fees credited while the pool was empty go to the first depositor(s)
+    // Only unallocated_rewards is used, never the whole reward_pool_balance: whatever else is in
+    // there is still owed to backers who already left, and re-crediting it would pay it twice
+    // The depositor's reward_debt is taken at the pre-allocation reward_per_share so the
+    // allocated delta is actually claimable by them rather than absorbed into their debt
+    let reward_per_share_before = treasury_pool.reward_per_share;
+    let allocated = treasury_pool.allocate_unallocated_rewards(deposit_amount)?;
+    if allocated > 0 {
+        msg!("[STAKE] Allocated {} lamports of unallocated rewards, reward_per_share: {}",
+             allocated, treasury_pool.reward_per_share);
     }
 
-    // Update deposit amount (100% of deposit_amount)
-    lender_stake.deposited_amount = lender_stake
+    // Guard against reward_debt overflow when reward_per_share has been pumped high
+    // (e.g. fees credited against a tiny total_deposited before a large deposit)
+    let new_deposited_amount = lender_stake
         .deposited_amount
         .checked_add(deposit_amount)
         .ok_or(ErrorCode::CalculationOverflow)?;
+    if BackerDeposit::reward_debt_for(new_deposited_amount, treasury_pool.reward_per_share).is_err() {
+        msg!("[STAKE] ERROR: reward_debt would overflow at reward_per_share {}", treasury_pool.reward_per_share);
+        msg!("[STAKE] Max position at current reward_per_share: {} lamports - deposit a smaller amount",
+             BackerDeposit::max_deposit_for(treasury_pool.reward_per_share));
+        return Err(ErrorCode::RewardDebtOverflow.into());
+    }
+
+    // Update deposit amount (100% of deposit_amount)
+    let current_time = Clock::get()?.unix_timestamp;
+    lender_stake.update_time_weighted(new_deposited_amount, current_time)?;
+    lender_stake.deposited_amount = new_deposited_amount;
+
+    // Per-backer cap (0 = unlimited). Existing over-cap positions are kept, only new deposits are blocked
+    if treasury_pool.max_deposit_per_backer > 0 {
+        require!(
+            lender_stake.deposited_amount <= treasury_pool.max_deposit_per_backer,
+            ErrorCode::DepositCapExceeded
+        );
+    }
+    // Pool-wide cap (0 = unlimited) keeps the deposit base sized to deployment demand
+    if treasury_pool.max_total_deposited > 0 {
+        let new_total_deposited = treasury_pool
+            .total_deposited
+            .checked_add(deposit_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        require!(
+            new_total_deposited <= treasury_pool.max_total_deposited,
+            ErrorCode::PoolCapReached
+        );
+    }
+    // Snapshot for governance: fresh stake cannot vote on already-open proposals
+    lender_stake.last_deposit_at = current_time;
+    // Claim cooldown anchor (compound_rewards does not move it)
+    lender_stake.last_action_ts = current_time;
+
+    // Lock applies to the whole position; a new lock never shortens an existing one
+    if lock_period > 0 {
+        let locked_until = current_time
+            .checked_add(lock_period)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        lender_stake.locked_until = lender_stake.locked_until.max(locked_until);
+        msg!("[STAKE] Position locked until {}", lender_stake.locked_until);
+    }
 
     // Update treasury pool state
     treasury_pool.total_deposited = treasury_pool
@@ -231,9 +304,54 @@ pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64)
     system_program::transfer(deposit_cpi, deposit_amount)?;
 
     // Update reward_debt after deposit
-    // This captures the current reward_per_share for the new total deposited_amount
+    // This captures reward_per_share (before any unallocated rewards were handed out) for the new total deposited_amount
     // pending_rewards already settled above (if not new deposit), safe to update debt
-    lender_stake.update_reward_debt(treasury_pool.reward_per_share)?;
+    lender_stake.update_reward_debt(reward_per_share_before)?;
+
+    // Deposit receipt (opt-in): minted once per position, kept across top-ups
+    if let (Some(receipt_mint), Some(receipt_token_account), Some(token_program)) = (
+        ctx.accounts.receipt_mint.as_ref(),
+        ctx.accounts.receipt_token_account.as_ref(),
+        ctx.accounts.token_program.as_ref(),
+    ) {
+        if lender_stake.receipt_mint.is_none() {
+            // Mint and freeze authority is the Treasury Pool PDA
+            let treasury_seeds: &[&[u8]] = &[TreasuryPool::PREFIX_SEED, &[treasury_pool_bump]];
+            let signer_seeds = &[treasury_seeds];
+
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    MintTo {
+                        mint: receipt_mint.to_account_info(),
+                        to: receipt_token_account.to_account_info(),
+                        authority: treasury_pool_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                1,
+            )?;
+            token::freeze_account(CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                FreezeAccount {
+                    account: receipt_token_account.to_account_info(),
+                    mint: receipt_mint.to_account_info(),
+                    authority: treasury_pool_info.clone(),
+                },
+                signer_seeds,
+            ))?;
+
+            lender_stake.receipt_mint = Some(receipt_mint.key());
+            msg!("[STAKE] Deposit receipt minted: {}", receipt_mint.key());
+
+            emit!(crate::events::DepositReceiptMinted {
+                backer: lender_stake.backer,
+                receipt_mint: receipt_mint.key(),
+                receipt_token_account: receipt_token_account.key(),
+                minted_at: current_time,
+            });
+        }
+    }
 
     // Serialize updated treasury_pool back to account
     let mut data = treasury_pool_info.try_borrow_mut_data()?;
@@ -243,7 +361,7 @@ pub fn stake_sol(ctx: Context<StakeSol>, deposit_amount: u64, _lock_period: i64)
         lender: lender_stake.backer,
         amount: deposit_amount, // 100% of deposit (no fees)
         total_staked: lender_stake.deposited_amount,
-        lock_period: 0, // Not used in new model
+        lock_period,
     });
     
     // Emit detailed deposit event
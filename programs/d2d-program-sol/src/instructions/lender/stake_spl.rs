@@ -0,0 +1,129 @@
+use crate::errors::ErrorCode;
+use crate::events::SplStaked;
+use crate::states::{BackerDeposit, TokenTreasury, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Deposit SPL tokens into a token treasury (reward-per-share model, rewards in the same mint)
+///
+/// Flow:
+/// 1. Settle pending rewards on an existing position
+/// 2. transfer_checked backer token account -> vault
+/// 3. Update total_deposited and the position's deposited_amount and reward_debt
+#[derive(Accounts)]
+pub struct StakeSpl<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [TokenTreasury::PREFIX_SEED, mint.key().as_ref()],
+        bump = token_treasury.bump
+    )]
+    pub token_treasury: Account<'info, TokenTreasury>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = token_treasury.vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = lender,
+        space = 8 + BackerDeposit::INIT_SPACE,
+        seeds = [TokenTreasury::STAKE_SEED, mint.key().as_ref(), lender.key().as_ref()],
+        bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.mint == token_treasury.mint @ ErrorCode::InvalidMint,
+        constraint = lender_token_account.owner == lender.key() @ ErrorCode::Unauthorized
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lender: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn stake_spl(ctx: Context<StakeSpl>, amount: u64) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let token_treasury = &mut ctx.accounts.token_treasury;
+    let lender_stake = &mut ctx.accounts.lender_stake;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPOSIT), ErrorCode::ProgramPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    if lender_stake.backer == Pubkey::default() {
+        lender_stake.backer = ctx.accounts.lender.key();
+        lender_stake.deposited_amount = 0;
+        lender_stake.reward_debt = 0;
+        lender_stake.pending_rewards = 0;
+        lender_stake.claimed_total = 0;
+        lender_stake.pro_rata_withdrawn = 0;
+        lender_stake.haircut_total = 0;
+        lender_stake.last_deposit_at = 0;
+        lender_stake.locked_until = 0;
+        lender_stake.layout_version = BackerDeposit::LAYOUT_VERSION;
+        lender_stake.claim_authority = None;
+        lender_stake.receipt_mint = None;
+        lender_stake.deposit_start_ts = 0;
+        lender_stake.time_weighted_amount = 0;
+        lender_stake.time_weighted_updated_at = 0;
+        lender_stake.last_action_ts = 0;
+        lender_stake.bump = ctx.bumps.lender_stake;
+    } else {
+        // Preserve rewards earned on the current deposit before reward_debt is recalculated
+        lender_stake.settle_pending_rewards(token_treasury.reward_per_share)?;
+    }
+    lender_stake.is_active = true;
+
+    let new_deposited_amount = lender_stake
+        .deposited_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    BackerDeposit::reward_debt_for(new_deposited_amount, token_treasury.reward_per_share)?;
+
+    lender_stake.update_time_weighted(new_deposited_amount, Clock::get()?.unix_timestamp)?;
+    lender_stake.deposited_amount = new_deposited_amount;
+    lender_stake.last_deposit_at = Clock::get()?.unix_timestamp;
+    lender_stake.update_reward_debt(token_treasury.reward_per_share)?;
+
+    token_treasury.total_deposited = token_treasury
+        .total_deposited
+        .checked_add(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    let transfer_cpi = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.lender_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.lender.to_account_info(),
+        },
+    );
+    token::transfer_checked(transfer_cpi, amount, token_treasury.decimals)?;
+
+    msg!("[STAKE_SPL] {} staked {} of mint {}, position: {}", lender_stake.backer, amount, token_treasury.mint, lender_stake.deposited_amount);
+
+    emit!(SplStaked {
+        backer: lender_stake.backer,
+        mint: token_treasury.mint,
+        amount,
+        total_staked: lender_stake.deposited_amount,
+    });
+
+    Ok(())
+}
@@ -3,12 +3,17 @@ use crate::events::SolUnstaked;
 use crate::states::{BackerDeposit, TreasuryPool};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token::{self, Burn, Mint, ThawAccount, Token, TokenAccount};
 
 /// Unstake SOL (withdraw deposit)
 /// 
 /// Reward-per-share model:
 /// - If liquid_balance >= amount: withdraw immediately
-/// - Else: create withdraw_request (to be implemented)
+/// - Else: create withdraw_request (to be implemented); once queued, stake_sol must reject
+///   further deposits until it is fulfilled (PendingWithdrawalExists) so the queued amount stays fixed
+/// - If the pool is flagged undercollateralized: pro-rata payout (shortfall shared by all backers)
+/// - If the stake is still locked: requires accept_penalty, penalty_bps goes to the reward pool
+/// - If the position holds a deposit receipt: a full withdrawal thaws and burns it
 #[derive(Accounts)]
 pub struct UnstakeSol<'info> {
     /// CHECK: Treasury Pool - will be migrated if needed
@@ -30,6 +35,14 @@ pub struct UnstakeSol<'info> {
         bump
     )]
     pub treasury_pda: UncheckedAccount<'info>,
+
+    /// CHECK: Reward Pool PDA (assets for pro-rata withdrawals, receives early-unstake penalties)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
     
     #[account(
         mut,
@@ -42,12 +55,36 @@ pub struct UnstakeSol<'info> {
     pub lender: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+
+    /// Deposit receipt minted by stake_sol; required for a full withdrawal when lender_stake.receipt_mint is set
+    #[account(
+        mut,
+        constraint = lender_stake.receipt_mint == Some(receipt_mint.key()) @ ErrorCode::InvalidMint
+    )]
+    pub receipt_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = lender
+    )]
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
 }
 
 /// Unstake SOL (withdraw principal)
 /// 
 /// Withdraws directly from liquid_balance based on deposited_amount in BackerDeposit
-pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
+///
+/// Pro-rata mode (only while treasury_pool.undercollateralized is set):
+/// - Payout = amount * assets / obligations
+/// - The full amount is debited from the deposit, the difference is forfeited as haircut
+///
+/// Early unstake (before locked_until):
+/// - Rejected unless accept_penalty is set
+/// - penalty = payout * penalty_bps / 10000, credited to the reward pool (reward_per_share)
+pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64, accept_penalty: bool) -> Result<()> {
     
     // Verify treasury_pda is the same as treasury_pool
     require!(
@@ -78,13 +115,14 @@ pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
             msg!("[UNSTAKE] Please call migrate_treasury_pool() instruction first");
             anchor_lang::error!(crate::errors::ErrorCode::InvalidAccountData)
         })?;
+    require!(treasury_pool.is_current_layout(), ErrorCode::StaleLayout);
     
     // Get account info and bump before mutable borrows
     let treasury_pda_info = ctx.accounts.treasury_pda.to_account_info();
     
     let lender_stake = &mut ctx.accounts.lender_stake;
 
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_WITHDRAW), ErrorCode::ProgramPaused);
     require!(amount > 0, ErrorCode::InvalidAmount);
     require!(
         amount <= lender_stake.deposited_amount,
@@ -122,10 +160,37 @@ pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
     msg!("[UNSTAKE] Available balance: {} lamports", available_balance);
     msg!("[UNSTAKE] liquid_balance (from struct): {} lamports", treasury_pool.liquid_balance);
     
+    // Pro-rata mode: share the shortfall instead of paying early movers in full
+    let (payout, haircut) = if treasury_pool.undercollateralized {
+        let (assets, obligations) = treasury_pool
+            .collateral_position(available_balance, ctx.accounts.reward_pool.lamports())?;
+        msg!("[UNSTAKE] Pool undercollateralized - pro-rata mode. Assets: {} lamports, obligations: {} lamports", assets, obligations);
+        TreasuryPool::pro_rata_withdrawal(amount, assets, obligations)?
+    } else {
+        (amount, 0)
+    };
+    if haircut > 0 {
+        msg!("[UNSTAKE] Pro-rata payout: {} lamports, haircut: {} lamports", payout, haircut);
+    }
+
+    // Early unstake from a locked position
+    let current_time = Clock::get()?.unix_timestamp;
+    let penalty = if current_time < lender_stake.locked_until {
+        require!(accept_penalty, ErrorCode::StakeLocked);
+        let penalty = treasury_pool.early_unstake_penalty(payout)?;
+        msg!("[UNSTAKE] Early unstake (locked until {}), penalty: {} lamports", lender_stake.locked_until, penalty);
+        penalty
+    } else {
+        0
+    };
+    let lender_payout = payout
+        .checked_sub(penalty)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
     // Check if available balance is sufficient for withdrawal
     // Use actual account balance as source of truth (may be out of sync with liquid_balance)
-    if available_balance < amount {
-        msg!("[UNSTAKE] ERROR: Insufficient available balance. Available: {} lamports, Requested: {} lamports", available_balance, amount);
+    if available_balance < payout {
+        msg!("[UNSTAKE] ERROR: Insufficient available balance. Available: {} lamports, Requested: {} lamports", available_balance, payout);
         return Err(ErrorCode::InsufficientLiquidBalance.into());
     }
     
@@ -140,16 +205,37 @@ pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
     }
 
     // Update backer deposit
-    lender_stake.deposited_amount = lender_stake
+    // Full amount is debited even in pro-rata mode so the haircut cannot be withdrawn again
+    let new_deposited_amount = lender_stake
         .deposited_amount
         .checked_sub(amount)
         .ok_or(ErrorCode::CalculationOverflow)?;
+    lender_stake.update_time_weighted(new_deposited_amount, current_time)?;
+    lender_stake.deposited_amount = new_deposited_amount;
+    if treasury_pool.undercollateralized {
+        lender_stake.record_pro_rata_withdrawal(payout, haircut)?;
+    }
+
+    // Update treasury pool state
+    treasury_pool.total_deposited = treasury_pool
+        .total_deposited
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    
+    // Penalty goes to the other backers via reward_per_share
+    // This backer's remaining deposit is excluded: their reward_debt is reset to the new
+    // reward_per_share below, so any share credited to it could never be claimed
+    if penalty > 0 {
+        treasury_pool.credit_fee_to_pool_excluding(penalty, new_deposited_amount)?;
+        treasury_pool.record_fee_credit(penalty, current_time)?;
+    }
 
     // If fully withdrawn, deactivate
     if lender_stake.deposited_amount == 0 {
         lender_stake.is_active = false;
         lender_stake.reward_debt = 0;
         // Keep pending_rewards intact - user can still claim them later
+        // They were settled above, so resetting reward_debt forfeits nothing
         msg!("[UNSTAKE] Fully withdrawn. Pending rewards preserved: {} lamports", lender_stake.pending_rewards);
     } else {
         // If there's remaining deposit, ensure is_active = true
@@ -160,16 +246,24 @@ pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
         lender_stake.update_reward_debt(treasury_pool.reward_per_share)?;
     }
 
-    // Update treasury pool state
-    treasury_pool.total_deposited = treasury_pool
-        .total_deposited
-        .checked_sub(amount)
-        .ok_or(ErrorCode::CalculationOverflow)?;
-    
+    // Burn the deposit receipt with the position it represents
+    if lender_stake.deposited_amount == 0 {
+        burn_deposit_receipt(
+            lender_stake,
+            ctx.accounts.receipt_mint.as_ref(),
+            ctx.accounts.receipt_token_account.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            &treasury_pool_info,
+            ctx.bumps.treasury_pool,
+            &ctx.accounts.lender.to_account_info(),
+            current_time,
+        )?;
+    }
+
     // Deduct from liquid_balance (shared between deployments and withdrawals)
     treasury_pool.liquid_balance = treasury_pool
         .liquid_balance
-        .checked_sub(amount)
+        .checked_sub(payout)
         .ok_or(ErrorCode::CalculationOverflow)?;
 
     // Transfer principal from Treasury PDA -> lender via lamport mutation
@@ -181,15 +275,24 @@ pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
         let mut lender_lamports = lender_info.try_borrow_mut_lamports()?;
 
         let new_treasury_balance = (**treasury_lamports)
-            .checked_sub(amount)
+            .checked_sub(payout)
             .ok_or(ErrorCode::CalculationOverflow)?;
         let new_lender_balance = (**lender_lamports)
-            .checked_add(amount)
+            .checked_add(lender_payout)
             .ok_or(ErrorCode::CalculationOverflow)?;
 
         **treasury_lamports = new_treasury_balance;
         **lender_lamports = new_lender_balance;
     }
+
+    // Move early-unstake penalty from Treasury PDA -> Reward Pool PDA
+    if penalty > 0 {
+        let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_add(penalty)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
     
     // Serialize updated treasury_pool back to account
     let mut data = treasury_pool_info.try_borrow_mut_data()?;
@@ -197,8 +300,69 @@ pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
 
     emit!(SolUnstaked {
         lender: lender_stake.backer,
-        amount, // Only principal, no rewards
+        amount: lender_payout, // Only principal, no rewards (after haircut and penalty)
         remaining_staked: lender_stake.deposited_amount,
+        haircut,
+        penalty,
+    });
+
+    Ok(())
+}
+
+/// Thaw and burn a fully exited position's deposit receipt and clear receipt_mint
+/// No-op for positions without a receipt; DepositReceiptRequired if the receipt accounts are missing.
+/// Shared by unstake_sol and emergency_unstake.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn burn_deposit_receipt<'info>(
+    lender_stake: &mut BackerDeposit,
+    receipt_mint: Option<&Account<'info, Mint>>,
+    receipt_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: Option<&Program<'info, Token>>,
+    treasury_pda_info: &AccountInfo<'info>,
+    treasury_bump: u8,
+    lender_info: &AccountInfo<'info>,
+    current_time: i64,
+) -> Result<()> {
+    let Some(receipt_mint_key) = lender_stake.receipt_mint else {
+        return Ok(());
+    };
+    let (Some(receipt_mint), Some(receipt_token_account), Some(token_program)) =
+        (receipt_mint, receipt_token_account, token_program)
+    else {
+        return Err(ErrorCode::DepositReceiptRequired.into());
+    };
+
+    // Freeze authority is the Treasury Pool PDA
+    let treasury_seeds: &[&[u8]] = &[TreasuryPool::PREFIX_SEED, &[treasury_bump]];
+    let signer_seeds = &[treasury_seeds];
+    token::thaw_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        ThawAccount {
+            account: receipt_token_account.to_account_info(),
+            mint: receipt_mint.to_account_info(),
+            authority: treasury_pda_info.clone(),
+        },
+        signer_seeds,
+    ))?;
+    token::burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: receipt_mint.to_account_info(),
+                from: receipt_token_account.to_account_info(),
+                authority: lender_info.clone(),
+            },
+        ),
+        receipt_token_account.amount,
+    )?;
+
+    lender_stake.receipt_mint = None;
+    msg!("[UNSTAKE] Deposit receipt burned: {}", receipt_mint_key);
+
+    emit!(crate::events::DepositReceiptBurned {
+        backer: lender_stake.backer,
+        receipt_mint: receipt_mint_key,
+        burned_at: current_time,
     });
 
     Ok(())
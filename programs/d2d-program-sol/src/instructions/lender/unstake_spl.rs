@@ -0,0 +1,101 @@
+use crate::errors::ErrorCode;
+use crate::events::SplUnstaked;
+use crate::states::{BackerDeposit, TokenTreasury, TreasuryPool};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+
+/// Withdraw SPL token principal from a token treasury
+/// Pending rewards are settled first and stay claimable through claim_rewards_spl
+#[derive(Accounts)]
+pub struct UnstakeSpl<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [TokenTreasury::PREFIX_SEED, mint.key().as_ref()],
+        bump = token_treasury.bump
+    )]
+    pub token_treasury: Account<'info, TokenTreasury>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = token_treasury.vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [TokenTreasury::STAKE_SEED, mint.key().as_ref(), lender.key().as_ref()],
+        bump = lender_stake.bump
+    )]
+    pub lender_stake: Account<'info, BackerDeposit>,
+
+    #[account(
+        mut,
+        constraint = lender_token_account.mint == token_treasury.mint @ ErrorCode::InvalidMint
+    )]
+    pub lender_token_account: Account<'info, TokenAccount>,
+
+    pub lender: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn unstake_spl(ctx: Context<UnstakeSpl>, amount: u64) -> Result<()> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+    let token_treasury = &mut ctx.accounts.token_treasury;
+    let lender_stake = &mut ctx.accounts.lender_stake;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_WITHDRAW), ErrorCode::ProgramPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(amount <= lender_stake.deposited_amount, ErrorCode::InsufficientStake);
+
+    lender_stake.settle_pending_rewards(token_treasury.reward_per_share)?;
+
+    let new_deposited_amount = lender_stake
+        .deposited_amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    lender_stake.update_time_weighted(new_deposited_amount, Clock::get()?.unix_timestamp)?;
+    lender_stake.deposited_amount = new_deposited_amount;
+    lender_stake.is_active = lender_stake.deposited_amount > 0;
+    lender_stake.update_reward_debt(token_treasury.reward_per_share)?;
+
+    token_treasury.total_deposited = token_treasury
+        .total_deposited
+        .checked_sub(amount)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    // Vault authority is the Treasury Pool PDA
+    let treasury_seeds: &[&[u8]] = &[TreasuryPool::PREFIX_SEED, &[treasury_pool.bump]];
+    let signer_seeds = &[treasury_seeds];
+    let transfer_cpi = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.lender_token_account.to_account_info(),
+            authority: treasury_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer_checked(transfer_cpi, amount, token_treasury.decimals)?;
+
+    msg!("[UNSTAKE_SPL] {} unstaked {} of mint {}, remaining: {}", lender_stake.backer, amount, token_treasury.mint, lender_stake.deposited_amount);
+
+    emit!(SplUnstaked {
+        backer: lender_stake.backer,
+        mint: token_treasury.mint,
+        amount,
+        remaining_staked: lender_stake.deposited_amount,
+    });
+
+    Ok(())
+}
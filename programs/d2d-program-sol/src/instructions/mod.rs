@@ -1,13 +1,17 @@
 pub mod admin;
 pub mod deploy_program;
 pub mod developer;
+pub mod governance;
 pub mod initialize;
 pub mod lender;
+pub mod pool;
 pub mod request_deployment_funds;
 
 pub use admin::*;
 pub use deploy_program::*;
 pub use developer::*;
+pub use governance::*;
 pub use initialize::*;
 pub use lender::*;
+pub use pool::*;
 pub use request_deployment_funds::*;
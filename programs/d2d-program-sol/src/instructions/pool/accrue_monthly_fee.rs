@@ -0,0 +1,93 @@
+use crate::errors::ErrorCode;
+use crate::events::MonthlyFeeAccrued;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Accrue the monthly borrow fee for a deployment (permissionless crank)
+///
+/// Charges borrowed_amount * 1% per whole month since the last accrual from the
+/// request's prepaid escrow and credits it to backers via reward_per_share.
+#[derive(Accounts)]
+pub struct AccrueMonthlyFee<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    pub caller: Signer<'info>,
+}
+
+/// Accrue borrow fees owed since last_fee_accrual (or created_at)
+///
+/// - fee = borrowed_amount * MONTHLY_BORROW_FEE_BPS / 10000 * months
+/// - Escrowed lamports already sit in the Reward Pool PDA, so only accounting moves
+/// - If the escrow cannot cover the fee, the remainder is charged and the
+///   request is marked SubscriptionExpired
+pub fn accrue_monthly_fee(ctx: Context<AccrueMonthlyFee>) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    // Fees accrue while the borrowed funds are out, not after the request is resolved
+    require!(
+        matches!(
+            deploy_request.status,
+            DeployRequestStatus::PendingDeployment
+                | DeployRequestStatus::Active
+                | DeployRequestStatus::SubscriptionExpired
+                | DeployRequestStatus::Suspended
+        ),
+        ErrorCode::InvalidRequestStatus
+    );
+    require!(deploy_request.borrowed_amount > 0, ErrorCode::NoFeeToAccrue);
+
+    let months = deploy_request.months_since_accrual(current_time);
+    require!(months > 0, ErrorCode::NoFeeToAccrue);
+
+    let fee_due = deploy_request.borrow_fee_for(months)?;
+    let fee_charged = fee_due.min(deploy_request.prepaid_balance);
+    let exhausted = fee_charged < fee_due;
+
+    msg!("[ACCRUE] Request: {:?}", deploy_request.request_id);
+    msg!("[ACCRUE] Months: {}, fee due: {} lamports, escrow: {} lamports",
+         months, fee_due, deploy_request.prepaid_balance);
+
+    treasury_pool.accrue_prepaid_fees(deploy_request, fee_charged, current_time)?;
+
+    // Advance by whole months only so partial months keep accruing
+    let accrued_seconds = (months as i64)
+        .checked_mul(DeployRequest::SECONDS_PER_MONTH)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    deploy_request.last_fee_accrual = deploy_request
+        .fee_accrual_start()
+        .checked_add(accrued_seconds)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+
+    if exhausted {
+        msg!("[ACCRUE] Prepaid subscription exhausted - marking SubscriptionExpired");
+        deploy_request.status = DeployRequestStatus::SubscriptionExpired;
+    }
+
+    emit!(MonthlyFeeAccrued {
+        request_id: deploy_request.request_id,
+        months,
+        fee_due,
+        fee_charged,
+        prepaid_remaining: deploy_request.prepaid_balance,
+        subscription_expired: exhausted,
+        accrued_at: current_time,
+    });
+
+    Ok(())
+}
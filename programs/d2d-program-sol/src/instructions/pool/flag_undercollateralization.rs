@@ -0,0 +1,69 @@
+use crate::errors::ErrorCode;
+use crate::events::Undercollateralized;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Flag the pool as undercollateralized (permissionless)
+///
+/// Anyone can call this to raise the on-chain alert when the pool can no longer
+/// cover backer principal plus rewards owed. Clearing requires admin confirmation.
+#[derive(Accounts)]
+pub struct FlagUndercollateralization<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (lamports counted as assets)
+    #[account(
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+/// Check solvency and set the undercollateralized flag if assets < obligations
+///
+/// Insolvent when:
+/// treasury_lamports + reward_pool_lamports + total_borrowed < total_deposited + reward_pool_balance + prepaid_fees_balance
+pub fn flag_undercollateralization(ctx: Context<FlagUndercollateralization>) -> Result<()> {
+    let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
+    let reward_pool_lamports = ctx.accounts.reward_pool.lamports();
+
+    // Treasury lamports available after rent exemption
+    let rent_exemption = Rent::get()?.minimum_balance(treasury_pool_info.data_len());
+    let treasury_available = treasury_pool_info.lamports().saturating_sub(rent_exemption);
+
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let (assets, obligations) =
+        treasury_pool.collateral_position(treasury_available, reward_pool_lamports)?;
+
+    msg!("[SOLVENCY] Assets: {} lamports, obligations: {} lamports", assets, obligations);
+
+    if assets >= obligations {
+        msg!("[SOLVENCY] Pool is fully collateralized");
+        return Ok(());
+    }
+
+    let shortfall = obligations
+        .checked_sub(assets)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    msg!("[SOLVENCY] Shortfall detected: {} lamports", shortfall);
+
+    treasury_pool.undercollateralized = true;
+
+    emit!(Undercollateralized {
+        assets,
+        obligations,
+        shortfall,
+        flagged_by: ctx.accounts.caller.key(),
+        flagged_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
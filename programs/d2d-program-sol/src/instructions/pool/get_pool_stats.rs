@@ -0,0 +1,49 @@
+use crate::errors::ErrorCode;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Read the headline pool figures in one call (no state mutation)
+///
+/// Returned via return data (`program.methods.getPoolStats().view()`) so dashboards do not
+/// depend on TreasuryPool's field order and its block of deprecated fields.
+#[derive(Accounts)]
+pub struct GetPoolStats<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+}
+
+/// Return buffer of get_pool_stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolStats {
+    pub total_deposited: u64,          // All SOL principal in the pool (lamports)
+    pub liquid_balance: u64,           // Principal available for deployments and withdrawals (lamports)
+    pub reward_pool_balance: u64,      // Rewards credited to backers, not yet claimed (lamports)
+    pub platform_pool_balance: u64,    // Platform fees held in the Platform Pool (lamports)
+    pub reward_per_share: u128,        // Reward accumulator (scaled by PRECISION)
+    pub utilization_bps: u64,          // total_borrowed * 10000 / total_deposited (0 if nothing deposited)
+    pub total_rewards_claimed: u64,    // Rewards ever paid out to backers (lamports)
+    pub total_deployment_losses: u64,  // Deployment costs never recovered (lamports)
+}
+
+pub fn get_pool_stats(ctx: Context<GetPoolStats>) -> Result<PoolStats> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+
+    let utilization_bps = TreasuryPool::utilization_bps(treasury_pool.total_borrowed, treasury_pool.total_deposited)?;
+
+    msg!("[POOL_STATS] Deposited: {}, liquid: {}, utilization: {} bps", treasury_pool.total_deposited, treasury_pool.liquid_balance, utilization_bps);
+
+    Ok(PoolStats {
+        total_deposited: treasury_pool.total_deposited,
+        liquid_balance: treasury_pool.liquid_balance,
+        reward_pool_balance: treasury_pool.reward_pool_balance,
+        platform_pool_balance: treasury_pool.platform_pool_balance,
+        reward_per_share: treasury_pool.reward_per_share,
+        utilization_bps,
+        total_rewards_claimed: treasury_pool.total_rewards_claimed,
+        total_deployment_losses: treasury_pool.total_deployment_losses,
+    })
+}
@@ -0,0 +1,39 @@
+use crate::errors::ErrorCode;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Read the share of backer deposits currently out on deployments (no state mutation)
+///
+/// Returned via return data (`program.methods.getUtilization().view()`), computed from the
+/// tracked total_borrowed rather than inferred from PDA balances.
+#[derive(Accounts)]
+pub struct GetUtilization<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+}
+
+/// Return buffer of get_utilization
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Utilization {
+    pub utilization_bps: u64,   // total_borrowed * 10000 / total_deposited (0 if nothing deposited)
+    pub total_borrowed: u64,    // Deployment funds out on ephemeral keys (lamports)
+    pub total_deposited: u64,   // All SOL principal in the pool (lamports)
+}
+
+pub fn get_utilization(ctx: Context<GetUtilization>) -> Result<Utilization> {
+    let treasury_pool = &ctx.accounts.treasury_pool;
+
+    let utilization_bps = TreasuryPool::utilization_bps(treasury_pool.total_borrowed, treasury_pool.total_deposited)?;
+
+    msg!("[UTILIZATION] Borrowed: {} / {} lamports ({} bps)", treasury_pool.total_borrowed, treasury_pool.total_deposited, utilization_bps);
+
+    Ok(Utilization {
+        utilization_bps,
+        total_borrowed: treasury_pool.total_borrowed,
+        total_deposited: treasury_pool.total_deposited,
+    })
+}
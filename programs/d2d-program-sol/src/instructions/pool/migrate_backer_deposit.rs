@@ -0,0 +1,104 @@
+use crate::errors::ErrorCode;
+use crate::events::BackerDepositMigrated;
+use crate::states::BackerDeposit;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Migrate a BackerDeposit account to the current layout (permissionless)
+///
+/// Anyone can pay to migrate a position; existing field values are preserved,
+/// so the backer's principal and rewards are unaffected.
+#[derive(Accounts)]
+pub struct MigrateBackerDeposit<'info> {
+    /// CHECK: Backer Deposit PDA - may have an old layout, resized and migrated here
+    /// We use UncheckedAccount to avoid deserialization issues with old layout
+    #[account(
+        mut,
+        seeds = [BackerDeposit::PREFIX_SEED, backer.key().as_ref()],
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidAccountOwner
+    )]
+    pub backer_deposit: UncheckedAccount<'info>,
+
+    /// CHECK: Backer wallet (only used to derive the deposit PDA)
+    pub backer: UncheckedAccount<'info>,
+
+    /// Pays the rent for the added bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrate backer deposit to the current layout
+///
+/// This instruction:
+/// 1. Reads the old layout (missing trailing fields decode as zero)
+/// 2. Tops up rent from the payer and resizes to 8 + INIT_SPACE, zero-filling new bytes
+/// 3. Writes the preserved fields back with layout_version = LAYOUT_VERSION
+pub fn migrate_backer_deposit(ctx: Context<MigrateBackerDeposit>) -> Result<()> {
+    let backer_deposit_info = ctx.accounts.backer_deposit.to_account_info();
+    let required_space = 8 + BackerDeposit::INIT_SPACE;
+    let current_space = backer_deposit_info.data_len();
+
+    msg!("[MIGRATE_DEPOSIT] Current account size: {} bytes", current_space);
+    msg!("[MIGRATE_DEPOSIT] Required size: {} bytes", required_space);
+
+    let deposit = BackerDeposit::from_legacy_bytes(&backer_deposit_info.data.borrow())?;
+    require!(
+        deposit.backer == ctx.accounts.backer.key(),
+        ErrorCode::Unauthorized
+    );
+
+    if current_space >= required_space {
+        let current = BackerDeposit::try_deserialize(&mut &backer_deposit_info.data.borrow()[..])?;
+        if current.layout_version == BackerDeposit::LAYOUT_VERSION {
+            msg!("[MIGRATE_DEPOSIT] Account already migrated");
+            return Ok(());
+        }
+    }
+
+    if current_space < required_space {
+        let rent = Rent::get()?;
+        let additional_lamports_needed = rent
+            .minimum_balance(required_space)
+            .saturating_sub(backer_deposit_info.lamports());
+
+        msg!("[MIGRATE_DEPOSIT] Additional lamports needed: {}", additional_lamports_needed);
+
+        if additional_lamports_needed > 0 {
+            let transfer_cpi = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: backer_deposit_info.clone(),
+                },
+            );
+            system_program::transfer(transfer_cpi, additional_lamports_needed)?;
+        }
+
+        msg!("[MIGRATE_DEPOSIT] Resizing account from {} to {} bytes", current_space, required_space);
+        // Using realloc for now (deprecated but still works); new bytes are zero-filled
+        #[allow(deprecated)]
+        backer_deposit_info.realloc(required_space, true)?;
+    }
+
+    // Write preserved fields back in the current layout
+    {
+        let mut data = backer_deposit_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..];
+        deposit.try_serialize(&mut writer)?;
+    }
+
+    msg!("[MIGRATE_DEPOSIT] Migrated backer {} to layout version {}", deposit.backer, deposit.layout_version);
+
+    emit!(BackerDepositMigrated {
+        backer: deposit.backer,
+        old_size: current_space as u64,
+        new_size: backer_deposit_info.data_len() as u64,
+        layout_version: deposit.layout_version,
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
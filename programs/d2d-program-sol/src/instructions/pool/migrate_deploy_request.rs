@@ -0,0 +1,92 @@
+use crate::errors::ErrorCode;
+use crate::events::DeployRequestMigrated;
+use crate::states::DeployRequest;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Move a legacy DeployRequest from [PREFIX_SEED, program_hash] to [PREFIX_SEED, developer, program_hash]
+/// (permissionless)
+///
+/// Field values are preserved (older layouts decode with zeroed trailing fields). The payer funds the
+/// new account and receives the legacy account's rent; any renewal_balance lamports move with the request.
+#[derive(Accounts)]
+#[instruction(program_hash: [u8; 32])]
+pub struct MigrateDeployRequest<'info> {
+    /// CHECK: Legacy Deploy Request PDA - may have an old layout, decoded manually and closed here
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, program_hash.as_ref()],
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidAccountOwner
+    )]
+    pub legacy_request: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DeployRequest::INIT_SPACE,
+        seeds = [DeployRequest::PREFIX_SEED, developer.key().as_ref(), program_hash.as_ref()],
+        bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    /// CHECK: Developer wallet (only used to derive the new PDA, must own the legacy request)
+    pub developer: UncheckedAccount<'info>,
+
+    /// Pays rent for the new account, receives the legacy account's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_deploy_request(ctx: Context<MigrateDeployRequest>, program_hash: [u8; 32]) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_request.to_account_info();
+    let new_info = ctx.accounts.deploy_request.to_account_info();
+    let payer_info = ctx.accounts.payer.to_account_info();
+
+    let mut request = DeployRequest::from_legacy_bytes(&legacy_info.data.borrow())?;
+    require!(request.program_hash == program_hash, ErrorCode::InvalidRequestId);
+    require!(
+        request.developer == ctx.accounts.developer.key(),
+        ErrorCode::Unauthorized
+    );
+
+    request.bump = ctx.bumps.deploy_request;
+    let renewal_balance = request.renewal_balance;
+    ctx.accounts.deploy_request.set_inner(request);
+
+    // Close the legacy account: renewal top-ups follow the request, the rest refunds the payer
+    let legacy_lamports = legacy_info.lamports();
+    let rent_refund = legacy_lamports
+        .checked_sub(renewal_balance)
+        .ok_or(ErrorCode::CalculationOverflow)?;
+    {
+        let mut legacy_lamports_mut = legacy_info.try_borrow_mut_lamports()?;
+        let mut new_lamports = new_info.try_borrow_mut_lamports()?;
+        let mut payer_lamports = payer_info.try_borrow_mut_lamports()?;
+
+        **new_lamports = (**new_lamports)
+            .checked_add(renewal_balance)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **payer_lamports = (**payer_lamports)
+            .checked_add(rent_refund)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **legacy_lamports_mut = 0;
+    }
+    legacy_info.assign(&system_program::ID);
+    #[allow(deprecated)]
+    legacy_info.realloc(0, false)?;
+
+    msg!("[MIGRATE_REQUEST] {} -> {} (developer {})", legacy_info.key(), new_info.key(), ctx.accounts.developer.key());
+
+    emit!(DeployRequestMigrated {
+        request_id: program_hash,
+        developer: ctx.accounts.developer.key(),
+        legacy_address: legacy_info.key(),
+        new_address: new_info.key(),
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::errors::ErrorCode;
+use crate::events::UserStatsMigrated;
+use crate::states::UserDeployStats;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Grow a UserDeployStats account to the current layout (permissionless)
+///
+/// Fields are only ever appended and new bytes are zero-filled, so existing
+/// counters are preserved and added fields start at zero (failed_deploys = 0).
+#[derive(Accounts)]
+pub struct MigrateUserStats<'info> {
+    /// CHECK: User Deploy Stats PDA - may be shorter than the current layout, resized here
+    #[account(
+        mut,
+        seeds = [UserDeployStats::PREFIX_SEED, developer.key().as_ref()],
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidAccountOwner
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Developer wallet (only used to derive the stats PDA)
+    pub developer: UncheckedAccount<'info>,
+
+    /// Pays the rent for the added bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user_stats(ctx: Context<MigrateUserStats>) -> Result<()> {
+    let user_stats_info = ctx.accounts.user_stats.to_account_info();
+    let required_space = 8 + UserDeployStats::INIT_SPACE;
+    let current_space = user_stats_info.data_len();
+
+    if current_space >= required_space {
+        msg!("[MIGRATE_USER_STATS] Account already migrated");
+        return Ok(());
+    }
+
+    let rent = Rent::get()?;
+    let additional_lamports_needed = rent
+        .minimum_balance(required_space)
+        .saturating_sub(user_stats_info.lamports());
+    if additional_lamports_needed > 0 {
+        let transfer_cpi = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: user_stats_info.clone(),
+            },
+        );
+        system_program::transfer(transfer_cpi, additional_lamports_needed)?;
+    }
+
+    msg!("[MIGRATE_USER_STATS] Resizing account from {} to {} bytes", current_space, required_space);
+    // Using realloc for now (deprecated but still works); new bytes are zero-filled
+    #[allow(deprecated)]
+    user_stats_info.realloc(required_space, true)?;
+
+    emit!(UserStatsMigrated {
+        developer: ctx.accounts.developer.key(),
+        old_size: current_space as u64,
+        new_size: required_space as u64,
+        migrated_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+pub mod accrue_monthly_fee;
+pub mod flag_undercollateralization;
+pub mod get_pool_stats;
+pub mod get_utilization;
+pub mod migrate_backer_deposit;
+pub mod migrate_deploy_request;
+pub mod migrate_user_stats;
+pub mod renew_from_prepaid;
+pub mod sponsor_reward_pool;
+pub mod verify_solvency;
+
+pub use accrue_monthly_fee::*;
+pub use flag_undercollateralization::*;
+pub use get_pool_stats::*;
+pub use get_utilization::*;
+pub use migrate_backer_deposit::*;
+pub use migrate_deploy_request::*;
+pub use migrate_user_stats::*;
+pub use renew_from_prepaid::*;
+pub use sponsor_reward_pool::*;
+pub use verify_solvency::*;
@@ -0,0 +1,95 @@
+use crate::errors::ErrorCode;
+use crate::events::SubscriptionPaid;
+use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool};
+use anchor_lang::prelude::*;
+
+/// Renew a subscription by one month from the developer's renewal_balance (permissionless crank)
+///
+/// - Only within RENEWAL_WINDOW_SECS of expiry, and not once the grace period has elapsed
+/// - Moves monthly_fee from the DeployRequest PDA to the Reward Pool and escrows it like pay_subscription
+/// - When the balance no longer covers a month the crank fails and the subscription lapses as usual
+#[derive(Accounts)]
+pub struct RenewFromPrepaid<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    #[account(
+        mut,
+        seeds = [DeployRequest::PREFIX_SEED, deploy_request.seed_developer().as_ref(), deploy_request.program_hash.as_ref()],
+        bump = deploy_request.bump
+    )]
+    pub deploy_request: Account<'info, DeployRequest>,
+
+    /// CHECK: Reward Pool PDA (holds prepaid subscription fees in escrow)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+pub fn renew_from_prepaid(ctx: Context<RenewFromPrepaid>) -> Result<()> {
+    let reward_pool_info = ctx.accounts.reward_pool.to_account_info();
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+    let deploy_request = &mut ctx.accounts.deploy_request;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_PAY_SUBSCRIPTION), ErrorCode::ProgramPaused);
+    require!(
+        deploy_request.status == DeployRequestStatus::Active
+            || deploy_request.status == DeployRequestStatus::SubscriptionExpired,
+        ErrorCode::InvalidRequestStatus
+    );
+    require!(
+        !deploy_request.is_past_grace(current_time, treasury_pool.grace_period_secs),
+        ErrorCode::GracePeriodElapsed
+    );
+    require!(deploy_request.is_renewal_due(current_time), ErrorCode::RenewalNotDue);
+
+    let payment_amount = deploy_request.monthly_fee;
+    require!(
+        deploy_request.renewal_balance >= payment_amount,
+        ErrorCode::InsufficientRenewalBalance
+    );
+
+    deploy_request.renewal_balance -= payment_amount;
+    deploy_request.extend_subscription(1);
+    deploy_request.status = DeployRequestStatus::Active;
+
+    // Escrow the month (accrued to backers as the borrow fee comes due)
+    treasury_pool.escrow_prepaid_fees(deploy_request, payment_amount)?;
+
+    // DeployRequest PDA is program-owned: move lamports directly
+    {
+        let deploy_request_info = deploy_request.to_account_info();
+        let mut deploy_request_lamports = deploy_request_info.try_borrow_mut_lamports()?;
+        let mut reward_pool_lamports = reward_pool_info.try_borrow_mut_lamports()?;
+
+        **deploy_request_lamports = (**deploy_request_lamports)
+            .checked_sub(payment_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        **reward_pool_lamports = (**reward_pool_lamports)
+            .checked_add(payment_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+    }
+
+    msg!("[RENEW] Renewed 1 month for {} lamports, renewal balance left: {}", payment_amount, deploy_request.renewal_balance);
+
+    emit!(SubscriptionPaid {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        months: 1,
+        payment_amount,
+        subscription_valid_until: deploy_request.subscription_paid_until,
+    });
+
+    Ok(())
+}
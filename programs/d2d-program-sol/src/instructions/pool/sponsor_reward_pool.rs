@@ -0,0 +1,75 @@
+use crate::errors::ErrorCode;
+use crate::events::RewardCredited;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Subsidize backer yield with external SOL (permissionless)
+///
+/// Unlike credit_fee_to_pool this is not admin-gated and not tied to developer fees:
+/// any signer transfers SOL into the Reward Pool PDA and it is distributed to current
+/// backers through reward_per_share.
+#[derive(Accounts)]
+pub struct SponsorRewardPool<'info> {
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (receives the sponsored SOL)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sponsor the reward pool
+///
+/// Flow:
+/// 1. Transfer sponsor -> Reward Pool PDA
+/// 2. credit_fee_to_pool(amount, 0): reward_pool_balance += amount,
+///    reward_per_share += amount * PRECISION / total_deposited
+pub fn sponsor_reward_pool(ctx: Context<SponsorRewardPool>, amount: u64) -> Result<()> {
+    let treasury_pool = &mut ctx.accounts.treasury_pool;
+
+    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    // Nobody to distribute to - funds would sit in the reward pool unclaimable
+    require!(treasury_pool.total_deposited > 0, ErrorCode::DivisionByZero);
+
+    let transfer_cpi = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.sponsor.to_account_info(),
+            to: ctx.accounts.reward_pool.to_account_info(),
+        },
+    );
+    system_program::transfer(transfer_cpi, amount)?;
+
+    // Not recorded in the fee-velocity window: a subsidy, not recurring fee income
+    treasury_pool.credit_fee_to_pool(amount, 0)?;
+
+    msg!("[SPONSOR] {} sponsored {} lamports to the Reward Pool", ctx.accounts.sponsor.key(), amount);
+    msg!("[SPONSOR] reward_per_share: {}", treasury_pool.reward_per_share);
+
+    emit!(RewardCredited {
+        fee_id: [0u8; 32],
+        fee_reward: amount,
+        fee_platform: 0,
+        reward_per_share: treasury_pool.reward_per_share,
+        total_deposited: treasury_pool.total_deposited,
+        credited_at: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::errors::ErrorCode;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Assert the pool PDAs cover everything the TreasuryPool says they hold (permissionless, read-only)
+///
+/// Succeeds only if every invariant in TreasuryPool::verify_solvency holds, so a watchdog
+/// can prove solvency on-chain; a breach fails with the error naming the broken invariant.
+#[derive(Accounts)]
+pub struct VerifySolvency<'info> {
+    #[account(
+        seeds = [TreasuryPool::PREFIX_SEED],
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
+    )]
+    pub treasury_pool: Account<'info, TreasuryPool>,
+
+    /// CHECK: Reward Pool PDA (lamports checked against rewards owed + escrow + dust)
+    #[account(
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Platform Pool PDA (lamports checked against platform_pool_balance)
+    #[account(
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+}
+
+pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<()> {
+    let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
+    let treasury_lamports = treasury_pool_info.lamports();
+    let treasury_rent = Rent::get()?.minimum_balance(treasury_pool_info.data_len());
+    let reward_pool_lamports = ctx.accounts.reward_pool.lamports();
+    let platform_pool_lamports = ctx.accounts.platform_pool.lamports();
+
+    let treasury_pool = &ctx.accounts.treasury_pool;
+
+    msg!("[SOLVENCY] Treasury: {} lamports + {} borrowed vs {} deposited + {} rent",
+         treasury_lamports, treasury_pool.total_borrowed, treasury_pool.total_deposited, treasury_rent);
+    msg!("[SOLVENCY] Reward Pool: {} lamports vs {} rewards + {} escrow + {} dust",
+         reward_pool_lamports, treasury_pool.reward_pool_balance, treasury_pool.prepaid_fees_balance, treasury_pool.undistributed_dust);
+    msg!("[SOLVENCY] Platform Pool: {} lamports vs {} tracked",
+         platform_pool_lamports, treasury_pool.platform_pool_balance);
+
+    treasury_pool.verify_solvency(
+        treasury_lamports,
+        treasury_rent,
+        reward_pool_lamports,
+        platform_pool_lamports,
+    )?;
+
+    msg!("[SOLVENCY] All invariants hold");
+    Ok(())
+}
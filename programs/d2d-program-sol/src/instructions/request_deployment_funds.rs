@@ -1,13 +1,14 @@
 use crate::errors::ErrorCode;
-use crate::events::DeploymentFundsRequested;
-use crate::states::{DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
+use crate::events::{DailyStatsReset, DeployRequested, DeploymentFundsRequested};
+use crate::states::{Blacklist, D2DConfig, DeployPayment, DeployRequest, DeployRequestStatus, TreasuryPool, UserDeployStats};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 /// Request deployment funds from treasury pool
 /// This instruction:
-/// 1. Developer pays service fee + subscription
-/// 2. Validates treasury has sufficient funds for deployment
+/// 1. Developer pays service fee + subscription into the Reward Pool and Platform Pool,
+///    split the same way as create_deploy_request
+/// 2. Reserves deployment_cost in TreasuryPool.escrowed_amount (liquid_balance - escrowed_amount must cover it)
 /// 3. Creates a deploy_request with status PendingDeployment
 /// 4. Backend will then call fund_temporary_wallet to get deployment funds
 #[derive(Accounts)]
@@ -16,15 +17,32 @@ pub struct RequestDeploymentFunds<'info> {
     #[account(
         mut,
         seeds = [TreasuryPool::PREFIX_SEED],
-        bump = treasury_pool.bump
+        bump = treasury_pool.bump,
+        constraint = treasury_pool.is_current_layout() @ ErrorCode::StaleLayout
     )]
     pub treasury_pool: Account<'info, TreasuryPool>,
     
+    /// CHECK: Reward Pool PDA (program-owned, receives subscription + reward share of the up-front fee)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::REWARD_POOL_SEED],
+        bump = treasury_pool.reward_pool_bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Platform Pool PDA (program-owned, receives platform share of the up-front fee)
+    #[account(
+        mut,
+        seeds = [TreasuryPool::PLATFORM_POOL_SEED],
+        bump = treasury_pool.platform_pool_bump
+    )]
+    pub platform_pool: UncheckedAccount<'info>,
+    
     #[account(
         init_if_needed,
         payer = developer,
         space = 8 + DeployRequest::INIT_SPACE,
-        seeds = [DeployRequest::PREFIX_SEED, program_hash.as_ref()],
+        seeds = [DeployRequest::PREFIX_SEED, developer.key().as_ref(), program_hash.as_ref()],
         bump
     )]
     pub deploy_request: Account<'info, DeployRequest>,
@@ -41,6 +59,20 @@ pub struct RequestDeploymentFunds<'info> {
     #[account(mut)]
     pub developer: Signer<'info>,
     
+    /// CHECK: Blacklist PDA for the developer - usually does not exist; rejected if it is a live Blacklist account
+    #[account(
+        seeds = [Blacklist::PREFIX_SEED, developer.key().as_ref()],
+        bump
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    
+    /// Optional D2DConfig; when passed its fee_rate and max_concurrent_per_user apply
+    #[account(
+        seeds = [D2DConfig::PREFIX_SEED],
+        bump = config.bump
+    )]
+    pub config: Option<Account<'info, D2DConfig>>,
+    
     #[account(
         mut,
         constraint = admin.key() == treasury_pool.admin @ ErrorCode::Unauthorized
@@ -64,10 +96,13 @@ pub fn request_deployment_funds(
     initial_months: u32,
     deployment_cost: u64,
 ) -> Result<()> {
-    // Get account infos before mutable borrows to avoid borrow checker issues
-    let treasury_pool_info = ctx.accounts.treasury_pool.to_account_info();
-    let _treasury_pool_bump = ctx.accounts.treasury_pool.bump;
-    
+    // Config limits fall back to the pool defaults when no D2DConfig is passed
+    let config = ctx.accounts.config.as_ref();
+    require!(!config.is_some_and(|c| c.is_paused), ErrorCode::ProgramPaused);
+    let max_concurrent_sessions = config
+        .map_or(ctx.accounts.treasury_pool.max_concurrent_sessions, |c| c.max_concurrent_per_user);
+    let min_service_fee = config.map_or(1, |c| c.fee_rate.max(1));
+
     let treasury_pool = &mut ctx.accounts.treasury_pool;
     let deploy_request = &mut ctx.accounts.deploy_request;
     let user_stats = &mut ctx.accounts.user_stats;
@@ -75,22 +110,31 @@ pub fn request_deployment_funds(
  
     let is_new_deploy_request =
         deploy_request.request_id == [0u8; 32] && deploy_request.developer == Pubkey::default();
+    // A retry of this developer's open request reuses its session instead of opening another
+    let holds_session = !is_new_deploy_request
+        && deploy_request.developer == ctx.accounts.developer.key()
+        && matches!(
+            deploy_request.status,
+            DeployRequestStatus::PendingDeployment | DeployRequestStatus::Active
+        );
 
     // Assign bump provided by Anchor (available for init / init_if_needed)
     deploy_request.bump = ctx.bumps.deploy_request;
 
     // Validation
-    require!(!treasury_pool.emergency_pause, ErrorCode::ProgramPaused);
-    require!(service_fee > 0, ErrorCode::InvalidAmount);
+    require!(!treasury_pool.is_paused(TreasuryPool::PAUSE_DEPLOY), ErrorCode::ProgramPaused);
+    require!(!treasury_pool.deployments_paused, ErrorCode::DeploymentsPaused);
+    require!(
+        !Blacklist::is_active(&ctx.accounts.blacklist.to_account_info()),
+        ErrorCode::DeveloperBlacklisted
+    );
+    require!(service_fee >= min_service_fee, ErrorCode::InvalidAmount);
+    // With monthly_fee_bps set the fee tracks deployment_cost instead of the passed value
+    let monthly_fee = TreasuryPool::monthly_fee_for(treasury_pool.monthly_fee_bps, deployment_cost, monthly_fee)?;
     require!(monthly_fee > 0, ErrorCode::InvalidAmount);
     require!(initial_months > 0, ErrorCode::InvalidAmount);
     require!(deployment_cost > 0, ErrorCode::InvalidAmount);
-
-    // Check if treasury has enough funds for deployment
-    require!(
-        deployment_cost <= treasury_pool.total_staked,
-        ErrorCode::InsufficientTreasuryFunds
-    );
+    treasury_pool.check_deployment_cost(deployment_cost)?;
 
     // Initialize user stats if first time
     if user_stats.user == Pubkey::default() {
@@ -98,18 +142,41 @@ pub fn request_deployment_funds(
         user_stats.active_sessions = 0;
         user_stats.daily_deploys = 0;
         user_stats.total_deploys = 0;
+        user_stats.failed_deploys = 0;
         user_stats.last_reset = current_time;
         user_stats.bump = ctx.bumps.user_stats;
     }
 
     // Reset daily counter if new day
-    if current_time - user_stats.last_reset > 86400 {
-        user_stats.daily_deploys = 0;
-        user_stats.last_reset = current_time;
+    if let Some(previous_daily_deploys) = user_stats.reset_daily_if_due(current_time) {
+        emit!(DailyStatsReset {
+            user: user_stats.user,
+            previous_daily_deploys,
+            last_reset: user_stats.last_reset,
+            total_deploys: user_stats.total_deploys,
+        });
     }
 
-    // Calculate total payment (service fee + subscription)
-    let total_payment = service_fee + (monthly_fee * initial_months as u64);
+    require!(
+        user_stats.daily_deploys < treasury_pool.max_daily_deploys,
+        ErrorCode::DailyDeployLimitExceeded
+    );
+
+    // Each open session has treasury funds committed to it; cap them per developer
+    require!(
+        holds_session || user_stats.active_sessions < max_concurrent_sessions,
+        ErrorCode::MaxConcurrentSessionsExceeded
+    );
+
+    // Same fee breakdown as create_deploy_request, with the default reward split
+    let DeployPayment {
+        monthly_fee_total,
+        reward_share,
+        platform_fee_amount,
+        reward_split_bps,
+        reward_fee_amount,
+        total_payment,
+    } = treasury_pool.deploy_payment(service_fee, monthly_fee, initial_months, deployment_cost, None)?;
 
     // Initialize deploy request with PendingDeployment status
     if is_new_deploy_request {
@@ -117,7 +184,9 @@ pub fn request_deployment_funds(
         deploy_request.developer = ctx.accounts.developer.key();
         deploy_request.program_hash = program_hash;
         deploy_request.created_at = current_time;
+        deploy_request.last_fee_accrual = current_time;
     } else {
+        require!(!deploy_request.funding_locked, ErrorCode::DeploymentInProgress);
         // Ensure this PDA corresponds to the provided hash/developer
         require!(
             deploy_request.program_hash == program_hash
@@ -126,35 +195,67 @@ pub fn request_deployment_funds(
         );
     }
 
-    deploy_request.service_fee = service_fee;
+    // Refunds debit the Reward Pool by service_fee, so record what the split actually credited there
+    deploy_request.service_fee = reward_share;
     deploy_request.monthly_fee = monthly_fee;
     deploy_request.deployment_cost = deployment_cost;
     deploy_request.subscription_paid_until =
         current_time + (initial_months as i64 * 30 * 24 * 60 * 60);
+    deploy_request.initial_months = initial_months;
     deploy_request.ephemeral_key = None; // Will be set when backend funds temporary wallet
     deploy_request.deployed_program_id = None; // Will be set after backend deploys
+    deploy_request.deployed_at = None;
     deploy_request.status = DeployRequestStatus::PendingDeployment;
 
+    // Reserve deployment_cost against liquid_balance not already held for other requests
+    // A retry swaps its previous reservation for the new cost
+    treasury_pool.release_deployment(deploy_request);
+    treasury_pool.reserve_deployment(deploy_request, deployment_cost)?;
+
     // Update user stats
-    user_stats.active_sessions += 1;
+    if !holds_session {
+        user_stats.active_sessions += 1;
+    }
     user_stats.daily_deploys += 1;
     user_stats.total_deploys += 1;
 
-    // Transfer developer payment (service fee + subscription) directly to Treasury Pool PDA
-    let developer_payment_cpi = CpiContext::new(
+    // Transfer developer payment: subscription + reward share -> Reward Pool, platform share -> Platform Pool
+    let reward_payment_cpi = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         system_program::Transfer {
             from: ctx.accounts.developer.to_account_info(),
-            to: treasury_pool_info.clone(),
+            to: ctx.accounts.reward_pool.to_account_info(),
         },
     );
-    system_program::transfer(developer_payment_cpi, total_payment)?;
+    system_program::transfer(reward_payment_cpi, reward_fee_amount)?;
+    if platform_fee_amount > 0 {
+        let platform_payment_cpi = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.developer.to_account_info(),
+                to: ctx.accounts.platform_pool.to_account_info(),
+            },
+        );
+        system_program::transfer(platform_payment_cpi, platform_fee_amount)?;
+    }
 
     // Note: Deployment cost will be transferred later via fund_temporary_wallet instruction
     // This separates developer payment from backend deployment funding
 
-    // Update treasury pool - only add developer payment, don't deduct deployment cost yet
-    treasury_pool.distribute_fees(total_payment)?;
+    // Same accounting as create_deploy_request: the up-front fee goes to backers
+    // (reward_per_share) and the Platform Pool, prepaid months stay in escrow
+    treasury_pool.credit_fee_to_pool(reward_share, platform_fee_amount)?;
+    treasury_pool.record_fee_credit(reward_share, current_time)?;
+    treasury_pool.escrow_prepaid_fees(deploy_request, monthly_fee_total)?;
+
+    require!(
+        treasury_pool.reward_pool_shortfall(ctx.accounts.reward_pool.lamports())? == 0,
+        ErrorCode::InsufficientTreasuryFunds
+    );
+    require!(
+        ctx.accounts.platform_pool.lamports() >= treasury_pool.platform_pool_balance,
+        ErrorCode::InsufficientTreasuryFunds
+    );
 
     emit!(DeploymentFundsRequested {
         request_id: deploy_request.request_id,
@@ -166,6 +267,20 @@ pub fn request_deployment_funds(
         deployment_cost,
         total_payment,
         requested_at: current_time,
+        reward_split_bps,
+        reward_fee_amount: reward_share,
+        platform_fee_amount,
+    });
+
+    emit!(DeployRequested {
+        request_id: deploy_request.request_id,
+        developer: deploy_request.developer,
+        program_hash: deploy_request.program_hash,
+        service_fee,
+        monthly_fee,
+        initial_months,
+        total_payment,
+        requested_at: current_time,
     });
 
     Ok(())
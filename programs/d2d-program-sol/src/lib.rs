@@ -18,12 +18,14 @@ pub mod d2d_program_sol {
     use super::*;
 
     /// Initialize the D2D program and treasury pool
+    /// reserved_padding: extra zeroed bytes after TreasuryPool for future fields (max 1024)
     pub fn initialize(
         ctx: Context<Initialize>,
         initial_apy: u64,
         dev_wallet: Pubkey,
+        reserved_padding: u32,
     ) -> Result<()> {
-        instructions::initialize(ctx, initial_apy, dev_wallet)
+        instructions::initialize(ctx, initial_apy, dev_wallet, reserved_padding)
     }
 
     /// Lender stake SOL into treasury pool
@@ -34,13 +36,80 @@ pub mod d2d_program_sol {
 
     /// Lender unstake SOL from treasury pool
     /// Kept for backward compatibility (use request_withdraw for new code)
-    pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
-        instructions::unstake_sol(ctx, amount)
+    /// accept_penalty: allow withdrawing a locked stake early (penalty_bps goes to the reward pool)
+    pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64, accept_penalty: bool) -> Result<()> {
+        instructions::unstake_sol(ctx, amount, accept_penalty)
+    }
+
+    /// Lender exit while the program is paused (emergency_withdraw_fee_bps goes to the insurance pool)
+    pub fn emergency_unstake(ctx: Context<EmergencyUnstake>, amount: u64) -> Result<()> {
+        instructions::emergency_unstake(ctx, amount)
+    }
+
+    /// Lender withdraw the whole deposit while emergency_pause is set (emergency_unstake of deposited_amount)
+    pub fn emergency_withdraw(ctx: Context<EmergencyUnstake>) -> Result<()> {
+        instructions::emergency_withdraw(ctx)
     }
 
     /// Lender claim accumulated rewards
-    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
-        instructions::claim_rewards(ctx)
+    /// amount: claim exactly this much (None = claim everything)
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, amount: Option<u64>) -> Result<()> {
+        instructions::claim_rewards(ctx, amount)
+    }
+
+    /// Lender claim all rewards and withdraw the whole deposit in one instruction
+    pub fn exit_position(ctx: Context<ExitPosition>) -> Result<()> {
+        instructions::exit_position(ctx)
+    }
+
+    /// Lender close a fully exited position (no principal or rewards left) and reclaim its rent
+    pub fn close_stake(ctx: Context<CloseStake>) -> Result<()> {
+        instructions::close_stake(ctx)
+    }
+
+    /// Backer delegate reward claims to another wallet (None = backer only)
+    pub fn set_claim_authority(ctx: Context<SetClaimAuthority>, claim_authority: Option<Pubkey>) -> Result<()> {
+        instructions::set_claim_authority(ctx, claim_authority)
+    }
+
+    /// Lender restake all claimable rewards into their deposit
+    pub fn compound_rewards(ctx: Context<CompoundRewards>) -> Result<()> {
+        instructions::compound_rewards(ctx)
+    }
+
+    /// Read a backer's claimable rewards, reward_per_share and deposit (return data, no state change)
+    pub fn get_claimable_rewards(ctx: Context<GetClaimableRewards>) -> Result<ClaimableRewards> {
+        instructions::get_claimable_rewards(ctx)
+    }
+
+    /// Read a backer's deposit, total_deposited and lock state as a governance weight (return data)
+    pub fn get_voting_power(ctx: Context<GetVotingPower>) -> Result<VotingPower> {
+        instructions::get_voting_power(ctx)
+    }
+
+    /// Quote the lamports a full exit (unstake + claim) would yield right now (ExitPreview event)
+    pub fn preview_exit(ctx: Context<PreviewExit>) -> Result<()> {
+        instructions::preview_exit(ctx)
+    }
+
+    /// Estimate a backer's rewards over the next `horizon_seconds` (forward-looking, not guaranteed)
+    pub fn project_rewards(ctx: Context<ProjectRewards>, horizon_seconds: i64) -> Result<()> {
+        instructions::project_rewards(ctx, horizon_seconds)
+    }
+
+    /// Lender stake SPL tokens into the token treasury for `mint` (rewards paid in the same mint)
+    pub fn stake_spl(ctx: Context<StakeSpl>, amount: u64) -> Result<()> {
+        instructions::stake_spl(ctx, amount)
+    }
+
+    /// Lender unstake SPL tokens from the token treasury for `mint`
+    pub fn unstake_spl(ctx: Context<UnstakeSpl>, amount: u64) -> Result<()> {
+        instructions::unstake_spl(ctx, amount)
+    }
+
+    /// Lender claim all accumulated SPL rewards
+    pub fn claim_rewards_spl(ctx: Context<ClaimRewardsSpl>) -> Result<()> {
+        instructions::claim_rewards_spl(ctx)
     }
 
     /// Request deployment funds from treasury pool
@@ -69,7 +138,7 @@ pub mod d2d_program_sol {
         instructions::deploy_program(ctx, program_hash, service_fee, monthly_fee, initial_months, deployment_cost)
     }
 
-    /// Developer pay monthly subscription
+    /// Developer pay monthly subscription (6+ months 5% off, 12+ months 10% off, max 36)
     pub fn pay_subscription(
         ctx: Context<PaySubscription>,
         request_id: [u8; 32],
@@ -78,21 +147,264 @@ pub mod d2d_program_sol {
         instructions::pay_subscription(ctx, request_id, months)
     }
 
+    /// Developer deposit SOL for automatic renewal (spent a month at a time by renew_from_prepaid)
+    pub fn top_up_prepaid(
+        ctx: Context<TopUpPrepaid>,
+        request_id: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        instructions::top_up_prepaid(ctx, request_id, amount)
+    }
+
+    /// Developer cancel a pending deploy request before it is funded
+    /// Refunds service fee + unused prepaid months from Reward Pool
+    pub fn cancel_deploy_request(
+        ctx: Context<CancelDeployRequest>,
+        request_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::cancel_deploy_request(ctx, request_id)
+    }
+
+    /// Developer retry a failed deploy request on the same PDA, re-paying service fee + prepaid months
+    pub fn retry_deployment(ctx: Context<RetryDeployment>, request_id: [u8; 32]) -> Result<()> {
+        instructions::retry_deployment(ctx, request_id)
+    }
+
+    /// Developer hand a live deploy request to another developer (both sign)
+    pub fn transfer_deploy_ownership(ctx: Context<TransferDeployOwnership>, request_id: [u8; 32]) -> Result<()> {
+        instructions::transfer_deploy_ownership(ctx, request_id)
+    }
+
+    /// Developer refund a pending deploy request while deployments are halted
+    /// Refunds service fee + all prepaid months from Reward Pool
+    pub fn refund_on_halt(ctx: Context<RefundOnHalt>, request_id: [u8; 32]) -> Result<()> {
+        instructions::refund_on_halt(ctx, request_id)
+    }
+
+    /// Preview the Reward Pool / Platform Pool payment for a deploy request (return data, no state change)
+    pub fn estimate_deploy_cost(
+        ctx: Context<EstimateDeployCost>,
+        monthly_fee: u64,
+        initial_months: u32,
+        service_fee: u64,
+        deployment_cost: u64,
+        reward_split_bps: Option<u64>,
+    ) -> Result<DeployCostEstimate> {
+        instructions::estimate_deploy_cost(ctx, monthly_fee, initial_months, service_fee, deployment_cost, reward_split_bps)
+    }
+
+    /// Read a deploy request's status, program id and subscription validity (return data, no state change)
+    pub fn get_deploy_status(
+        ctx: Context<GetDeployStatus>,
+        program_hash: [u8; 32],
+    ) -> Result<DeployStatus> {
+        instructions::get_deploy_status(ctx, program_hash)
+    }
+
+    /// Read a developer's sessions, daily / lifetime deploys and deploys left today (return data, no state change)
+    pub fn get_user_stats(ctx: Context<GetUserStats>) -> Result<UserStats> {
+        instructions::get_user_stats(ctx)
+    }
+
+    /// Check subscription validity of the DeployRequests in remaining_accounts (bitmap return data)
+    pub fn check_subscriptions_batch(ctx: Context<CheckSubscriptionsBatch>) -> Result<SubscriptionBatch> {
+        instructions::check_subscriptions_batch(ctx)
+    }
+
     /// Admin update APY
     pub fn update_apy(ctx: Context<UpdateApy>, new_apy: u64) -> Result<()> {
         instructions::update_apy(ctx, new_apy)
     }
 
+    /// Admin crank: pay claimable rewards to many backers (pairs in remaining_accounts)
+    pub fn claim_rewards_batch(ctx: Context<ClaimRewardsBatch>) -> Result<()> {
+        instructions::claim_rewards_batch(ctx)
+    }
+
     /// Admin suspend expired programs
     pub fn suspend_expired_programs(ctx: Context<SuspendExpiredPrograms>) -> Result<()> {
         instructions::suspend_expired_programs(ctx)
     }
 
+    /// Admin propose a new admin key (step 1 of two-step transfer)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin(ctx, new_admin)
+    }
+
+    /// Pending admin accepts the transfer (step 2 of two-step transfer)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
+    /// Admin cancel a pending admin transfer
+    pub fn cancel_admin_transfer(ctx: Context<ProposeAdmin>) -> Result<()> {
+        instructions::cancel_admin_transfer(ctx)
+    }
+
+    /// Emergency rotate admin and reward admin together (admin or recovery authority)
+    pub fn emergency_rotate_keys(
+        ctx: Context<EmergencyRotateKeys>,
+        new_admin: Pubkey,
+        new_reward_admin: Pubkey,
+    ) -> Result<()> {
+        instructions::emergency_rotate_keys(ctx, new_admin, new_reward_admin)
+    }
+
+    /// Admin rotate the reward admin (admin_withdraw_reward_pool authority)
+    pub fn set_reward_admin(ctx: Context<SetRewardAdmin>, new_reward_admin: Pubkey) -> Result<()> {
+        instructions::set_reward_admin(ctx, new_reward_admin)
+    }
+
+    /// Admin set the recovery authority (multisig) for emergency key rotation
+    pub fn set_recovery_authority(
+        ctx: Context<SetRecoveryAuthority>,
+        recovery_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_recovery_authority(ctx, recovery_authority)
+    }
+
+    /// Admin set the per-backer deposit cap (0 = unlimited)
+    pub fn set_deposit_cap(ctx: Context<SetDepositCap>, max_deposit_per_backer: u64) -> Result<()> {
+        instructions::set_deposit_cap(ctx, max_deposit_per_backer)
+    }
+
+    /// Admin set the pool-wide total_deposited cap (0 = unlimited)
+    pub fn set_pool_cap(ctx: Context<SetPoolCap>, max_total_deposited: u64) -> Result<()> {
+        instructions::set_pool_cap(ctx, max_total_deposited)
+    }
+
+    /// Admin blacklist a developer from opening deploy requests
+    pub fn blacklist_developer(ctx: Context<BlacklistDeveloper>) -> Result<()> {
+        instructions::blacklist_developer(ctx)
+    }
+
+    /// Admin remove a developer from the blacklist
+    pub fn unblacklist_developer(ctx: Context<UnblacklistDeveloper>) -> Result<()> {
+        instructions::unblacklist_developer(ctx)
+    }
+
+    /// Admin set the minimum claim_rewards amount (lamports, 0 = no minimum)
+    pub fn set_min_claim(ctx: Context<SetMinClaim>, min_claim_amount: u64) -> Result<()> {
+        instructions::set_min_claim(ctx, min_claim_amount)
+    }
+
+    /// Admin set the monthly fee as bps of deployment_cost (0 = caller passes monthly_fee explicitly)
+    pub fn set_monthly_fee_bps(ctx: Context<SetMonthlyFeeBps>, monthly_fee_bps: u64) -> Result<()> {
+        instructions::set_monthly_fee_bps(ctx, monthly_fee_bps)
+    }
+
+    /// Admin set the bounds a per-request reward_split_bps override is clamped to
+    pub fn set_reward_split_bounds(
+        ctx: Context<SetRewardSplitBounds>,
+        min_reward_split_bps: u64,
+        max_reward_split_bps: u64,
+    ) -> Result<()> {
+        instructions::set_reward_split_bounds(ctx, min_reward_split_bps, max_reward_split_bps)
+    }
+
+    /// Admin set how long after a stake_sol deposit the backer must wait to claim (0 = off)
+    pub fn set_claim_cooldown(ctx: Context<SetClaimCooldown>, claim_cooldown_secs: i64) -> Result<()> {
+        instructions::set_claim_cooldown(ctx, claim_cooldown_secs)
+    }
+
+    /// Admin set the minimum stake_sol deposit
+    pub fn set_min_deposit(ctx: Context<SetMinDeposit>, min_deposit: u64) -> Result<()> {
+        instructions::set_min_deposit(ctx, min_deposit)
+    }
+
+    /// Admin set the reward and platform fee rates (basis points, max 10% each)
+    pub fn set_fee_rates(ctx: Context<SetFeeRates>, reward_fee_bps: u64, platform_fee_bps: u64) -> Result<()> {
+        instructions::set_fee_rates(ctx, reward_fee_bps, platform_fee_bps)
+    }
+
+    /// Admin set the grace period between subscription expiry and suspension (seconds, max 90 days)
+    pub fn set_grace_period(ctx: Context<SetGracePeriod>, grace_period_secs: i64) -> Result<()> {
+        instructions::set_grace_period(ctx, grace_period_secs)
+    }
+
+    /// Admin set how long after expiry a Suspended request may be reinstated (seconds, max 365 days)
+    pub fn set_max_lapse(ctx: Context<SetMaxLapse>, max_lapse_secs: i64) -> Result<()> {
+        instructions::set_max_lapse(ctx, max_lapse_secs)
+    }
+
+    /// Admin set the timeout before force_recover_ephemeral may sweep a hung deployment (seconds)
+    pub fn set_ephemeral_recovery_timeout(
+        ctx: Context<SetEphemeralRecoveryTimeout>,
+        timeout_secs: i64,
+    ) -> Result<()> {
+        instructions::set_ephemeral_recovery_timeout(ctx, timeout_secs)
+    }
+
+    /// Admin set the share of recovered deployment funds routed to the Platform Pool (basis points, max 50%)
+    pub fn set_recovery_platform_bps(
+        ctx: Context<SetRecoveryPlatformBps>,
+        recovery_platform_bps: u64,
+    ) -> Result<()> {
+        instructions::set_recovery_platform_bps(ctx, recovery_platform_bps)
+    }
+
+    /// Admin set the repeated-failure policy: refunds past `threshold` failures lose `slash_bps` (max 50%)
+    pub fn set_failure_slash(ctx: Context<SetFailureSlash>, threshold: u32, slash_bps: u64) -> Result<()> {
+        instructions::set_failure_slash(ctx, threshold, slash_bps)
+    }
+
+    /// Admin set the early-unstake penalty for locked stakes (basis points)
+    pub fn set_penalty_bps(ctx: Context<SetPenaltyBps>, penalty_bps: u64) -> Result<()> {
+        instructions::set_penalty_bps(ctx, penalty_bps)
+    }
+
+    /// Admin set per-developer deploy limits (concurrent sessions, deploy requests per day)
+    pub fn set_deploy_limits(
+        ctx: Context<SetDeployLimits>,
+        max_concurrent_sessions: u32,
+        max_daily_deploys: u32,
+    ) -> Result<()> {
+        instructions::set_deploy_limits(ctx, max_concurrent_sessions, max_daily_deploys)
+    }
+
+    /// Admin cap a single request's deployment_cost (lamports and/or bps of liquid_balance, 0 = no cap)
+    pub fn set_deployment_cost_cap(
+        ctx: Context<SetDeploymentCostCap>,
+        max_deployment_cost: u64,
+        max_deployment_bps: u64,
+    ) -> Result<()> {
+        instructions::set_deployment_cost_cap(ctx, max_deployment_cost, max_deployment_bps)
+    }
+
+    /// Admin halt/resume new deployments
+    pub fn set_deployments_paused(ctx: Context<SetDeploymentsPaused>, paused: bool) -> Result<()> {
+        instructions::set_deployments_paused(ctx, paused)
+    }
+
+    /// Admin toggle reward pool isolation (blocks Reward Pool deployment funding)
+    pub fn set_reward_pool_isolated(ctx: Context<SetRewardPoolIsolated>, isolated: bool) -> Result<()> {
+        instructions::set_reward_pool_isolated(ctx, isolated)
+    }
+
+    /// Admin set the emergency_unstake fee routed to the insurance pool (basis points)
+    pub fn set_emergency_withdraw_fee_bps(ctx: Context<SetEmergencyWithdrawFeeBps>, fee_bps: u64) -> Result<()> {
+        instructions::set_emergency_withdraw_fee_bps(ctx, fee_bps)
+    }
+
     /// Emergency pause/unpause
     pub fn emergency_pause(ctx: Context<EmergencyPause>, pause: bool) -> Result<()> {
         instructions::emergency_pause(ctx, pause)
     }
 
+    /// Admin set the Reward Pool outflow limit per window that auto-trips emergency_pause (0 = disabled)
+    pub fn set_outflow_breaker(
+        ctx: Context<SetOutflowBreaker>,
+        max_outflow_per_window: u64,
+        outflow_window_secs: i64,
+    ) -> Result<()> {
+        instructions::set_outflow_breaker(ctx, max_outflow_per_window, outflow_window_secs)
+    }
+
+    /// Admin pause individual operations (TreasuryPool::PAUSE_* bits, PAUSE_ALL = emergency_pause)
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, paused_ops: u8) -> Result<()> {
+        instructions::set_pause_flags(ctx, paused_ops)
+    }
+
     /// Admin confirm deployment success
     pub fn confirm_deployment_success(
         ctx: Context<ConfirmDeployment>,
@@ -112,6 +424,14 @@ pub mod d2d_program_sol {
         instructions::confirm_deployment_failure(ctx, request_id, failure_reason)
     }
 
+    /// Admin sweep a hung deployment's ephemeral key back to the treasury after the recovery timeout
+    pub fn force_recover_ephemeral(
+        ctx: Context<ForceRecoverEphemeral>,
+        request_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::force_recover_ephemeral(ctx, request_id)
+    }
+
     /// Admin close program and refund recovered lamports to pool
     pub fn close_program_and_refund(
         ctx: Context<CloseProgramAndRefund>,
@@ -121,9 +441,37 @@ pub mod d2d_program_sol {
         instructions::close_program_and_refund(ctx, request_id, recovered_lamports)
     }
 
+    /// Admin grant free subscription months without payment (tracked in credited_months)
+    pub fn grant_subscription_credit(
+        ctx: Context<GrantSubscriptionCredit>,
+        request_id: [u8; 32],
+        months: u32,
+    ) -> Result<()> {
+        instructions::grant_subscription_credit(ctx, request_id, months)
+    }
+
+    /// Admin force-close a deploy request whose subscription lapsed more than abandoned_after_days ago
+    pub fn force_close_abandoned(
+        ctx: Context<ForceCloseAbandoned>,
+        request_id: [u8; 32],
+        recovered_lamports: u64,
+        abandoned_after_days: u32,
+    ) -> Result<()> {
+        instructions::force_close_abandoned(ctx, request_id, recovered_lamports, abandoned_after_days)
+    }
+
+    /// Admin close a Failed / Cancelled / Closed deploy request and reclaim its rent
+    pub fn close_deploy_request(
+        ctx: Context<CloseDeployRequest>,
+        request_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::close_deploy_request(ctx, request_id)
+    }
+
     /// Admin fund temporary wallet for deployment
     /// Only backend admin can call this to transfer deployment funds
     /// use_admin_pool: true = use Admin Pool, false = use Reward Pool (preferred)
+    /// Reward Pool requests are rejected while reward_pool_isolated is set
     pub fn fund_temporary_wallet(
         ctx: Context<FundTemporaryWallet>,
         request_id: [u8; 32],
@@ -133,9 +481,16 @@ pub mod d2d_program_sol {
         instructions::fund_temporary_wallet(ctx, request_id, amount, use_admin_pool)
     }
 
+    /// Admin fund several deployments at once (deploy_request / temporary wallet pairs in remaining_accounts)
+    /// All-or-nothing: fails if any request cannot be funded
+    pub fn fund_temporary_wallets_batch(ctx: Context<FundTemporaryWalletsBatch>, use_admin_pool: bool) -> Result<()> {
+        instructions::fund_temporary_wallets_batch(ctx, use_admin_pool)
+    }
+
     /// Admin create deploy request after payment verification
     /// Only backend admin can call this after verifying developer payment
     /// Payment has already been transferred to Reward Pool
+    /// reward_split_bps overrides the Reward/Platform split of the up-front fee (None = default)
     pub fn create_deploy_request(
         ctx: Context<CreateDeployRequest>,
         program_hash: [u8; 32],
@@ -143,11 +498,13 @@ pub mod d2d_program_sol {
         monthly_fee: u64,
         initial_months: u32,
         deployment_cost: u64,
+        reward_split_bps: Option<u64>,
     ) -> Result<()> {
-        instructions::create_deploy_request(ctx, program_hash, service_fee, monthly_fee, initial_months, deployment_cost)
+        instructions::create_deploy_request(ctx, program_hash, service_fee, monthly_fee, initial_months, deployment_cost, reward_split_bps)
     }
 
     /// Admin withdraw funds from Admin Pool
+    /// Admin Pool aliases the Platform Pool: debits platform_pool_balance like withdraw_platform_pool
     pub fn admin_withdraw(
         ctx: Context<AdminWithdraw>,
         amount: u64,
@@ -165,14 +522,74 @@ pub mod d2d_program_sol {
         instructions::admin_withdraw_reward_pool(ctx, amount, reason)
     }
 
+    /// Admin withdraw platform fees from Platform Pool (debits platform_pool_balance)
+    pub fn withdraw_platform_pool(
+        ctx: Context<WithdrawPlatformPool>,
+        amount: u64,
+        reason: String,
+    ) -> Result<()> {
+        instructions::withdraw_platform_pool(ctx, amount, reason)
+    }
+
+    /// Admin move platform fees to the Reward Pool (credited to backers via reward_per_share)
+    pub fn move_platform_to_reward(ctx: Context<MovePlatformToReward>, amount: u64) -> Result<()> {
+        instructions::move_platform_to_reward(ctx, amount)
+    }
+
+    /// Admin top up an insolvent Reward Pool from the Platform Pool so claims can proceed
+    pub fn backstop_reward_pool(ctx: Context<BackstopRewardPool>) -> Result<()> {
+        instructions::backstop_reward_pool(ctx)
+    }
+
+    /// Admin move reward_per_share truncation dust from the Reward Pool to the Platform Pool
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        instructions::sweep_dust(ctx)
+    }
+
+    /// Admin create the reward_per_share history ring buffer (recorded by credit_fee_to_pool)
+    pub fn initialize_reward_history(ctx: Context<InitializeRewardHistory>) -> Result<()> {
+        instructions::initialize_reward_history(ctx)
+    }
+
+    /// Admin create the D2DConfig account (minimum service fee and per-user session cap for deploy requests)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_rate: u64,
+        max_concurrent_per_user: u32,
+    ) -> Result<()> {
+        instructions::initialize_config(ctx, fee_rate, max_concurrent_per_user)
+    }
+
+    /// Admin update the D2DConfig tunables
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        fee_rate: u64,
+        max_concurrent_per_user: u32,
+        is_paused: bool,
+    ) -> Result<()> {
+        instructions::update_config(ctx, fee_rate, max_concurrent_per_user, is_paused)
+    }
+
+    /// Admin open SPL token deposits for a mint (creates the TokenTreasury and its vault ATA)
+    pub fn initialize_token_treasury(ctx: Context<InitializeTokenTreasury>) -> Result<()> {
+        instructions::initialize_token_treasury(ctx)
+    }
+
+    /// Admin fund SPL rewards for a token treasury (credited via its reward_per_share)
+    pub fn fund_spl_rewards(ctx: Context<FundSplRewards>, amount: u64) -> Result<()> {
+        instructions::fund_spl_rewards(ctx, amount)
+    }
+
     /// Close Treasury Pool account (Admin only)
     /// 
     /// This closes the treasury pool account and transfers all lamports to admin.
     /// Does NOT require deserializing the account, so it works with old struct layouts.
     /// 
+    /// A current-layout pool that still holds backer principal or rewards is only closed with force = true.
+    ///
     /// After closing, call reinitialize_treasury_pool() to create a new account with the updated layout.
-    pub fn close_treasury_pool(ctx: Context<CloseTreasuryPool>) -> Result<()> {
-        instructions::close_treasury_pool(ctx)
+    pub fn close_treasury_pool(ctx: Context<CloseTreasuryPool>, force: bool) -> Result<()> {
+        instructions::close_treasury_pool(ctx, force)
     }
 
     /// Reinitialize Treasury Pool (Admin only)
@@ -185,18 +602,20 @@ pub mod d2d_program_sol {
         ctx: Context<ReinitializeTreasuryPool>,
         initial_apy: u64,
         dev_wallet: Pubkey,
+        reserved_padding: u32,
     ) -> Result<()> {
-        instructions::reinitialize_treasury_pool(ctx, initial_apy, dev_wallet)
+        instructions::reinitialize_treasury_pool(ctx, initial_apy, dev_wallet, reserved_padding)
     }
 
     /// Credit fees to pools and update reward_per_share
-    /// Admin/backend only - called when devs pay fees
+    /// Admin/backend only - called when devs pay fees; a repeated fee_id is rejected (DuplicateFee)
     pub fn credit_fee_to_pool(
         ctx: Context<CreditFeeToPool>,
+        fee_id: [u8; 32],
         fee_reward: u64,
         fee_platform: u64,
     ) -> Result<()> {
-        instructions::credit_fee_to_pool(ctx, fee_reward, fee_platform)
+        instructions::credit_fee_to_pool(ctx, fee_id, fee_reward, fee_platform)
     }
 
     /// Admin sync liquid_balance with actual account balance
@@ -205,16 +624,100 @@ pub mod d2d_program_sol {
         instructions::sync_liquid_balance(ctx)
     }
 
+    /// Admin check liquid, reward and platform balances against PDA lamports
+    /// Fails beyond `tolerance` lamports unless `force` resets the tracked balances
+    pub fn reconcile_pools(ctx: Context<ReconcilePools>, tolerance: u64, force: bool) -> Result<()> {
+        instructions::reconcile_pools(ctx, tolerance, force)
+    }
+
     /// Emergency force rebalance withdrawal pool (no admin check)
     /// Temporary workaround when admin keypair is lost
     pub fn force_rebalance(ctx: Context<ForceRebalance>) -> Result<()> {
         instructions::force_rebalance(ctx)
     }
 
-    /// Migrate Treasury Pool to new layout (removed withdrawal_pool_balance)
-    /// Admin-only instruction to migrate existing pool to new struct layout
-    /// This preserves all existing data and removes withdrawal_pool_balance field
+    /// Migrate Treasury Pool to the current layout (admin-only, once per LAYOUT_VERSION)
+    /// Preserves deposits, pool balances, admin and bumps, unlike close + reinitialize
     pub fn migrate_treasury_pool(ctx: Context<MigrateTreasuryPool>) -> Result<()> {
         instructions::migrate_treasury_pool(ctx)
     }
+
+    /// Flag the pool as undercollateralized (permissionless)
+    /// Sets the on-chain alert when assets cannot cover deposits + rewards owed
+    pub fn flag_undercollateralization(ctx: Context<FlagUndercollateralization>) -> Result<()> {
+        instructions::flag_undercollateralization(ctx)
+    }
+
+    /// Assert each pool PDA covers its tracked obligations (permissionless, fails naming the broken invariant)
+    pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<()> {
+        instructions::verify_solvency(ctx)
+    }
+
+    /// Read pool utilization (total_borrowed / total_deposited in bps, return data, no state change)
+    pub fn get_utilization(ctx: Context<GetUtilization>) -> Result<Utilization> {
+        instructions::get_utilization(ctx)
+    }
+
+    /// Read headline pool figures (deposits, balances, reward_per_share, utilization) as return data
+    pub fn get_pool_stats(ctx: Context<GetPoolStats>) -> Result<PoolStats> {
+        instructions::get_pool_stats(ctx)
+    }
+
+    /// Accrue the 1% monthly borrow fee from a deployment's prepaid escrow (permissionless)
+    pub fn accrue_monthly_fee(ctx: Context<AccrueMonthlyFee>) -> Result<()> {
+        instructions::accrue_monthly_fee(ctx)
+    }
+
+    /// Renew a subscription by one month from the developer's renewal balance (permissionless)
+    pub fn renew_from_prepaid(ctx: Context<RenewFromPrepaid>) -> Result<()> {
+        instructions::renew_from_prepaid(ctx)
+    }
+
+    /// Sponsor backer yield: any signer transfers SOL into the Reward Pool, credited via reward_per_share
+    pub fn sponsor_reward_pool(ctx: Context<SponsorRewardPool>, amount: u64) -> Result<()> {
+        instructions::sponsor_reward_pool(ctx, amount)
+    }
+
+    /// Migrate a backer deposit account to the current layout (permissionless)
+    /// Resizes, tops up rent from the payer and preserves existing fields
+    pub fn migrate_backer_deposit(ctx: Context<MigrateBackerDeposit>) -> Result<()> {
+        instructions::migrate_backer_deposit(ctx)
+    }
+
+    /// Grow a developer's UserDeployStats account to the current layout (permissionless)
+    pub fn migrate_user_stats(ctx: Context<MigrateUserStats>) -> Result<()> {
+        instructions::migrate_user_stats(ctx)
+    }
+
+    /// Move a legacy hash-only deploy request to its developer-scoped PDA (permissionless)
+    pub fn migrate_deploy_request(
+        ctx: Context<MigrateDeployRequest>,
+        program_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::migrate_deploy_request(ctx, program_hash)
+    }
+
+    /// Admin clear the undercollateralized flag once the shortfall is resolved
+    pub fn clear_undercollateralization(ctx: Context<ClearUndercollateralization>) -> Result<()> {
+        instructions::clear_undercollateralization(ctx)
+    }
+
+    /// Create a deposit-weighted governance proposal (permissionless)
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        param: ProposalParam,
+        new_value: u64,
+    ) -> Result<()> {
+        instructions::create_proposal(ctx, param, new_value)
+    }
+
+    /// Backer vote on a proposal, weighted by deposited_amount
+    pub fn vote(ctx: Context<Vote>, proposal_id: u64, support: bool) -> Result<()> {
+        instructions::vote(ctx, proposal_id, support)
+    }
+
+    /// Apply a proposal that reached quorum and majority after its deadline
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+        instructions::execute_proposal(ctx, proposal_id)
+    }
 }
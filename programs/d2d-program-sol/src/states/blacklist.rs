@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Marker that a developer may not open new deploy requests
+///
+/// - Exists only while the developer is blacklisted (blacklist_developer creates it,
+///   unblacklist_developer closes it)
+/// - Deploy request instructions take the PDA unconditionally and reject if it is live
+#[account]
+#[derive(InitSpace)]
+pub struct Blacklist {
+    pub developer: Pubkey,    // Blacklisted developer
+    pub blacklisted_at: i64,  // When the entry was created
+    pub bump: u8,             // PDA bump
+}
+
+impl Blacklist {
+    pub const PREFIX_SEED: &'static [u8] = b"blacklist";
+
+    /// True if `info` is a live Blacklist account owned by this program
+    /// Callers pin the address with seeds, so a missing (system-owned, empty) account reads as false
+    pub fn is_active(info: &AccountInfo) -> bool {
+        if info.owner != &crate::ID {
+            return false;
+        }
+        let data = match info.try_borrow_data() {
+            Ok(data) => data,
+            Err(_) => return false,
+        };
+        data.len() >= 8 && &data[..8] == Self::DISCRIMINATOR
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::ErrorCode;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -27,19 +28,312 @@ pub struct DeployRequest {
     pub status: DeployRequestStatus,         // Current status
     pub created_at: i64,                     // Creation timestamp
     pub bump: u8,                            // PDA bump
+    pub prepaid_balance: u64,                // Prepaid subscription fees escrowed in Reward Pool, not yet accrued
+    pub last_fee_accrual: i64,               // Last monthly fee accrual checkpoint (created_at on creation; 0 on legacy requests = use created_at)
+    pub funding_locked: bool,                // Set while deployment funds are in flight, cleared on confirm/fail
+    pub initial_months: u32,                 // Months prepaid when the request was (re)submitted (0 on legacy requests)
+    pub renewal_balance: u64,                // Developer top-ups held in this PDA for renew_from_prepaid (not yet escrowed)
+    pub reserved_amount: u64,                // Deployment cost held in TreasuryPool.escrowed_amount until funded or resolved
+    pub deployed_at: Option<i64>,            // Set by confirm_deployment_success (deploy latency = deployed_at - created_at)
+    pub original_developer: Option<Pubkey>,  // Developer the PDA was derived with, set by the first transfer_deploy_ownership (None = developer)
+    pub credited_months: u32,                // Free months granted by grant_subscription_credit (never refunded)
 }
 
 impl DeployRequest {
-    pub const PREFIX_SEED: &'static [u8] = b"deploy_request";
+    pub const PREFIX_SEED: &'static [u8] = b"deploy_request"; // Seeds: [PREFIX_SEED, developer, program_hash] (legacy: [PREFIX_SEED, program_hash])
+    pub const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60; // 30 days
+    pub const MONTHLY_BORROW_FEE_BPS: u64 = 100; // 1% of borrowed_amount per month
+    pub const RENEWAL_WINDOW_SECS: i64 = 3 * 24 * 60 * 60; // renew_from_prepaid may run 3 days before expiry
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
 
-    pub fn is_subscription_valid(&self) -> Result<bool> {
+    /// Decode an account written with an older (shorter) layout
+    /// Fields are append-only, so missing trailing bytes are zero-filled and decode as 0/false
+    pub fn from_legacy_bytes(data: &[u8]) -> Result<Self> {
+        let mut buf = data.to_vec();
+        if buf.len() < 8 + Self::INIT_SPACE {
+            buf.resize(8 + Self::INIT_SPACE, 0);
+        }
+        Self::try_deserialize(&mut &buf[..])
+    }
+
+    /// Developer key in this request's PDA seeds; stays fixed when ownership is transferred
+    pub fn seed_developer(&self) -> Pubkey {
+        self.original_developer.unwrap_or(self.developer)
+    }
+
+    /// Seconds from created_at to confirm_deployment_success (None until deployed)
+    pub fn deploy_latency_secs(&self) -> Option<i64> {
+        self.deployed_at.map(|deployed_at| deployed_at.saturating_sub(self.created_at))
+    }
+
+    /// Subscription paid, or expired but still within the grace period
+    pub fn is_subscription_valid(&self, grace_period_secs: i64) -> Result<bool> {
         let current_time = Clock::get()?.unix_timestamp;
-        Ok(current_time <= self.subscription_paid_until)
+        Ok(self.is_subscription_valid_at(current_time, grace_period_secs))
+    }
+
+    /// is_subscription_valid against a caller-supplied clock (batch checks read Clock once)
+    pub fn is_subscription_valid_at(&self, current_time: i64, grace_period_secs: i64) -> bool {
+        current_time <= self.subscription_paid_until
+            || self.is_in_grace(current_time, grace_period_secs)
+    }
+
+    /// Expired but not yet suspendable (pay_subscription can still recover it)
+    pub fn is_in_grace(&self, current_time: i64, grace_period_secs: i64) -> bool {
+        current_time > self.subscription_paid_until
+            && !self.is_past_grace(current_time, grace_period_secs)
+    }
+
+    /// Grace period elapsed: subscription_paid_until + grace_period_secs < current_time
+    pub fn is_past_grace(&self, current_time: i64, grace_period_secs: i64) -> bool {
+        self.subscription_paid_until.saturating_add(grace_period_secs) < current_time
+    }
+
+    /// Lapsed too long to reinstate: subscription_paid_until + max_lapse_secs < current_time
+    /// Past this point a Suspended request needs a fresh deployment
+    pub fn is_past_lapse(&self, current_time: i64, max_lapse_secs: i64) -> bool {
+        self.subscription_paid_until.saturating_add(max_lapse_secs) < current_time
+    }
+
+    /// Unpaid for more than abandoned_after_days and past the grace period (force_close_abandoned)
+    pub fn is_abandoned(&self, current_time: i64, grace_period_secs: i64, abandoned_after_days: u32) -> bool {
+        let abandoned_after_secs = (abandoned_after_days as i64).saturating_mul(Self::SECONDS_PER_DAY);
+        self.is_past_grace(current_time, grace_period_secs.max(abandoned_after_secs))
+    }
+
+    /// Subscription expires within RENEWAL_WINDOW_SECS (or already has)
+    pub fn is_renewal_due(&self, current_time: i64) -> bool {
+        current_time >= self.subscription_paid_until.saturating_sub(Self::RENEWAL_WINDOW_SECS)
     }
 
     pub fn extend_subscription(&mut self, months: u32) {
-        let seconds_per_month = 30 * 24 * 60 * 60; // 30 days
-        let extension_seconds = months as i64 * seconds_per_month;
+        let extension_seconds = months as i64 * Self::SECONDS_PER_MONTH;
         self.subscription_paid_until += extension_seconds;
     }
+
+    /// Subscription months paid since the request was created (at least 1)
+    /// Credited months extend subscription_paid_until but were never paid, so they are excluded
+    pub fn months_paid(&self) -> u64 {
+        (((self.subscription_paid_until - self.created_at).max(0) / Self::SECONDS_PER_MONTH) as u64)
+            .saturating_sub(self.credited_months as u64)
+            .max(1)
+    }
+
+    /// Months prepaid with the current submission, refunded in full if the deployment fails
+    /// Legacy requests without initial_months fall back to months_paid
+    pub fn prepaid_months(&self) -> u64 {
+        if self.initial_months > 0 {
+            self.initial_months as u64
+        } else {
+            self.months_paid()
+        }
+    }
+
+    /// Prepaid subscription months not yet consumed at `current_time`
+    /// Partial months are rounded up (never more than the months originally paid)
+    pub fn months_remaining(&self, current_time: i64) -> u64 {
+        if self.subscription_paid_until <= current_time {
+            return 0;
+        }
+        let remaining_seconds = self.subscription_paid_until - current_time;
+        let months = (remaining_seconds + Self::SECONDS_PER_MONTH - 1) / Self::SECONDS_PER_MONTH;
+        // Credited months are counted as the tail of the subscription
+        (months as u64)
+            .saturating_sub(self.credited_months as u64)
+            .min(self.months_paid())
+    }
+
+    /// Whole prepaid months left unused at `current_time` (partial current month is not refunded)
+    pub fn months_unused(&self, current_time: i64) -> u64 {
+        if self.subscription_paid_until <= current_time {
+            return 0;
+        }
+        let months = (self.subscription_paid_until - current_time) / Self::SECONDS_PER_MONTH;
+        (months as u64)
+            .saturating_sub(self.credited_months as u64)
+            .min(self.months_paid())
+    }
+
+    /// Start of the current fee period: last_fee_accrual, or created_at for requests
+    /// written before the checkpoint was initialized
+    pub fn fee_accrual_start(&self) -> i64 {
+        if self.last_fee_accrual > 0 {
+            self.last_fee_accrual
+        } else {
+            self.created_at
+        }
+    }
+
+    /// Whole 30-day periods elapsed since the last fee accrual
+    pub fn months_since_accrual(&self, current_time: i64) -> u64 {
+        ((current_time - self.fee_accrual_start()).max(0) / Self::SECONDS_PER_MONTH) as u64
+    }
+
+    /// Borrow fee owed for `months`: borrowed_amount * 1% * months
+    pub fn borrow_fee_for(&self, months: u64) -> Result<u64> {
+        let fee = (self.borrowed_amount as u128)
+            .checked_mul(Self::MONTHLY_BORROW_FEE_BPS as u128)
+            .and_then(|x| x.checked_mul(months as u128))
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / 10000;
+        u64::try_from(fee).map_err(|_| error!(ErrorCode::CalculationOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn funded_request(created_at: i64) -> DeployRequest {
+        DeployRequest {
+            request_id: [0u8; 32],
+            developer: Pubkey::default(),
+            program_hash: [0u8; 32],
+            service_fee: 0,
+            monthly_fee: 0,
+            deployment_cost: 10_000_000_000,
+            borrowed_amount: 10_000_000_000,
+            subscription_paid_until: created_at,
+            ephemeral_key: None,
+            deployed_program_id: None,
+            status: DeployRequestStatus::Active,
+            created_at,
+            bump: 0,
+            prepaid_balance: 0,
+            last_fee_accrual: 0,
+            funding_locked: false,
+            initial_months: 0,
+            renewal_balance: 0,
+            reserved_amount: 0,
+            deployed_at: None,
+            original_developer: None,
+            credited_months: 0,
+        }
+    }
+
+    #[test]
+    fn accrual_counts_whole_months_since_checkpoint() {
+        let mut request = funded_request(1_000);
+        let month = DeployRequest::SECONDS_PER_MONTH;
+
+        assert_eq!(request.months_since_accrual(1_000 + month - 1), 0);
+        assert_eq!(request.months_since_accrual(1_000 + 3 * month + 5), 3);
+
+        request.last_fee_accrual = 1_000 + 2 * month;
+        assert_eq!(request.months_since_accrual(1_000 + 3 * month + 5), 1);
+        assert_eq!(request.months_since_accrual(0), 0);
+    }
+
+    #[test]
+    fn unused_months_exclude_the_current_month() {
+        let mut request = funded_request(0);
+        let month = DeployRequest::SECONDS_PER_MONTH;
+        request.subscription_paid_until = 3 * month;
+
+        assert_eq!(request.months_unused(0), 3);
+        assert_eq!(request.months_unused(month / 2), 2);
+        assert_eq!(request.months_unused(3 * month), 0);
+        assert_eq!(request.months_unused(4 * month), 0);
+    }
+
+    #[test]
+    fn credited_months_are_never_refundable() {
+        let mut request = funded_request(0);
+        let month = DeployRequest::SECONDS_PER_MONTH;
+        request.subscription_paid_until = 3 * month;
+        request.extend_subscription(2);
+        request.credited_months = 2;
+
+        assert_eq!(request.months_paid(), 3);
+        assert_eq!(request.months_unused(0), 3);
+        assert_eq!(request.months_remaining(0), 3);
+        // Into the credited tail: nothing paid is left
+        assert_eq!(request.months_unused(3 * month), 0);
+        assert_eq!(request.months_remaining(3 * month), 0);
+    }
+
+    #[test]
+    fn prepaid_months_prefer_the_recorded_prepay() {
+        let mut request = funded_request(0);
+        let month = DeployRequest::SECONDS_PER_MONTH;
+        request.subscription_paid_until = 6 * month;
+        assert_eq!(request.prepaid_months(), 6);
+
+        // A same-developer retry keeps created_at, so the recorded value wins
+        request.created_at = -month;
+        request.initial_months = 6;
+        assert_eq!(request.prepaid_months(), 6);
+    }
+
+    #[test]
+    fn renewal_is_due_inside_the_window() {
+        let mut request = funded_request(0);
+        request.subscription_paid_until = DeployRequest::SECONDS_PER_MONTH;
+        let window_start = request.subscription_paid_until - DeployRequest::RENEWAL_WINDOW_SECS;
+
+        assert!(!request.is_renewal_due(window_start - 1));
+        assert!(request.is_renewal_due(window_start));
+        assert!(request.is_renewal_due(request.subscription_paid_until + 1));
+    }
+
+    #[test]
+    fn borrow_fee_is_one_percent_per_month() {
+        let request = funded_request(0);
+        assert_eq!(request.borrow_fee_for(1).unwrap(), 100_000_000);
+        assert_eq!(request.borrow_fee_for(3).unwrap(), 300_000_000);
+        assert_eq!(request.borrow_fee_for(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn grace_period_sits_between_expiry_and_suspension() {
+        let mut request = funded_request(0);
+        request.subscription_paid_until = 1_000;
+        let grace = 500;
+
+        assert!(!request.is_in_grace(1_000, grace));
+        assert!(request.is_in_grace(1_001, grace));
+        assert!(request.is_in_grace(1_500, grace));
+        assert!(!request.is_past_grace(1_500, grace));
+        assert!(!request.is_in_grace(1_501, grace));
+        assert!(request.is_past_grace(1_501, grace));
+        assert!(request.is_subscription_valid_at(1_500, grace));
+        assert!(!request.is_subscription_valid_at(1_501, grace));
+
+        // No grace: suspendable as soon as it expires
+        assert!(!request.is_in_grace(1_001, 0));
+        assert!(request.is_past_grace(1_001, 0));
+    }
+
+    #[test]
+    fn lapse_window_bounds_reinstatement() {
+        let mut request = funded_request(0);
+        request.subscription_paid_until = 1_000;
+
+        assert!(!request.is_past_lapse(3_000, 2_000));
+        assert!(request.is_past_lapse(3_001, 2_000));
+        // No window: never reinstatable once expired
+        assert!(request.is_past_lapse(1_001, 0));
+    }
+
+    #[test]
+    fn abandonment_requires_both_grace_and_day_threshold() {
+        let mut request = funded_request(0);
+        request.subscription_paid_until = 1_000;
+        let day = DeployRequest::SECONDS_PER_DAY;
+
+        assert!(!request.is_abandoned(1_000 + 7 * day, 0, 7));
+        assert!(request.is_abandoned(1_001 + 7 * day, 0, 7));
+        // A longer grace period wins over a shorter day threshold
+        assert!(!request.is_abandoned(1_001 + 7 * day, 10 * day, 7));
+        assert!(request.is_abandoned(1_001 + 10 * day, 10 * day, 7));
+    }
+
+    #[test]
+    fn deploy_latency_runs_from_creation_to_confirmation() {
+        let mut request = funded_request(1_000);
+        assert_eq!(request.deploy_latency_secs(), None);
+
+        request.deployed_at = Some(1_450);
+        assert_eq!(request.deploy_latency_secs(), Some(450));
+    }
 }
@@ -8,6 +8,16 @@ use anchor_lang::prelude::*;
 /// - reward_debt: Tracks accumulated rewards at deposit time (deposited_amount * reward_per_share)
 /// - pending_rewards: Rewards that have been settled but not yet claimed (preserved during unstake/stake)
 /// - claimed_total: Total rewards claimed so far
+/// - pro_rata_withdrawn / haircut_total: Payouts and forfeited principal from pro-rata withdrawals
+/// - last_deposit_at: Deposits made after a proposal was created cannot vote on it
+/// - locked_until: Principal is locked until this timestamp (0 = unlocked)
+/// - layout_version: Bumped by migrate_backer_deposit (0 = legacy account, never migrated)
+/// - claim_authority: Delegate that claims rewards instead of the backer (principal stays backer-only)
+/// - receipt_mint: Non-transferable deposit receipt minted through stake_sol (None = not opted in)
+/// - deposit_start_ts / time_weighted_amount: How long capital has been deposited (loyalty multiplier)
+/// - last_action_ts: Latest stake_sol deposit; claims wait out TreasuryPool.claim_cooldown_secs after it
+///
+/// New fields must only be appended so older accounts can be migrated in place
 #[account]
 #[derive(InitSpace)]
 pub struct BackerDeposit {
@@ -18,6 +28,17 @@ pub struct BackerDeposit {
     pub claimed_total: u64,      // Total rewards claimed so far (lamports)
     pub is_active: bool,         // Is deposit active
     pub bump: u8,                // PDA bump
+    pub pro_rata_withdrawn: u64, // Lamports received through pro-rata withdrawals (undercollateralized pool)
+    pub haircut_total: u64,      // Principal forfeited to pro-rata haircuts (lamports)
+    pub last_deposit_at: i64,    // Timestamp of the latest stake (governance vote snapshot)
+    pub locked_until: i64,       // Unlock timestamp (early unstake pays penalty_bps)
+    pub layout_version: u8,      // Account layout version (see LAYOUT_VERSION)
+    pub claim_authority: Option<Pubkey>, // Delegate allowed to claim rewards (None = backer only)
+    pub receipt_mint: Option<Pubkey>,    // Deposit receipt mint holding 1 frozen unit in the backer's ATA (None = no receipt)
+    pub deposit_start_ts: i64,           // Start of the current position (0 = nothing deposited / not tracked yet)
+    pub time_weighted_amount: u128,      // Sum of deposited_amount * seconds held since deposit_start_ts (lamport-seconds)
+    pub time_weighted_updated_at: i64,   // Last time time_weighted_amount was brought up to date
+    pub last_action_ts: i64,             // Timestamp of the latest stake_sol deposit (claim cooldown anchor)
 }
 
 /// Legacy alias for backward compatibility
@@ -25,6 +46,79 @@ pub type LenderStake = BackerDeposit;
 
 impl BackerDeposit {
     pub const PREFIX_SEED: &'static [u8] = b"lender_stake"; // Keep same seed for backward compatibility
+    pub const RECEIPT_MINT_SEED: &'static [u8] = b"deposit_receipt"; // Seeds: [RECEIPT_MINT_SEED, backer]
+    pub const LOYALTY_RAMP_SECS: i64 = 365 * 24 * 60 * 60; // Capital-time for the full loyalty bonus: deposited_amount held for a year
+    pub const MAX_LOYALTY_BONUS_BPS: u64 = 1000;           // Loyalty multiplier tops out at 1.1x
+    pub const LAYOUT_VERSION: u8 = 5;
+
+    /// Decode an account written with an older (shorter) layout
+    /// Fields are append-only, so missing trailing bytes are zero-filled and decode as 0/false
+    pub fn from_legacy_bytes(data: &[u8]) -> Result<Self> {
+        let mut buf = data.to_vec();
+        if buf.len() < 8 + Self::INIT_SPACE {
+            buf.resize(8 + Self::INIT_SPACE, 0);
+        }
+        let mut deposit = Self::try_deserialize(&mut &buf[..])?;
+        deposit.layout_version = Self::LAYOUT_VERSION;
+        Ok(deposit)
+    }
+
+    /// A claim now would land within cooldown_secs of the latest deposit (0 = no cooldown)
+    pub fn claim_cooldown_active(&self, cooldown_secs: i64, current_time: i64) -> bool {
+        cooldown_secs > 0 && current_time < self.last_action_ts.saturating_add(cooldown_secs)
+    }
+
+    /// time_weighted_amount brought up to current_time at the current deposited_amount
+    /// Positions never tracked (time_weighted_updated_at == 0, e.g. migrated) accrue nothing until their next update
+    pub fn time_weighted_amount_at(&self, current_time: i64) -> Result<u128> {
+        if self.deposited_amount == 0 || self.time_weighted_updated_at == 0 {
+            return Ok(self.time_weighted_amount);
+        }
+        let elapsed = current_time.saturating_sub(self.time_weighted_updated_at).max(0) as u128;
+        (self.deposited_amount as u128)
+            .checked_mul(elapsed)
+            .and_then(|x| x.checked_add(self.time_weighted_amount))
+            .ok_or(ErrorCode::CalculationOverflow.into())
+    }
+
+    /// Fold the time held at the old deposited_amount into time_weighted_amount before it changes
+    /// Call before every deposited_amount update: a new position starts the clock, a full withdrawal resets it
+    pub fn update_time_weighted(&mut self, new_deposited_amount: u64, current_time: i64) -> Result<()> {
+        if new_deposited_amount == 0 {
+            self.deposit_start_ts = 0;
+            self.time_weighted_amount = 0;
+        } else {
+            self.time_weighted_amount = self.time_weighted_amount_at(current_time)?;
+            if self.deposit_start_ts == 0 {
+                self.deposit_start_ts = current_time;
+            }
+        }
+        self.time_weighted_updated_at = current_time;
+        Ok(())
+    }
+
+    /// Loyalty multiplier in bps (10000 = 1x) for future reward boosts
+    /// The bonus ramps linearly to MAX_LOYALTY_BONUS_BPS as capital-time reaches
+    /// deposited_amount * LOYALTY_RAMP_SECS, so fresh top-ups dilute it
+    pub fn loyalty_multiplier_bps(&self, current_time: i64) -> Result<u64> {
+        if self.deposited_amount == 0 {
+            return Ok(10000);
+        }
+        let time_weighted = self.time_weighted_amount_at(current_time)?;
+        let full = (self.deposited_amount as u128)
+            .checked_mul(Self::LOYALTY_RAMP_SECS as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let bonus = (Self::MAX_LOYALTY_BONUS_BPS as u128)
+            .checked_mul(time_weighted.min(full))
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / full;
+        Ok(10000 + bonus as u64)
+    }
+
+    /// Wallet that must sign reward claims: the claim_authority when set, else the backer
+    pub fn claim_signer(&self) -> Pubkey {
+        self.claim_authority.unwrap_or(self.backer)
+    }
 
     /// Calculate claimable rewards using reward-per-share
     /// Formula: pending_rewards + (deposited_amount * reward_per_share - reward_debt) / PRECISION
@@ -72,12 +166,264 @@ impl BackerDeposit {
         Ok(())
     }
 
+    /// Consume part of the claimable rewards (partial claim)
+    /// Takes from pending_rewards first, then advances reward_debt by the rest,
+    /// so the unclaimed remainder stays claimable. Caller checks amount <= claimable
+    pub fn consume_claimable(&mut self, amount: u64) -> Result<()> {
+        use crate::states::TreasuryPool;
+
+        let from_pending = amount.min(self.pending_rewards);
+        self.pending_rewards -= from_pending;
+
+        let from_reward_per_share = (amount - from_pending) as u128;
+        self.reward_debt = from_reward_per_share
+            .checked_mul(TreasuryPool::PRECISION)
+            .and_then(|x| x.checked_add(self.reward_debt))
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        Ok(())
+    }
+
+    /// Record a pro-rata withdrawal
+    /// The full requested principal has already been debited from deposited_amount,
+    /// so the haircut is forfeited and cannot be withdrawn again later
+    pub fn record_pro_rata_withdrawal(&mut self, payout: u64, haircut: u64) -> Result<()> {
+        self.pro_rata_withdrawn = self.pro_rata_withdrawn
+            .checked_add(payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.haircut_total = self.haircut_total
+            .checked_add(haircut)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// reward_debt for a position: deposited_amount * reward_per_share
+    /// Fails with RewardDebtOverflow if the product does not fit in u128
+    pub fn reward_debt_for(deposited_amount: u64, reward_per_share: u128) -> Result<u128> {
+        (deposited_amount as u128)
+            .checked_mul(reward_per_share)
+            .ok_or_else(|| error!(ErrorCode::RewardDebtOverflow))
+    }
+
+    /// Largest deposited_amount whose reward_debt fits at the given reward_per_share
+    pub fn max_deposit_for(reward_per_share: u128) -> u64 {
+        if reward_per_share == 0 {
+            return u64::MAX;
+        }
+        (u128::MAX / reward_per_share).min(u64::MAX as u128) as u64
+    }
+
     /// Update reward_debt after deposit or claim
     /// Sets reward_debt = deposited_amount * reward_per_share
     pub fn update_reward_debt(&mut self, reward_per_share: u128) -> Result<()> {
-        self.reward_debt = (self.deposited_amount as u128)
-            .checked_mul(reward_per_share)
-            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.reward_debt = Self::reward_debt_for(self.deposited_amount, reward_per_share)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reward_debt_fits_below_limit() {
+        let reward_per_share = u128::MAX / 1_000_000_000;
+        let max_deposit = BackerDeposit::max_deposit_for(reward_per_share);
+        assert_eq!(max_deposit, 1_000_000_000);
+        assert!(BackerDeposit::reward_debt_for(max_deposit, reward_per_share).is_ok());
+    }
+
+    #[test]
+    fn reward_debt_overflow_is_rejected() {
+        // Accumulator pumped so that a 1 SOL deposit no longer fits
+        let reward_per_share = u128::MAX / 1_000_000_000;
+        let err = BackerDeposit::reward_debt_for(1_000_000_001, reward_per_share).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::RewardDebtOverflow));
+    }
+
+    #[test]
+    fn zero_accumulator_never_overflows() {
+        assert_eq!(BackerDeposit::max_deposit_for(0), u64::MAX);
+        assert_eq!(BackerDeposit::reward_debt_for(u64::MAX, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn partial_claim_keeps_remainder_claimable() {
+        use crate::states::TreasuryPool;
+
+        let reward_per_share = 3 * TreasuryPool::PRECISION;
+        let mut deposit = BackerDeposit {
+            backer: Pubkey::default(),
+            deposited_amount: 100,
+            reward_debt: 100 * TreasuryPool::PRECISION,
+            pending_rewards: 50,
+            claimed_total: 0,
+            is_active: true,
+            bump: 0,
+            pro_rata_withdrawn: 0,
+            haircut_total: 0,
+            last_deposit_at: 0,
+            locked_until: 0,
+            layout_version: BackerDeposit::LAYOUT_VERSION,
+            claim_authority: None,
+            receipt_mint: None,
+            deposit_start_ts: 0,
+            time_weighted_amount: 0,
+            time_weighted_updated_at: 0,
+            last_action_ts: 0,
+        };
+        // 50 pending + 100 * (3 - 1) accrued
+        assert_eq!(deposit.calculate_claimable_rewards(reward_per_share).unwrap(), 250);
+
+        deposit.consume_claimable(30).unwrap();
+        assert_eq!(deposit.pending_rewards, 20);
+        assert_eq!(deposit.calculate_claimable_rewards(reward_per_share).unwrap(), 220);
+
+        deposit.consume_claimable(120).unwrap();
+        assert_eq!(deposit.pending_rewards, 0);
+        assert_eq!(deposit.calculate_claimable_rewards(reward_per_share).unwrap(), 100);
+    }
+
+    #[test]
+    fn full_withdrawal_keeps_rewards_claimable() {
+        use crate::states::TreasuryPool;
+
+        let mut deposit = BackerDeposit {
+            backer: Pubkey::default(),
+            deposited_amount: 1_000,
+            reward_debt: 0,
+            pending_rewards: 0,
+            claimed_total: 0,
+            is_active: true,
+            bump: 0,
+            pro_rata_withdrawn: 0,
+            haircut_total: 0,
+            last_deposit_at: 0,
+            locked_until: 0,
+            layout_version: BackerDeposit::LAYOUT_VERSION,
+            claim_authority: None,
+            receipt_mint: None,
+            deposit_start_ts: 0,
+            time_weighted_amount: 0,
+            time_weighted_updated_at: 0,
+            last_action_ts: 0,
+        };
+        // deposit -> fee credit of 100 against 1_000 deposited
+        let reward_per_share = 100 * TreasuryPool::PRECISION / 1_000;
+        assert_eq!(deposit.calculate_claimable_rewards(reward_per_share).unwrap(), 100);
+
+        // Full unstake, in unstake_sol's order: settle, debit, reset reward_debt
+        deposit.settle_pending_rewards(reward_per_share).unwrap();
+        deposit.deposited_amount = 0;
+        deposit.is_active = false;
+        deposit.reward_debt = 0;
+        assert_eq!(deposit.calculate_claimable_rewards(reward_per_share).unwrap(), 100);
+
+        // Later fees do not accrue to the empty position, the settled amount stays
+        let reward_per_share = reward_per_share * 5;
+        assert_eq!(deposit.calculate_claimable_rewards(reward_per_share).unwrap(), 100);
+    }
+
+    #[test]
+    fn time_weighted_amount_tracks_capital_held_over_time() {
+        let mut deposit = BackerDeposit::from_legacy_bytes(BackerDeposit::DISCRIMINATOR).unwrap();
+        let day = 24 * 60 * 60;
+
+        // First deposit starts the clock
+        deposit.update_time_weighted(1_000, 10 * day).unwrap();
+        deposit.deposited_amount = 1_000;
+        assert_eq!(deposit.deposit_start_ts, 10 * day);
+
+        // Top-up after 5 days: 1_000 * 5 days folded in, start unchanged
+        deposit.update_time_weighted(3_000, 15 * day).unwrap();
+        deposit.deposited_amount = 3_000;
+        assert_eq!(deposit.time_weighted_amount, 1_000 * 5 * day as u128);
+        assert_eq!(deposit.deposit_start_ts, 10 * day);
+        assert_eq!(
+            deposit.time_weighted_amount_at(16 * day).unwrap(),
+            (1_000 * 5 * day + 3_000 * day) as u128
+        );
+
+        // Full withdrawal resets the position
+        deposit.update_time_weighted(0, 20 * day).unwrap();
+        deposit.deposited_amount = 0;
+        assert_eq!(deposit.deposit_start_ts, 0);
+        assert_eq!(deposit.time_weighted_amount, 0);
+    }
+
+    #[test]
+    fn untracked_positions_accrue_from_first_update_only() {
+        // Migrated position: deposited but never tracked
+        let mut deposit = BackerDeposit::from_legacy_bytes(BackerDeposit::DISCRIMINATOR).unwrap();
+        deposit.deposited_amount = 1_000;
+        assert_eq!(deposit.time_weighted_amount_at(1_000_000).unwrap(), 0);
+
+        deposit.update_time_weighted(1_000, 1_000_000).unwrap();
+        assert_eq!(deposit.time_weighted_amount, 0);
+        assert_eq!(deposit.deposit_start_ts, 1_000_000);
+        assert_eq!(deposit.time_weighted_amount_at(1_000_010).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn loyalty_multiplier_ramps_to_cap_and_is_overflow_safe() {
+        let mut deposit = BackerDeposit::from_legacy_bytes(BackerDeposit::DISCRIMINATOR).unwrap();
+        assert_eq!(deposit.loyalty_multiplier_bps(0).unwrap(), 10000);
+
+        deposit.update_time_weighted(u64::MAX, 1).unwrap();
+        deposit.deposited_amount = u64::MAX;
+        assert_eq!(deposit.loyalty_multiplier_bps(1).unwrap(), 10000);
+        // Half the ramp: half the bonus
+        let half = 1 + BackerDeposit::LOYALTY_RAMP_SECS / 2;
+        assert_eq!(deposit.loyalty_multiplier_bps(half).unwrap(), 10000 + BackerDeposit::MAX_LOYALTY_BONUS_BPS / 2);
+        // Far past the ramp: capped
+        assert_eq!(
+            deposit.loyalty_multiplier_bps(i64::MAX).unwrap(),
+            10000 + BackerDeposit::MAX_LOYALTY_BONUS_BPS
+        );
+    }
+
+    #[test]
+    fn claim_cooldown_runs_from_last_deposit() {
+        let mut deposit = BackerDeposit::from_legacy_bytes(BackerDeposit::DISCRIMINATOR).unwrap();
+        deposit.last_action_ts = 1_000;
+
+        // Default cooldown of 0 never blocks
+        assert!(!deposit.claim_cooldown_active(0, 1_000));
+        assert!(deposit.claim_cooldown_active(60, 1_059));
+        assert!(!deposit.claim_cooldown_active(60, 1_060));
+        // Overflow-safe near i64::MAX
+        deposit.last_action_ts = i64::MAX - 1;
+        assert!(deposit.claim_cooldown_active(60, i64::MAX - 1));
+    }
+
+    #[test]
+    fn legacy_layout_is_migrated_with_fields_preserved() {
+        // Original layout: backer, deposited_amount, reward_debt, pending_rewards,
+        // claimed_total, is_active, bump
+        let backer = Pubkey::new_unique();
+        let mut legacy = BackerDeposit::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(backer.as_ref());
+        legacy.extend_from_slice(&5_000_000_000u64.to_le_bytes());
+        legacy.extend_from_slice(&123_456_789u128.to_le_bytes());
+        legacy.extend_from_slice(&42u64.to_le_bytes());
+        legacy.extend_from_slice(&7u64.to_le_bytes());
+        legacy.push(1);
+        legacy.push(254);
+        assert!(legacy.len() < 8 + BackerDeposit::INIT_SPACE);
+
+        let deposit = BackerDeposit::from_legacy_bytes(&legacy).unwrap();
+        assert_eq!(deposit.backer, backer);
+        assert_eq!(deposit.deposited_amount, 5_000_000_000);
+        assert_eq!(deposit.reward_debt, 123_456_789);
+        assert_eq!(deposit.pending_rewards, 42);
+        assert_eq!(deposit.claimed_total, 7);
+        assert!(deposit.is_active);
+        assert_eq!(deposit.bump, 254);
+        assert_eq!(deposit.pro_rata_withdrawn, 0);
+        assert_eq!(deposit.haircut_total, 0);
+        assert_eq!(deposit.last_deposit_at, 0);
+        assert_eq!(deposit.locked_until, 0);
+        assert_eq!(deposit.layout_version, BackerDeposit::LAYOUT_VERSION);
+        assert_eq!(deposit.claim_authority, None);
+    }
+}
@@ -1,11 +1,21 @@
+pub mod blacklist;
 pub mod d2d_config;
 pub mod deploy_request;
 pub mod lender_stake;
+pub mod processed_fee;
+pub mod proposal;
+pub mod reward_history;
+pub mod token_treasury;
 pub mod treasury_pool;
 pub mod user_deploy_stats;
 
+pub use blacklist::*;
 pub use d2d_config::*;
 pub use deploy_request::*;
 pub use lender_stake::*;
+pub use processed_fee::*;
+pub use proposal::*;
+pub use reward_history::*;
+pub use token_treasury::*;
 pub use treasury_pool::*;
 pub use user_deploy_stats::*;
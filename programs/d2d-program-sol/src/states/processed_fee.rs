@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Marker that a fee credit has been applied
+///
+/// - Created by credit_fee_to_pool on first use of a fee_id (fee_payer pays the rent)
+/// - A retry with the same fee_id finds it already stamped and fails with DuplicateFee,
+///   so backend retries after a timeout cannot double-credit reward_per_share
+#[account]
+#[derive(InitSpace)]
+pub struct ProcessedFee {
+    pub fee_id: [u8; 32],   // Backend-assigned id of the fee payment
+    pub fee_reward: u64,    // Reward fee credited (lamports)
+    pub fee_platform: u64,  // Platform fee credited (lamports)
+    pub processed_at: i64,  // When the fee was credited (0 = not yet processed)
+    pub bump: u8,           // PDA bump
+}
+
+impl ProcessedFee {
+    pub const PREFIX_SEED: &'static [u8] = b"processed_fee";
+}
@@ -0,0 +1,66 @@
+use crate::errors::ErrorCode;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// Pool parameter a governance proposal can change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProposalParam {
+    RewardFeeBps,   // TreasuryPool.reward_fee_bps
+    PlatformFeeBps, // TreasuryPool.platform_fee_bps
+}
+
+/// Deposit-weighted governance proposal
+///
+/// Voting weight = backer's deposited_amount, only for deposits made before created_at.
+/// Passes when votes_for + votes_against >= quorum of total_deposited_snapshot
+/// and votes_for > votes_against, after voting_ends_at.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub proposal_id: u64,                // Sequential id (TreasuryPool.proposal_count)
+    pub proposer: Pubkey,                // Creator of the proposal
+    pub param: ProposalParam,            // Parameter to change
+    pub new_value: u64,                  // Value applied on execution
+    pub votes_for: u64,                  // Deposit-weighted support (lamports)
+    pub votes_against: u64,              // Deposit-weighted opposition (lamports)
+    pub total_deposited_snapshot: u64,   // TreasuryPool.total_deposited at creation (quorum base)
+    pub created_at: i64,                 // Creation timestamp (vote snapshot)
+    pub voting_ends_at: i64,             // Voting deadline
+    pub executed: bool,                  // Applied to the pool
+    pub bump: u8,                        // PDA bump
+}
+
+impl Proposal {
+    pub const PREFIX_SEED: &'static [u8] = b"proposal";
+    pub const VOTING_PERIOD: i64 = 3 * 24 * 60 * 60; // 3 days
+    pub const QUORUM_BPS: u64 = 2000;                 // 20% of deposits must vote
+    pub const MAX_FEE_BPS: u64 = TreasuryPool::MAX_FEE_BPS; // 10% upper bound for fee proposals
+
+    /// Quorum reached and majority in favour
+    pub fn has_passed(&self) -> Result<bool> {
+        let turnout = self.votes_for
+            .checked_add(self.votes_against)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let quorum = (self.total_deposited_snapshot as u128)
+            .checked_mul(Self::QUORUM_BPS as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::CalculationOverflow)? as u64;
+        Ok(turnout > 0 && turnout >= quorum && self.votes_for > self.votes_against)
+    }
+}
+
+/// One vote per backer per proposal (PDA existence prevents double voting)
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey, // Proposal voted on
+    pub voter: Pubkey,    // Backer who voted
+    pub weight: u64,      // deposited_amount at vote time
+    pub support: bool,    // true = for, false = against
+    pub bump: u8,         // PDA bump
+}
+
+impl VoteRecord {
+    pub const PREFIX_SEED: &'static [u8] = b"vote_record";
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+/// One reward_per_share observation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RewardSnapshot {
+    pub timestamp: i64,         // When the snapshot was taken
+    pub reward_per_share: u128, // Treasury Pool accumulator at that time (scaled by PRECISION)
+    pub total_deposited: u64,   // Total backer deposits at that time (lamports)
+}
+
+/// Bounded history of reward_per_share for period-over-period yield
+///
+/// - Optional: created by initialize_reward_history, recorded by credit_fee_to_pool when passed
+/// - Ring buffer of CAPACITY entries; once full the oldest entry is overwritten
+#[account]
+#[derive(InitSpace)]
+pub struct RewardHistory {
+    pub entries: [RewardSnapshot; 32], // Ring buffer (RewardHistory::CAPACITY)
+    pub next_index: u8,                // Slot the next snapshot is written to
+    pub len: u8,                       // Populated entries (saturates at CAPACITY)
+    pub bump: u8,                      // PDA bump
+}
+
+impl RewardHistory {
+    pub const PREFIX_SEED: &'static [u8] = b"reward_history";
+    pub const CAPACITY: usize = 32;
+
+    /// Append a snapshot, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, timestamp: i64, reward_per_share: u128, total_deposited: u64) {
+        self.entries[self.next_index as usize] = RewardSnapshot {
+            timestamp,
+            reward_per_share,
+            total_deposited,
+        };
+        self.next_index = ((self.next_index as usize + 1) % Self::CAPACITY) as u8;
+        if (self.len as usize) < Self::CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Most recent snapshot, if any
+    pub fn latest(&self) -> Option<&RewardSnapshot> {
+        if self.len == 0 {
+            return None;
+        }
+        let index = (self.next_index as usize + Self::CAPACITY - 1) % Self::CAPACITY;
+        Some(&self.entries[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_history() -> RewardHistory {
+        RewardHistory {
+            entries: [RewardSnapshot::default(); RewardHistory::CAPACITY],
+            next_index: 0,
+            len: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn record_overwrites_oldest_once_full() {
+        let mut history = empty_history();
+        assert!(history.latest().is_none());
+
+        for i in 0..(RewardHistory::CAPACITY as i64 + 3) {
+            history.record(i, i as u128 * 10, 1_000);
+        }
+
+        assert_eq!(history.len as usize, RewardHistory::CAPACITY);
+        assert_eq!(history.next_index, 3);
+        assert_eq!(history.latest().unwrap().timestamp, RewardHistory::CAPACITY as i64 + 2);
+        // Slots 0..3 hold the three newest entries, slot 3 the oldest survivor
+        assert_eq!(history.entries[0].timestamp, RewardHistory::CAPACITY as i64);
+        assert_eq!(history.entries[3].timestamp, 3);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::errors::ErrorCode;
+use crate::states::TreasuryPool;
+use anchor_lang::prelude::*;
+
+/// SPL token deposit pool, one per mint
+///
+/// - Deposits live in `vault`, the associated token account of the Treasury Pool PDA for `mint`
+/// - Backer positions are BackerDeposit accounts under STAKE_SEED (same reward-per-share math as SOL)
+/// - Rewards are paid in the same mint from reward_pool_balance (also held in the vault)
+#[account]
+#[derive(InitSpace)]
+pub struct TokenTreasury {
+    pub mint: Pubkey,              // Accepted deposit mint (e.g. USDC)
+    pub vault: Pubkey,             // Treasury Pool ATA holding deposits and rewards
+    pub decimals: u8,              // Mint decimals (transfer_checked)
+    pub total_deposited: u64,      // Total tokens deposited by backers (base units)
+    pub reward_per_share: u128,    // Accumulated rewards per deposited token (scaled by PRECISION)
+    pub reward_pool_balance: u64,  // Reward tokens in the vault owed to backers (base units)
+    pub bump: u8,                  // PDA bump
+}
+
+impl TokenTreasury {
+    pub const PREFIX_SEED: &'static [u8] = b"token_treasury";
+    pub const STAKE_SEED: &'static [u8] = b"token_stake";
+
+    /// Credit reward tokens to backers and update reward_per_share
+    /// Requires deposits, otherwise the rewards could never be claimed
+    pub fn credit_rewards(&mut self, amount: u64) -> Result<()> {
+        require!(self.total_deposited > 0, ErrorCode::NoTokenDeposits);
+
+        self.reward_pool_balance = self
+            .reward_pool_balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        // delta = amount * PRECISION / total_deposited
+        let delta = (amount as u128)
+            .checked_mul(TreasuryPool::PRECISION)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(self.total_deposited as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.reward_per_share = self
+            .reward_per_share
+            .checked_add(delta)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Debit reward tokens (when rewards are claimed)
+    pub fn debit_reward_pool(&mut self, amount: u64) -> Result<()> {
+        self.reward_pool_balance = self
+            .reward_pool_balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientTreasuryFunds)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::BackerDeposit;
+
+    fn token_treasury(total_deposited: u64) -> TokenTreasury {
+        TokenTreasury {
+            mint: Pubkey::default(),
+            vault: Pubkey::default(),
+            decimals: 6,
+            total_deposited,
+            reward_per_share: 0,
+            reward_pool_balance: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn rewards_are_shared_pro_rata_with_backer_deposit_math() {
+        let mut treasury = token_treasury(4_000_000);
+        treasury.credit_rewards(1_000_000).unwrap();
+
+        let mut position = BackerDeposit {
+            backer: Pubkey::default(),
+            deposited_amount: 1_000_000,
+            reward_debt: 0,
+            pending_rewards: 0,
+            claimed_total: 0,
+            is_active: true,
+            bump: 0,
+            pro_rata_withdrawn: 0,
+            haircut_total: 0,
+            last_deposit_at: 0,
+            locked_until: 0,
+            layout_version: BackerDeposit::LAYOUT_VERSION,
+            claim_authority: None,
+            receipt_mint: None,
+            deposit_start_ts: 0,
+            time_weighted_amount: 0,
+            time_weighted_updated_at: 0,
+            last_action_ts: 0,
+        };
+        // A quarter of the deposits earns a quarter of the rewards
+        assert_eq!(position.calculate_claimable_rewards(treasury.reward_per_share).unwrap(), 250_000);
+
+        position.update_reward_debt(treasury.reward_per_share).unwrap();
+        assert_eq!(position.calculate_claimable_rewards(treasury.reward_per_share).unwrap(), 0);
+
+        treasury.debit_reward_pool(250_000).unwrap();
+        assert_eq!(treasury.reward_pool_balance, 750_000);
+        assert!(treasury.debit_reward_pool(750_001).is_err());
+    }
+
+    #[test]
+    fn rewards_without_deposits_are_rejected() {
+        let mut treasury = token_treasury(0);
+        let err = treasury.credit_rewards(1).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::NoTokenDeposits));
+    }
+}
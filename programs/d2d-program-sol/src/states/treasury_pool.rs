@@ -1,4 +1,5 @@
 use crate::errors::ErrorCode;
+use crate::states::DeployRequest;
 use anchor_lang::prelude::*;
 
 /// Fee-Based Treasury System with Reward-Per-Share Model
@@ -7,6 +8,12 @@ use anchor_lang::prelude::*;
 /// - reward_per_share: Accumulator that increases when fees are credited
 /// - Each backer tracks reward_debt = deposited_amount * reward_per_share at deposit time
 /// - Claimable = (deposited_amount * reward_per_share - reward_debt) / PRECISION
+///
+/// Account layout:
+/// [8-byte discriminator][TreasuryPool fields (INIT_SPACE)][reserved padding (zeroed)]
+/// New fields are appended at the end of the struct. While they fit in the reserved
+/// padding they deserialize in place from zero bytes (0 / false / None), so no
+/// realloc or close/reinitialize is needed.
 #[account]
 #[derive(InitSpace)]
 pub struct TreasuryPool {
@@ -46,20 +53,146 @@ pub struct TreasuryPool {
     pub total_fees_collected: u64,         // DEPRECATED
     pub current_apy: u64,                  // DEPRECATED
     pub treasury_wallet: Pubkey,           // DEPRECATED
+    
+    // Solvency tracking
+    pub total_borrowed: u64,                // Deployment funds dispatched to ephemeral keys, not yet confirmed
+    pub undercollateralized: bool,          // Set when assets cannot cover deposits + rewards
+    
+    // Governance
+    pub proposal_count: u64,                // Governance proposals created (next proposal_id)
+    
+    // Admin transfer
+    pub pending_admin: Option<Pubkey>,      // Proposed admin awaiting accept_admin (two-step transfer)
+    
+    // Key management
+    pub reward_admin: Pubkey,               // Reward pool withdraw authority (set_reward_admin rotates it)
+    pub recovery_authority: Option<Pubkey>, // Recovery multisig allowed to rotate keys in an incident
+    
+    // Deposit limits
+    pub max_deposit_per_backer: u64,        // Max deposited_amount per backer (0 = unlimited)
+    pub min_deposit: u64,                   // Minimum stake_sol deposit (lamports)
+    
+    // Fee velocity (estimates only)
+    pub fee_window_start: i64,              // Start of the current fee-velocity window
+    pub fees_credited_current_window: u64,  // Reward fees credited in the current window
+    pub fees_credited_last_window: u64,     // Reward fees credited in the last completed window
+    pub last_window_seconds: i64,           // Duration of the last completed window
+    
+    // Lock-period staking
+    pub penalty_bps: u64,                   // Early-unstake penalty for locked stakes (basis points)
+    
+    // Deployment halt
+    pub deployments_paused: bool,           // New deployments halted (developers may refund_on_halt)
+    
+    // Reward pool isolation
+    pub reward_pool_isolated: bool,         // Deployments may never request Reward Pool funding
+    
+    // Monthly fee accrual
+    pub prepaid_fees_balance: u64,          // Prepaid subscription fees escrowed in Reward Pool (owed to developers until accrued)
+    
+    // Insurance backstop
+    pub emergency_withdraw_fee_bps: u64,    // Fee on emergency_unstake exits (basis points, routed to the insurance pool)
+    pub insurance_pool_balance: u64,        // Emergency withdrawal fees held in the Insurance Pool PDA
+    
+    // Per-operation pause
+    pub paused_ops: u8,                     // Per-operation pause bits (PAUSE_*); all bits set <=> emergency_pause
+    
+    // Claim limits
+    pub min_claim_amount: u64,              // Minimum claim_rewards amount (lamports, 0 = any non-zero claim)
+    
+    // Deploy session limits
+    pub max_concurrent_sessions: u32,       // Max concurrent deploy sessions per developer (UserDeployStats.active_sessions cap)
+    pub max_daily_deploys: u32,             // Max deploy requests per developer per day (UserDeployStats.daily_deploys cap)
+    
+    // Subscription grace period
+    pub grace_period_secs: i64,             // Seconds after subscription_paid_until before a program is Suspended
+    
+    // Stuck deployment recovery
+    pub ephemeral_recovery_timeout_secs: i64, // Seconds after created_at before force_recover_ephemeral may sweep a hung deployment
+    
+    // Recovery split
+    pub recovery_platform_bps: u64,         // Share of recovered deployment funds routed to the Platform Pool (basis points)
+    
+    // Pool cap
+    pub max_total_deposited: u64,           // Max total_deposited across all backers (0 = unlimited)
+    
+    // Failure slashing
+    pub failure_slash_threshold: u32,       // Failed deployments tolerated before refunds are slashed
+    pub failure_slash_bps: u64,             // Share of the failure refund kept by the Platform Pool (basis points, 0 = off)
+    
+    // Deployment escrow
+    pub escrowed_amount: u64,               // Deployment costs reserved by pending requests, not yet funded (lamports)
+    
+    // Reward dust
+    pub undistributed_dust: u64,            // Reward fee lost to reward_per_share truncation, held in the Reward Pool PDA (lamports)
+    
+    // Reward statistics
+    pub total_rewards_claimed: u64,         // Rewards ever paid out to backers (claims + compounds, lamports)
+    
+    // Subscription lapse
+    pub max_lapse_secs: i64,                // Seconds after subscription_paid_until a Suspended request may still be reinstated by pay_subscription (0 = never)
+    
+    // Deployment losses
+    pub total_deployment_losses: u64,       // deployment_cost not recovered on confirm_deployment_success (lamports, informational)
+    
+    // Layout
+    pub layout_version: u8,                 // Account layout version (see LAYOUT_VERSION, 0 = never migrated)
+    
+    // Monthly fee
+    pub monthly_fee_bps: u64,               // Monthly fee as bps of deployment_cost (0 = use the explicit monthly_fee argument)
+    
+    // Unallocated rewards
+    pub unallocated_rewards: u64,           // Reward fees credited while total_deposited was 0, held in reward_pool_balance for the next first depositor (lamports)
+    
+    // Per-request fee split bounds
+    pub min_reward_split_bps: u64,          // Lower bound for a per-request reward_split_bps override (basis points of the up-front fee)
+    pub max_reward_split_bps: u64,          // Upper bound for a per-request reward_split_bps override (basis points of the up-front fee)
+    
+    // Claim cooldown
+    pub claim_cooldown_secs: i64,           // Seconds after a stake_sol deposit before that backer may claim (0 = no cooldown)
+    
+    // Reward accounting
+    pub total_claimable: u64,               // Rewards credited through reward_per_share and not yet paid out (admin_withdraw_reward_pool floor)
+    
+    // Deployment cost ceiling
+    pub max_deployment_cost: u64,           // Largest deployment_cost a single request may commit (lamports, 0 = no cap)
+    pub max_deployment_bps: u64,            // Largest deployment_cost as bps of liquid_balance at request time (0 = no cap)
+    
+    // Reward outflow circuit breaker
+    pub max_outflow_per_window: u64,        // Reward Pool outflow (claims + admin withdrawals) per window that trips emergency_pause (lamports, 0 = disabled)
+    pub outflow_window_secs: i64,           // Length of the outflow window (seconds, 0 = disabled)
+    pub outflow_window_start: i64,          // Start of the current outflow window
+    pub outflow_in_window: u64,             // Reward Pool outflow recorded in the current window (lamports)
+}
+
+/// Up-front payment for a deploy request (see TreasuryPool::deploy_payment)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeployPayment {
+    pub monthly_fee_total: u64,   // monthly_fee * initial_months, escrowed in the Reward Pool
+    pub reward_share: u64,        // Part of service_fee + platform fee credited to backers
+    pub platform_fee_amount: u64, // Part of service_fee + platform fee sent to the Platform Pool
+    pub reward_split_bps: u64,    // Effective split between the two (after clamping)
+    pub reward_fee_amount: u64,   // monthly_fee_total + reward_share -> Reward Pool PDA
+    pub total_payment: u64,       // reward_fee_amount + platform_fee_amount
 }
 
 impl TreasuryPool {
     pub const PREFIX_SEED: &'static [u8] = b"treasury_pool";
     pub const REWARD_POOL_SEED: &'static [u8] = b"reward_pool";
     pub const PLATFORM_POOL_SEED: &'static [u8] = b"platform_pool";
+    pub const INSURANCE_POOL_SEED: &'static [u8] = b"insurance_pool";
     
     // Legacy constants for backward compatibility
     pub const ADMIN_POOL_SEED: &'static [u8] = b"platform_pool"; // Maps to platform_pool
     pub const MAX_FEE_AMOUNT: u128 = 1_000_000_000 * 1_000_000_000; // Legacy alias
     
-    // Fee rates (fixed)
+    // Default fee rates (copied into reward_fee_bps / platform_fee_bps at init)
     pub const REWARD_FEE_BPS: u64 = 100;      // 1% = 100 basis points
     pub const PLATFORM_FEE_BPS: u64 = 10;     // 0.1% = 10 basis points
+    pub const MAX_FEE_BPS: u64 = 1000;        // 10% upper bound for either rate
+    
+    // Initial reward_admin; also the fallback for pools created before reward_admin was stored
+    pub const DEFAULT_REWARD_ADMIN: Pubkey = anchor_lang::solana_program::pubkey!("A1dVA8adW1XXgcVmLCtbrvbVEVA1n3Q7kNPaTZVonjpq");
     
     // Precision for reward_per_share (1e12)
     pub const PRECISION: u128 = 1_000_000_000_000;
@@ -67,20 +200,272 @@ impl TreasuryPool {
     // Maximum reasonable amount: 1 billion SOL
     pub const MAX_AMOUNT: u128 = 1_000_000_000 * 1_000_000_000;
 
-    /// Calculate reward fee (1% of deposit)
-    pub fn calculate_reward_fee(deposit_amount: u64) -> Result<u64> {
+    // Ceiling for reward_per_share: a MAX_AMOUNT position's reward_debt
+    // (deposited_amount * reward_per_share) must still fit in u128.
+    // reward_per_share is never rescaled downward - that would need a matching
+    // reward_debt adjustment in every BackerDeposit account at once - so growth past
+    // the ceiling is refused instead (~3.4e8 lamports of rewards per deposited lamport).
+    pub const MAX_REWARD_PER_SHARE: u128 = u128::MAX / Self::MAX_AMOUNT;
+
+    // Default minimum deposit: 0.01 SOL (avoids dust BackerDeposit accounts)
+    pub const DEFAULT_MIN_DEPOSIT: u64 = 10_000_000;
+
+    // Early-unstake penalty default: 5% (routed to the reward pool)
+    pub const DEFAULT_PENALTY_BPS: u64 = 500;
+    pub const MAX_PENALTY_BPS: u64 = 5000;
+
+    // Emergency withdrawal fee cap: 20% (default 0 = free emergency exits)
+    pub const MAX_EMERGENCY_WITHDRAW_FEE_BPS: u64 = 2000;
+
+    // Maximum lock period: 10 years
+    pub const MAX_LOCK_PERIOD: i64 = 10 * 365 * 24 * 60 * 60;
+
+    // Fee-velocity window for reward projections: 7 days
+    pub const FEE_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+    // paused_ops bits (PAUSE_ALL is what emergency_pause sets)
+    pub const PAUSE_DEPOSIT: u8 = 1 << 0;
+    pub const PAUSE_WITHDRAW: u8 = 1 << 1;
+    pub const PAUSE_CLAIM: u8 = 1 << 2;
+    pub const PAUSE_DEPLOY: u8 = 1 << 3;
+    pub const PAUSE_PAY_SUBSCRIPTION: u8 = 1 << 4;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_DEPOSIT
+        | Self::PAUSE_WITHDRAW
+        | Self::PAUSE_CLAIM
+        | Self::PAUSE_DEPLOY
+        | Self::PAUSE_PAY_SUBSCRIPTION;
+
+    // Default per-developer deploy limits (set_deploy_limits)
+    pub const DEFAULT_MAX_CONCURRENT_SESSIONS: u32 = 10;
+    pub const DEFAULT_MAX_DAILY_DEPLOYS: u32 = 20;
+
+    // pay_subscription prepay discounts as (minimum months, discount bps), longest commitment first
+    pub const SUBSCRIPTION_DISCOUNT_TIERS: [(u32, u64); 2] = [(12, 1000), (6, 500)];
+    pub const MAX_SUBSCRIPTION_MONTHS: u32 = 36;
+
+    // Grace period after expiry before suspension: 7 days default, 90 days max
+    pub const DEFAULT_GRACE_PERIOD_SECS: i64 = 7 * 24 * 60 * 60;
+    pub const MAX_GRACE_PERIOD_SECS: i64 = 90 * 24 * 60 * 60;
+
+    // Reinstatement window for Suspended requests: 0 default (redeploy required), 365 days max
+    pub const MAX_LAPSE_SECS: i64 = 365 * 24 * 60 * 60;
+
+    // Claim cooldown after a deposit: 0 default (off), 7 days max
+    pub const MAX_CLAIM_COOLDOWN_SECS: i64 = 7 * 24 * 60 * 60;
+
+    // force_recover_ephemeral timeout: 24 hours default
+    pub const DEFAULT_EPHEMERAL_RECOVERY_TIMEOUT_SECS: i64 = 24 * 60 * 60;
+
+    // Platform cut of recovered deployment funds: 0 default, 50% max
+    pub const MAX_RECOVERY_PLATFORM_BPS: u64 = 5000;
+
+    // Refund cut for developers with repeated failed deployments: 0 default, 50% max
+    pub const MAX_FAILURE_SLASH_BPS: u64 = 5000;
+
+    // Longest free-text reason logged in events (bytes)
+    pub const MAX_REASON_LEN: usize = 256;
+
+    // Reserved padding after the struct for future fields (bytes)
+    pub const MAX_RESERVED_PADDING: u32 = 1024;
+
+    // Current account layout, written by initialize / migrate_treasury_pool
+    // 2: monthly_fee_bps, unallocated_rewards
+    // 3: min_reward_split_bps, max_reward_split_bps
+    // 4: claim_cooldown_secs
+    // 5: total_claimable
+    // 6: max_deployment_cost, max_deployment_bps
+    // 7: reward outflow circuit breaker
+    pub const LAYOUT_VERSION: u8 = 7;
+
+    /// Account size including discriminator and reserved padding
+    pub fn space_with_padding(reserved_padding: u32) -> usize {
+        8 + Self::INIT_SPACE + reserved_padding as usize
+    }
+
+    /// Loaded pool is at the layout this code expects (older pools need migrate_treasury_pool)
+    pub fn is_current_layout(&self) -> bool {
+        self.layout_version >= Self::LAYOUT_VERSION
+    }
+
+    /// Decode an account written with an older (shorter) layout
+    /// Fields are append-only, so missing trailing bytes are zero-filled and decode as 0/false
+    pub fn from_legacy_bytes(data: &[u8]) -> Result<Self> {
+        let mut buf = data.to_vec();
+        if buf.len() < 8 + Self::INIT_SPACE {
+            buf.resize(8 + Self::INIT_SPACE, 0);
+        }
+        Self::try_deserialize(&mut &buf[..])
+    }
+
+    /// Monthly subscription fee for a deployment
+    /// monthly_fee_bps > 0: deployment_cost (what the request will borrow) * monthly_fee_bps / 10000
+    /// monthly_fee_bps == 0: the caller's explicit monthly_fee (legacy path)
+    pub fn monthly_fee_for(monthly_fee_bps: u64, deployment_cost: u64, explicit_monthly_fee: u64) -> Result<u64> {
+        if monthly_fee_bps == 0 {
+            return Ok(explicit_monthly_fee);
+        }
+        let fee = (deployment_cost as u128)
+            .checked_mul(monthly_fee_bps as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / 10000;
+        u64::try_from(fee).map_err(|_| error!(ErrorCode::CalculationOverflow))
+    }
+
+    /// Whether any of the given operations is paused
+    /// emergency_pause pauses everything (pools paused before paused_ops existed keep working)
+    pub fn is_paused(&self, ops: u8) -> bool {
+        self.emergency_pause || self.paused_ops & ops != 0
+    }
+
+    /// Discount for prepaying `months` of subscription (basis points, 0 below the smallest tier)
+    pub fn subscription_discount_bps(months: u32) -> u64 {
+        Self::SUBSCRIPTION_DISCOUNT_TIERS
+            .iter()
+            .find(|(min_months, _)| months >= *min_months)
+            .map_or(0, |(_, discount_bps)| *discount_bps)
+    }
+
+    /// Subscription payment for `months`: monthly_fee * months less the tier discount
+    pub fn subscription_payment(monthly_fee: u64, months: u32) -> Result<u64> {
+        let gross = (monthly_fee as u128)
+            .checked_mul(months as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let discount_bps = Self::subscription_discount_bps(months) as u128;
+        let net = gross
+            .checked_mul(10000 - discount_bps)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / 10000;
+        u64::try_from(net).map_err(|_| error!(ErrorCode::CalculationOverflow))
+    }
+
+    /// reward_per_share + delta, refused with CalculationOverflow past MAX_REWARD_PER_SHARE
+    pub fn next_reward_per_share(reward_per_share: u128, delta: u128) -> Result<u128> {
+        let next = reward_per_share
+            .checked_add(delta)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        require!(next <= Self::MAX_REWARD_PER_SHARE, ErrorCode::CalculationOverflow);
+        Ok(next)
+    }
+
+    /// Split a reward fee into (reward_per_share delta, lamports distributed, truncation dust)
+    /// delta = fee_reward * PRECISION / total_deposited; distributed = delta * total_deposited / PRECISION
+    pub fn split_reward_dust(fee_reward: u64, total_deposited: u64) -> Result<(u128, u64, u64)> {
+        let delta = (fee_reward as u128)
+            .checked_mul(Self::PRECISION)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(total_deposited as u128)
+            .ok_or(ErrorCode::DivisionByZero)?;
+        let distributed = delta
+            .checked_mul(total_deposited as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / Self::PRECISION;
+        let distributed = u64::try_from(distributed).map_err(|_| error!(ErrorCode::CalculationOverflow))?;
+        let dust = fee_reward
+            .checked_sub(distributed)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok((delta, distributed, dust))
+    }
+
+    /// Split a deploy request's up-front fee (service_fee + platform fee) into (Reward Pool, Platform Pool, effective bps)
+    /// reward_split_bps: None keeps the default split (service_fee to rewards, platform_fee to the
+    /// Platform Pool); Some(bps) sends bps of the total to rewards, clamped to min/max_reward_split_bps.
+    pub fn split_deploy_fees(&self, service_fee: u64, platform_fee: u64, reward_split_bps: Option<u64>) -> Result<(u64, u64, u64)> {
+        let total = service_fee
+            .checked_add(platform_fee)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let Some(requested_bps) = reward_split_bps else {
+            let default_bps = if total == 0 { 10000 } else { (service_fee as u128 * 10000 / total as u128) as u64 };
+            return Ok((service_fee, platform_fee, default_bps));
+        };
+        let split_bps = requested_bps.clamp(self.min_reward_split_bps, self.max_reward_split_bps.max(self.min_reward_split_bps));
+        let to_reward = (total as u128)
+            .checked_mul(split_bps as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / 10000;
+        let to_reward = u64::try_from(to_reward).map_err(|_| error!(ErrorCode::CalculationOverflow))?;
+        let to_platform = total
+            .checked_sub(to_reward)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok((to_reward, to_platform, split_bps))
+    }
+
+    /// Fee breakdown the developer pays before create_deploy_request
+    /// - monthlyFee * initial_months + reward share of the up-front fee -> Reward Pool
+    /// - platform share of the up-front fee -> Platform Pool
+    ///
+    /// The up-front fee is service_fee + 0.1% of deployment_cost, split by split_deploy_fees.
+    pub fn deploy_payment(
+        &self,
+        service_fee: u64,
+        monthly_fee: u64,
+        initial_months: u32,
+        deployment_cost: u64,
+        reward_split_bps: Option<u64>,
+    ) -> Result<DeployPayment> {
+        let monthly_fee_total = monthly_fee
+            .checked_mul(initial_months as u64)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let deployment_platform_fee = deployment_cost
+            .checked_div(1000)
+            .ok_or(ErrorCode::CalculationOverflow)?; // 0.1% of deployment_cost
+        let (reward_share, platform_fee_amount, reward_split_bps) =
+            self.split_deploy_fees(service_fee, deployment_platform_fee, reward_split_bps)?;
+        let reward_fee_amount = monthly_fee_total
+            .checked_add(reward_share)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let total_payment = reward_fee_amount
+            .checked_add(platform_fee_amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(DeployPayment {
+            monthly_fee_total,
+            reward_share,
+            platform_fee_amount,
+            reward_split_bps,
+            reward_fee_amount,
+            total_payment,
+        })
+    }
+
+    /// Split recovered deployment funds into (liquid_balance share, Platform Pool share)
+    pub fn split_recovered(recovered: u64, recovery_platform_bps: u64) -> Result<(u64, u64)> {
+        let to_platform = (recovered as u128)
+            .checked_mul(recovery_platform_bps as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / 10000;
+        let to_platform = u64::try_from(to_platform).map_err(|_| error!(ErrorCode::CalculationOverflow))?;
+        let to_liquid = recovered
+            .checked_sub(to_platform)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok((to_liquid, to_platform))
+    }
+
+    /// Portion of a failure refund retained for the Platform Pool
+    /// Applies only once the developer's failed_deploys exceeds failure_slash_threshold
+    pub fn failure_slash(refund: u64, failed_deploys: u32, threshold: u32, slash_bps: u64) -> Result<u64> {
+        if slash_bps == 0 || failed_deploys <= threshold {
+            return Ok(0);
+        }
+        let slashed = (refund as u128)
+            .checked_mul(slash_bps as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            / 10000;
+        u64::try_from(slashed).map_err(|_| error!(ErrorCode::CalculationOverflow))
+    }
+
+    /// Calculate reward fee (reward_fee_bps of deposit, 1% by default)
+    pub fn calculate_reward_fee(&self, deposit_amount: u64) -> Result<u64> {
         let fee = (deposit_amount as u128)
-            .checked_mul(Self::REWARD_FEE_BPS as u128)
+            .checked_mul(self.reward_fee_bps as u128)
             .ok_or(ErrorCode::CalculationOverflow)?
             .checked_div(10000)
             .ok_or(ErrorCode::CalculationOverflow)?;
         Ok(fee as u64)
     }
 
-    /// Calculate platform fee (0.1% of deposit)
-    pub fn calculate_platform_fee(deposit_amount: u64) -> Result<u64> {
+    /// Calculate platform fee (platform_fee_bps of deposit, 0.1% by default)
+    pub fn calculate_platform_fee(&self, deposit_amount: u64) -> Result<u64> {
         let fee = (deposit_amount as u128)
-            .checked_mul(Self::PLATFORM_FEE_BPS as u128)
+            .checked_mul(self.platform_fee_bps as u128)
             .ok_or(ErrorCode::CalculationOverflow)?
             .checked_div(10000)
             .ok_or(ErrorCode::CalculationOverflow)?;
@@ -98,31 +483,303 @@ impl TreasuryPool {
             .platform_pool_balance
             .checked_add(fee_platform)
             .ok_or_else(|| ErrorCode::CalculationOverflow)?;
-        
+
+        self.credit_reward_over(fee_reward, self.total_deposited)
+    }
+
+    /// Credit a reward fee to every backer except `excluded` lamports of deposits
+    /// Used for an early-unstake penalty: the unstaker's remaining deposit must not share in
+    /// their own penalty, since their reward_debt is reset to the new reward_per_share right after.
+    pub fn credit_fee_to_pool_excluding(&mut self, fee_reward: u64, excluded: u64) -> Result<()> {
+        require!(fee_reward <= Self::MAX_AMOUNT as u64, ErrorCode::FeeAmountTooLarge);
+        let eligible_deposits = self
+            .total_deposited
+            .checked_sub(excluded)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.credit_reward_over(fee_reward, eligible_deposits)
+    }
+
+    /// Credit fee_reward to the Reward Pool over eligible_deposits of total_deposited
+    fn credit_reward_over(&mut self, fee_reward: u64, eligible_deposits: u64) -> Result<()> {
+        // Update reward_per_share if there are deposits
+        // Only the part backers can actually claim is credited; the truncated remainder is dust
+        let mut credited = fee_reward;
+        if eligible_deposits > 0 {
+            let (delta, distributed, dust) = Self::split_reward_dust(fee_reward, eligible_deposits)?;
+            self.reward_per_share = Self::next_reward_per_share(self.reward_per_share, delta)?;
+            self.undistributed_dust = self
+                .undistributed_dust
+                .checked_add(dust)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+            self.credit_claimable(distributed)?;
+            credited = distributed;
+        } else {
+            // No one to attribute it to yet: held for the next first depositor
+            self.unallocated_rewards = self
+                .unallocated_rewards
+                .checked_add(fee_reward)
+                .ok_or(ErrorCode::CalculationOverflow)?;
+        }
+
         // Credit reward pool
         self.reward_pool_balance = self
             .reward_pool_balance
-            .checked_add(fee_reward)
-            .ok_or_else(|| ErrorCode::CalculationOverflow)?;
+            .checked_add(credited)
+            .ok_or(ErrorCode::CalculationOverflow)?;
         
-        // Update reward_per_share if there are deposits
-        if self.total_deposited > 0 {
-            // delta = fee_reward * PRECISION / total_deposited
-            let delta = (fee_reward as u128)
-                .checked_mul(Self::PRECISION)
+        Ok(())
+    }
+
+    /// Hand fees credited while the pool was empty to the first depositor of deposit_amount
+    /// Only unallocated_rewards is distributed: the rest of reward_pool_balance is still owed
+    /// (as pending_rewards / reward_debt) to backers who already left, so it must not be re-credited.
+    /// Returns the lamports distributed
+    pub fn allocate_unallocated_rewards(&mut self, deposit_amount: u64) -> Result<u64> {
+        if self.total_deposited > 0 || deposit_amount == 0 {
+            return Ok(0);
+        }
+        // Admin withdrawals may have taken reward_pool_balance below what was held back
+        let unallocated = self.unallocated_rewards.min(self.reward_pool_balance);
+        self.unallocated_rewards = 0;
+        if unallocated == 0 {
+            return Ok(0);
+        }
+
+        let (delta, distributed, dust) = Self::split_reward_dust(unallocated, deposit_amount)?;
+        self.reward_per_share = Self::next_reward_per_share(self.reward_per_share, delta)?;
+        self.reward_pool_balance = self
+            .reward_pool_balance
+            .checked_sub(dust)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.undistributed_dust = self
+            .undistributed_dust
+            .checked_add(dust)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.credit_claimable(distributed)?;
+        Ok(distributed)
+    }
+
+    /// Count rewards just attributed to backers through reward_per_share as owed
+    pub fn credit_claimable(&mut self, amount: u64) -> Result<()> {
+        self.total_claimable = self
+            .total_claimable
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Part of reward_pool_balance not owed to backers (what admin_withdraw_reward_pool may take)
+    pub fn excess_rewards(&self) -> u64 {
+        self.reward_pool_balance.saturating_sub(self.total_claimable)
+    }
+
+    /// Backers still have principal or rewards in the pool (close_treasury_pool refuses without force)
+    pub fn holds_backer_funds(&self) -> bool {
+        self.total_deposited > 0 || self.reward_pool_balance > 0
+    }
+
+    /// Hold prepaid subscription fees (already in the Reward Pool PDA) for a deploy request
+    /// Escrowed fees are owed to the developer until accrued to backers
+    pub fn escrow_prepaid_fees(&mut self, deploy_request: &mut DeployRequest, amount: u64) -> Result<()> {
+        deploy_request.prepaid_balance = deploy_request
+            .prepaid_balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.prepaid_fees_balance = self
+            .prepaid_fees_balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Move escrowed fees to backers (reward_per_share)
+    pub fn accrue_prepaid_fees(
+        &mut self,
+        deploy_request: &mut DeployRequest,
+        amount: u64,
+        current_time: i64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        self.release_prepaid_fees(deploy_request, amount)?;
+        self.credit_fee_to_pool(amount, 0)?;
+        self.record_fee_credit(amount, current_time)
+    }
+
+    /// Split a developer refund between escrowed fees and already-credited rewards
+    /// The monthly portion comes from escrow first; the rest is debited from reward_pool_balance,
+    /// but only up to excess_rewards(): a fee already attributed to backers through
+    /// reward_per_share is owed to them and stays in the pool
+    /// Returns the refund actually paid (at most service_refund + monthly_refund)
+    pub fn refund_fees(
+        &mut self,
+        deploy_request: &mut DeployRequest,
+        service_refund: u64,
+        monthly_refund: u64,
+    ) -> Result<u64> {
+        let from_escrow = monthly_refund.min(deploy_request.prepaid_balance);
+        self.release_prepaid_fees(deploy_request, from_escrow)?;
+
+        let from_rewards = service_refund
+            .checked_add(monthly_refund - from_escrow)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .min(self.excess_rewards());
+        self.debit_reward_pool(from_rewards)?;
+        // Whatever is still held for the next first depositor must remain backed
+        self.unallocated_rewards = self.unallocated_rewards.min(self.excess_rewards());
+
+        from_escrow
+            .checked_add(from_rewards)
+            .ok_or_else(|| error!(ErrorCode::CalculationOverflow))
+    }
+
+    fn release_prepaid_fees(&mut self, deploy_request: &mut DeployRequest, amount: u64) -> Result<()> {
+        deploy_request.prepaid_balance = deploy_request
+            .prepaid_balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        self.prepaid_fees_balance = self
+            .prepaid_fees_balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// liquid_balance not yet reserved by a pending deploy request
+    pub fn available_liquidity(&self) -> u64 {
+        self.liquid_balance.saturating_sub(self.escrowed_amount)
+    }
+
+    /// Reject a deployment_cost above either ceiling (each 0 = disabled)
+    /// - max_deployment_cost: absolute lamports
+    /// - max_deployment_bps: share of liquid_balance, so one request cannot commit the whole pool
+    pub fn check_deployment_cost(&self, deployment_cost: u64) -> Result<()> {
+        require!(
+            self.max_deployment_cost == 0 || deployment_cost <= self.max_deployment_cost,
+            ErrorCode::DeploymentCostTooLarge
+        );
+        if self.max_deployment_bps > 0 {
+            let ceiling = (self.liquid_balance as u128)
+                .checked_mul(self.max_deployment_bps as u128)
                 .ok_or(ErrorCode::CalculationOverflow)?
-                .checked_div(self.total_deposited as u128)
-                .ok_or(ErrorCode::CalculationOverflow)?;
-            
-            self.reward_per_share = self
-                .reward_per_share
-                .checked_add(delta)
-                .ok_or_else(|| ErrorCode::CalculationOverflow)?;
+                / 10000;
+            require!(deployment_cost as u128 <= ceiling, ErrorCode::DeploymentCostTooLarge);
         }
-        
         Ok(())
     }
 
+    /// Set aside deployment_cost for a deploy request so concurrent requests cannot over-commit liquid_balance
+    /// Any previous reservation of the request must be released first
+    pub fn reserve_deployment(&mut self, deploy_request: &mut DeployRequest, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.available_liquidity(),
+            ErrorCode::InsufficientLiquidBalance
+        );
+        self.escrowed_amount = self
+            .escrowed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        deploy_request.reserved_amount = amount;
+        Ok(())
+    }
+
+    /// Drop a deploy request's reservation (funded, confirmed, failed or cancelled)
+    pub fn release_deployment(&mut self, deploy_request: &mut DeployRequest) {
+        self.escrowed_amount = self.escrowed_amount.saturating_sub(deploy_request.reserved_amount);
+        deploy_request.reserved_amount = 0;
+    }
+
+    /// Count a Reward Pool payout against the outflow circuit breaker (disabled while either setting is 0)
+    /// The window restarts once outflow_window_secs has elapsed. If the payout would take the window
+    /// past max_outflow_per_window it is not recorded: everything is paused and true is returned,
+    /// and the caller must skip the payout.
+    pub fn record_reward_outflow(&mut self, amount: u64, current_time: i64) -> Result<bool> {
+        if self.max_outflow_per_window == 0 || self.outflow_window_secs == 0 {
+            return Ok(false);
+        }
+        if self.outflow_window_start == 0
+            || current_time.saturating_sub(self.outflow_window_start) >= self.outflow_window_secs
+        {
+            self.outflow_window_start = current_time;
+            self.outflow_in_window = 0;
+        }
+        let outflow = self
+            .outflow_in_window
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        if outflow > self.max_outflow_per_window {
+            self.emergency_pause = true;
+            self.paused_ops = Self::PAUSE_ALL;
+            return Ok(true);
+        }
+        self.outflow_in_window = outflow;
+        Ok(false)
+    }
+
+    /// Track reward fees per window for velocity estimates
+    /// Rolls the current window into the last window once FEE_WINDOW_SECONDS has elapsed
+    pub fn record_fee_credit(&mut self, fee_reward: u64, current_time: i64) -> Result<()> {
+        if self.fee_window_start == 0 {
+            self.fee_window_start = current_time;
+        }
+
+        let elapsed = current_time.saturating_sub(self.fee_window_start);
+        if elapsed >= Self::FEE_WINDOW_SECONDS {
+            self.fees_credited_last_window = self.fees_credited_current_window;
+            self.last_window_seconds = elapsed;
+            self.fees_credited_current_window = 0;
+            self.fee_window_start = current_time;
+        }
+
+        self.fees_credited_current_window = self
+            .fees_credited_current_window
+            .checked_add(fee_reward)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
+    /// Recent reward fee velocity as (fees, seconds)
+    /// Uses the last completed window, or the current window until one has completed
+    pub fn fee_velocity(&self, current_time: i64) -> (u64, i64) {
+        if self.last_window_seconds > 0 {
+            (self.fees_credited_last_window, self.last_window_seconds)
+        } else if self.fee_window_start > 0 {
+            let elapsed = current_time.saturating_sub(self.fee_window_start).max(1);
+            (self.fees_credited_current_window, elapsed)
+        } else {
+            (0, 0)
+        }
+    }
+
+    /// Estimated rewards for `deposited_amount` over `horizon_seconds` at the recent fee velocity
+    /// projected = deposited_amount / total_deposited * fees / window_seconds * horizon_seconds
+    /// Estimate only - future fees are not guaranteed
+    pub fn project_rewards(
+        &self,
+        deposited_amount: u64,
+        horizon_seconds: i64,
+        current_time: i64,
+    ) -> Result<u64> {
+        let (fees, window_seconds) = self.fee_velocity(current_time);
+        if fees == 0 || window_seconds <= 0 || horizon_seconds <= 0 || self.total_deposited == 0 {
+            return Ok(0);
+        }
+
+        let projected = (deposited_amount as u128)
+            .checked_mul(fees as u128)
+            .and_then(|x| x.checked_mul(horizon_seconds as u128))
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(
+                (self.total_deposited as u128)
+                    .checked_mul(window_seconds as u128)
+                    .ok_or(ErrorCode::CalculationOverflow)?,
+            )
+            .ok_or(ErrorCode::CalculationOverflow)?;
+
+        Ok(projected.min(u64::MAX as u128) as u64)
+    }
+
     /// Calculate backer's claimable rewards using reward-per-share
     /// Formula: (deposited_amount * reward_per_share - reward_debt) / PRECISION
     pub fn calculate_claimable_rewards(&self, deposited_amount: u64, reward_debt: u128) -> Result<u64> {
@@ -159,6 +816,18 @@ impl TreasuryPool {
         Ok(())
     }
 
+    /// Pay out rewards to a backer: debit the reward pool and count them in total_rewards_claimed
+    pub fn pay_rewards(&mut self, amount: u64) -> Result<()> {
+        self.debit_reward_pool(amount)?;
+        // Saturating: rewards credited before total_claimable was tracked are paid out too
+        self.total_claimable = self.total_claimable.saturating_sub(amount);
+        self.total_rewards_claimed = self
+            .total_rewards_claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(())
+    }
+
 
     /// Credit platform pool (add fees)
     pub fn credit_platform_pool(&mut self, amount: u128) -> Result<()> {
@@ -170,6 +839,124 @@ impl TreasuryPool {
         Ok(())
     }
 
+    /// Compare pool assets against obligations to backers
+    /// Assets: treasury lamports (minus rent) + reward pool lamports + funds out on deployment
+    /// Obligations: total_deposited + reward_pool_balance (rewards owed to backers) + prepaid_fees_balance (escrow)
+    /// Returns (assets, obligations)
+    pub fn collateral_position(
+        &self,
+        treasury_available: u64,
+        reward_pool_lamports: u64,
+    ) -> Result<(u64, u64)> {
+        let assets = treasury_available
+            .checked_add(reward_pool_lamports)
+            .and_then(|x| x.checked_add(self.total_borrowed))
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let obligations = self
+            .total_deposited
+            .checked_add(self.reward_pool_balance)
+            .and_then(|x| x.checked_add(self.prepaid_fees_balance))
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok((assets, obligations))
+    }
+
+    /// Check that each PDA holds the lamports its tracked balances promise
+    /// - Treasury: lamports + total_borrowed >= total_deposited + rent (funds out on deployment count, as in collateral_position)
+    /// - Reward Pool: lamports >= reward_pool_balance + prepaid_fees_balance + undistributed_dust
+    /// - Platform Pool: lamports >= platform_pool_balance
+    ///
+    /// Fails with the error naming the first broken invariant
+    pub fn verify_solvency(
+        &self,
+        treasury_lamports: u64,
+        treasury_rent: u64,
+        reward_pool_lamports: u64,
+        platform_pool_lamports: u64,
+    ) -> Result<()> {
+        let treasury_assets = treasury_lamports
+            .checked_add(self.total_borrowed)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        let treasury_obligations = self
+            .total_deposited
+            .checked_add(treasury_rent)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        require!(treasury_assets >= treasury_obligations, ErrorCode::TreasuryInsolvent);
+
+        require!(
+            self.reward_pool_shortfall(reward_pool_lamports)? == 0,
+            ErrorCode::RewardPoolInsolvent
+        );
+
+        require!(
+            platform_pool_lamports >= self.platform_pool_balance,
+            ErrorCode::PlatformPoolInsolvent
+        );
+        Ok(())
+    }
+
+    /// Lamports the Reward Pool PDA is missing against rewards owed + prepaid escrow + dust
+    /// Zero when the RewardPoolInsolvent invariant holds
+    pub fn reward_pool_shortfall(&self, reward_pool_lamports: u64) -> Result<u64> {
+        let reward_obligations = self
+            .reward_pool_balance
+            .checked_add(self.prepaid_fees_balance)
+            .and_then(|x| x.checked_add(self.undistributed_dust))
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(reward_obligations.saturating_sub(reward_pool_lamports))
+    }
+
+    /// Key allowed to withdraw excess rewards from the Reward Pool
+    /// Falls back to DEFAULT_REWARD_ADMIN for pools whose reward_admin was never stored
+    pub fn effective_reward_admin(&self) -> Pubkey {
+        if self.reward_admin == Pubkey::default() {
+            Self::DEFAULT_REWARD_ADMIN
+        } else {
+            self.reward_admin
+        }
+    }
+
+    /// Pro-rata withdrawal payout while the pool is undercollateralized
+    /// payout = amount * assets / obligations (never more than amount)
+    /// Returns (payout, haircut)
+    pub fn pro_rata_withdrawal(amount: u64, assets: u64, obligations: u64) -> Result<(u64, u64)> {
+        if obligations == 0 || assets >= obligations {
+            return Ok((amount, 0));
+        }
+        let payout = (amount as u128)
+            .checked_mul(assets as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(obligations as u128)
+            .ok_or(ErrorCode::CalculationOverflow)? as u64;
+        let haircut = amount
+            .checked_sub(payout)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok((payout, haircut))
+    }
+
+    /// Early-unstake penalty on a payout (penalty_bps, routed to the reward pool)
+    pub fn early_unstake_penalty(&self, payout: u64) -> Result<u64> {
+        let penalty = (payout as u128)
+            .checked_mul(self.penalty_bps as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(penalty as u64)
+    }
+
+    /// Share of backer deposits currently out on deployments (basis points)
+    /// total_borrowed * 10000 / total_deposited, 0 when nothing is deposited
+    pub fn utilization_bps(total_borrowed: u64, total_deposited: u64) -> Result<u64> {
+        if total_deposited == 0 {
+            return Ok(0);
+        }
+        let utilization = (total_borrowed as u128)
+            .checked_mul(10000)
+            .ok_or(ErrorCode::CalculationOverflow)?
+            .checked_div(total_deposited as u128)
+            .ok_or(ErrorCode::CalculationOverflow)?;
+        Ok(utilization as u64)
+    }
+
     // Legacy methods for backward compatibility (deprecated)
     
     /// Calculate available rewards (legacy - now just returns reward_pool_balance)
@@ -188,3 +975,472 @@ impl TreasuryPool {
         self.credit_reward_pool(fees as u128)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_discount_follows_tiers() {
+        assert_eq!(TreasuryPool::subscription_discount_bps(1), 0);
+        assert_eq!(TreasuryPool::subscription_discount_bps(5), 0);
+        assert_eq!(TreasuryPool::subscription_discount_bps(6), 500);
+        assert_eq!(TreasuryPool::subscription_discount_bps(11), 500);
+        assert_eq!(TreasuryPool::subscription_discount_bps(12), 1000);
+        assert_eq!(TreasuryPool::subscription_discount_bps(36), 1000);
+
+        assert_eq!(TreasuryPool::subscription_payment(1_000, 3).unwrap(), 3_000);
+        assert_eq!(TreasuryPool::subscription_payment(1_000, 6).unwrap(), 5_700);
+        assert_eq!(TreasuryPool::subscription_payment(1_000, 12).unwrap(), 10_800);
+    }
+
+    #[test]
+    fn monthly_fee_follows_bps_of_deployment_cost() {
+        // Legacy path: explicit value passes through
+        assert_eq!(TreasuryPool::monthly_fee_for(0, 1_000_000_000, 123).unwrap(), 123);
+        // 1% of 1 SOL, explicit value ignored
+        assert_eq!(TreasuryPool::monthly_fee_for(100, 1_000_000_000, 123).unwrap(), 10_000_000);
+        // Tiny deployment rounds down to zero (rejected by the caller)
+        assert_eq!(TreasuryPool::monthly_fee_for(100, 99, 123).unwrap(), 0);
+    }
+
+    #[test]
+    fn deploy_fee_split_override_is_clamped() {
+        let mut pool = zeroed_pool();
+        pool.min_reward_split_bps = 5000;
+        pool.max_reward_split_bps = 9000;
+
+        // No override: the default split, reported as its effective bps
+        assert_eq!(pool.split_deploy_fees(900, 100, None).unwrap(), (900, 100, 9000));
+        assert_eq!(pool.split_deploy_fees(800, 200, Some(7500)).unwrap(), (750, 250, 7500));
+        assert_eq!(pool.split_deploy_fees(800, 200, Some(10000)).unwrap(), (900, 100, 9000));
+        assert_eq!(pool.split_deploy_fees(800, 200, Some(0)).unwrap(), (500, 500, 5000));
+    }
+
+    #[test]
+    fn outflow_breaker_pauses_before_the_window_limit_is_crossed() {
+        let mut pool = zeroed_pool();
+        assert!(!pool.record_reward_outflow(u64::MAX, 100).unwrap());
+
+        pool.max_outflow_per_window = 1_000;
+        pool.outflow_window_secs = 3_600;
+        assert!(!pool.record_reward_outflow(600, 100).unwrap());
+        assert!(!pool.record_reward_outflow(400, 200).unwrap());
+        assert!(!pool.emergency_pause);
+
+        // The payout that would cross the limit is refused and pauses everything
+        assert!(pool.record_reward_outflow(1, 300).unwrap());
+        assert!(pool.emergency_pause);
+        assert_eq!(pool.paused_ops, TreasuryPool::PAUSE_ALL);
+        assert_eq!(pool.outflow_in_window, 1_000);
+
+        // A new window starts from zero
+        pool.emergency_pause = false;
+        assert!(!pool.record_reward_outflow(900, 100 + 3_600).unwrap());
+        assert_eq!(pool.outflow_in_window, 900);
+    }
+
+    #[test]
+    fn deployment_cost_ceilings_apply_independently() {
+        let mut pool = zeroed_pool();
+        pool.liquid_balance = 10_000;
+        assert!(pool.check_deployment_cost(u64::MAX).is_ok());
+
+        pool.max_deployment_cost = 3_000;
+        assert!(pool.check_deployment_cost(3_000).is_ok());
+        let err = pool.check_deployment_cost(3_001).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::DeploymentCostTooLarge));
+
+        // 20% of liquid_balance is the tighter bound
+        pool.max_deployment_bps = 2000;
+        assert!(pool.check_deployment_cost(2_000).is_ok());
+        assert!(pool.check_deployment_cost(2_001).is_err());
+    }
+
+    #[test]
+    fn deploy_payment_adds_prepaid_months_to_the_reward_side() {
+        let mut pool = zeroed_pool();
+        pool.max_reward_split_bps = 10000;
+
+        // 3 months of 10_000, service_fee 100_000, 0.1% of 1_000_000 to the platform
+        let payment = pool.deploy_payment(100_000, 10_000, 3, 1_000_000, None).unwrap();
+        assert_eq!(payment.monthly_fee_total, 30_000);
+        assert_eq!(payment.reward_fee_amount, 130_000);
+        assert_eq!(payment.platform_fee_amount, 1_000);
+        assert_eq!(payment.total_payment, 131_000);
+
+        let payment = pool.deploy_payment(100_000, 10_000, 3, 1_000_000, Some(5000)).unwrap();
+        assert_eq!(payment.reward_share, 50_500);
+        assert_eq!(payment.total_payment, 131_000);
+    }
+
+    #[test]
+    fn recovered_funds_split_by_platform_bps() {
+        assert_eq!(TreasuryPool::split_recovered(1_000_000, 0).unwrap(), (1_000_000, 0));
+        assert_eq!(TreasuryPool::split_recovered(1_000_000, 250).unwrap(), (975_000, 25_000));
+        // Rounding favours liquid_balance
+        assert_eq!(TreasuryPool::split_recovered(999, 5000).unwrap(), (500, 499));
+    }
+
+    #[test]
+    fn reward_fee_truncation_is_tracked_as_dust() {
+        // Divides evenly: nothing lost
+        assert_eq!(
+            TreasuryPool::split_reward_dust(1_000_000, 1_000_000_000).unwrap(),
+            (1_000_000_000, 1_000_000, 0)
+        );
+        // 10 lamports over 3,000 SOL: delta truncates to 3, so only 9 lamports are claimable
+        assert_eq!(
+            TreasuryPool::split_reward_dust(10, 3_000_000_000_000).unwrap(),
+            (3, 9, 1)
+        );
+    }
+
+    #[test]
+    fn failure_refund_is_slashed_past_threshold() {
+        // Disabled or within the threshold: full refund
+        assert_eq!(TreasuryPool::failure_slash(1_000_000, 10, 3, 0).unwrap(), 0);
+        assert_eq!(TreasuryPool::failure_slash(1_000_000, 3, 3, 2_000).unwrap(), 0);
+        // Past the threshold
+        assert_eq!(TreasuryPool::failure_slash(1_000_000, 4, 3, 2_000).unwrap(), 200_000);
+    }
+
+    #[test]
+    fn reward_per_share_ceiling_keeps_largest_position_in_range() {
+        use crate::states::BackerDeposit;
+
+        // Extreme long-lived pool: accumulator at the ceiling, 1 billion SOL deposited
+        let ceiling = TreasuryPool::MAX_REWARD_PER_SHARE;
+        let largest_position = TreasuryPool::MAX_AMOUNT as u64;
+        assert!(BackerDeposit::reward_debt_for(largest_position, ceiling).is_ok());
+
+        // Growth up to the ceiling is allowed, one unit more is refused
+        assert_eq!(TreasuryPool::next_reward_per_share(ceiling - 1, 1).unwrap(), ceiling);
+        let err = TreasuryPool::next_reward_per_share(ceiling, 1).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::CalculationOverflow));
+        assert!(TreasuryPool::next_reward_per_share(u128::MAX, 1).is_err());
+    }
+
+    fn zeroed_pool() -> TreasuryPool {
+        let mut data = TreasuryPool::DISCRIMINATOR.to_vec();
+        data.resize(8 + TreasuryPool::INIT_SPACE, 0);
+        TreasuryPool::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    fn zeroed_deposit() -> crate::states::BackerDeposit {
+        use crate::states::BackerDeposit;
+        let mut data = BackerDeposit::DISCRIMINATOR.to_vec();
+        data.resize(8 + BackerDeposit::INIT_SPACE, 0);
+        BackerDeposit::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    fn zeroed_request() -> DeployRequest {
+        let mut data = DeployRequest::DISCRIMINATOR.to_vec();
+        data.resize(8 + DeployRequest::INIT_SPACE, 0);
+        DeployRequest::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    // Same order of operations as stake_sol / unstake_sol
+    fn stake(pool: &mut TreasuryPool, deposit: &mut crate::states::BackerDeposit, amount: u64) {
+        deposit.settle_pending_rewards(pool.reward_per_share).unwrap();
+        let reward_per_share_before = pool.reward_per_share;
+        pool.allocate_unallocated_rewards(amount).unwrap();
+        deposit.deposited_amount += amount;
+        pool.total_deposited += amount;
+        deposit.update_reward_debt(reward_per_share_before).unwrap();
+    }
+
+    fn unstake_all(pool: &mut TreasuryPool, deposit: &mut crate::states::BackerDeposit) {
+        deposit.settle_pending_rewards(pool.reward_per_share).unwrap();
+        pool.total_deposited -= deposit.deposited_amount;
+        deposit.deposited_amount = 0;
+        deposit.update_reward_debt(pool.reward_per_share).unwrap();
+    }
+
+    #[test]
+    fn deposit_after_credit_does_not_share_earlier_fee() {
+        let mut pool = zeroed_pool();
+        let (mut a, mut b) = (zeroed_deposit(), zeroed_deposit());
+
+        stake(&mut pool, &mut a, 1_000);
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        stake(&mut pool, &mut b, 1_000);
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 100);
+        assert_eq!(b.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 0);
+
+        pool.credit_fee_to_pool(200, 0).unwrap();
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 200);
+        assert_eq!(b.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 100);
+        assert_eq!(pool.reward_pool_balance, 300);
+    }
+
+    #[test]
+    fn deploy_request_on_empty_pool_goes_to_first_depositor() {
+        let mut pool = zeroed_pool();
+        let mut a = zeroed_deposit();
+
+        // Same crediting as create_deploy_request, with nobody staked yet
+        let payment = pool.deploy_payment(100_000, 10_000, 1, 1_000_000_000, None).unwrap();
+        pool.credit_fee_to_pool(payment.reward_share, payment.platform_fee_amount).unwrap();
+        assert_eq!(pool.unallocated_rewards, payment.reward_share);
+        assert_eq!(pool.platform_pool_balance, payment.platform_fee_amount);
+
+        stake(&mut pool, &mut a, 4_000);
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), payment.reward_share);
+        assert_eq!(pool.total_claimable, payment.reward_share);
+        assert_eq!(pool.excess_rewards(), 0);
+    }
+
+    #[test]
+    fn fee_credited_to_empty_pool_goes_to_first_depositor() {
+        let mut pool = zeroed_pool();
+        let mut a = zeroed_deposit();
+
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        assert_eq!(pool.unallocated_rewards, 100);
+        assert_eq!(pool.reward_per_share, 0);
+
+        stake(&mut pool, &mut a, 1_000);
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 100);
+        assert_eq!(pool.unallocated_rewards, 0);
+    }
+
+    #[test]
+    fn first_depositor_after_exit_does_not_capture_owed_rewards() {
+        let mut pool = zeroed_pool();
+        let (mut a, mut b) = (zeroed_deposit(), zeroed_deposit());
+
+        stake(&mut pool, &mut a, 1_000);
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        unstake_all(&mut pool, &mut a);
+        assert_eq!(pool.total_deposited, 0);
+
+        // reward_pool_balance still holds A's unclaimed 100; B must not get it too
+        stake(&mut pool, &mut b, 1_000);
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 100);
+        assert_eq!(b.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 0);
+
+        // Fees credited while empty (after A left) do go to B
+        unstake_all(&mut pool, &mut b);
+        pool.credit_fee_to_pool(30, 0).unwrap();
+        stake(&mut pool, &mut b, 1_000);
+        assert_eq!(b.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 30);
+        assert_eq!(pool.reward_pool_balance, 130);
+    }
+
+    #[test]
+    fn legacy_layout_is_migrated_with_balances_preserved() {
+        let mut pool = zeroed_pool();
+        pool.admin = Pubkey::new_unique();
+        pool.total_deposited = 5_000_000_000;
+        pool.liquid_balance = 4_000_000_000;
+        pool.reward_pool_balance = 42;
+        pool.platform_pool_balance = 7;
+        pool.reward_per_share = 123_456_789;
+        pool.bump = 254;
+
+        // Pre-versioning account: everything up to (not including) layout_version
+        let mut data = Vec::new();
+        pool.try_serialize(&mut data).unwrap();
+        data.truncate(data.len() - (1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32)); // layout_version, monthly_fee_bps, unallocated_rewards, reward split bounds, claim_cooldown_secs, total_claimable, deployment cost ceiling, outflow breaker
+        assert!(data.len() < 8 + TreasuryPool::INIT_SPACE);
+
+        let migrated = TreasuryPool::from_legacy_bytes(&data).unwrap();
+        assert_eq!(migrated.admin, pool.admin);
+        assert_eq!(migrated.total_deposited, 5_000_000_000);
+        assert_eq!(migrated.liquid_balance, 4_000_000_000);
+        assert_eq!(migrated.reward_pool_balance, 42);
+        assert_eq!(migrated.platform_pool_balance, 7);
+        assert_eq!(migrated.reward_per_share, 123_456_789);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.layout_version, 0);
+    }
+
+    #[test]
+    fn pools_behind_layout_version_are_stale() {
+        let mut pool = zeroed_pool();
+        // Pre-versioning pool (layout_version decodes as 0)
+        assert!(!pool.is_current_layout());
+
+        pool.layout_version = TreasuryPool::LAYOUT_VERSION - 1;
+        assert!(!pool.is_current_layout());
+
+        pool.layout_version = TreasuryPool::LAYOUT_VERSION;
+        assert!(pool.is_current_layout());
+    }
+
+    #[test]
+    fn solvency_check_names_the_broken_invariant() {
+        let mut pool = zeroed_pool();
+        pool.total_deposited = 10_000;
+        pool.total_borrowed = 4_000;
+        pool.reward_pool_balance = 500;
+        pool.prepaid_fees_balance = 300;
+        pool.undistributed_dust = 1;
+        pool.platform_pool_balance = 200;
+
+        // Funds out on deployment still count toward the treasury
+        assert!(pool.verify_solvency(6_100, 100, 801, 200).is_ok());
+
+        let err = pool.verify_solvency(6_099, 100, 801, 200).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::TreasuryInsolvent));
+        let err = pool.verify_solvency(6_100, 100, 800, 200).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::RewardPoolInsolvent));
+        let err = pool.verify_solvency(6_100, 100, 801, 199).unwrap_err();
+        assert_eq!(err, error!(ErrorCode::PlatformPoolInsolvent));
+    }
+
+    #[test]
+    fn reward_pool_shortfall_counts_escrow_and_dust() {
+        let mut pool = zeroed_pool();
+        pool.reward_pool_balance = 500;
+        pool.prepaid_fees_balance = 300;
+        pool.undistributed_dust = 1;
+
+        assert_eq!(pool.reward_pool_shortfall(801).unwrap(), 0);
+        assert_eq!(pool.reward_pool_shortfall(2_000).unwrap(), 0);
+        assert_eq!(pool.reward_pool_shortfall(600).unwrap(), 201);
+    }
+
+    #[test]
+    fn prepaid_escrow_cannot_back_a_fee_credit() {
+        let mut pool = zeroed_pool();
+        pool.prepaid_fees_balance = 500;
+
+        // Reward Pool PDA holds only the escrow: a fee credited on top of it is unbacked
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        assert_eq!(pool.reward_pool_shortfall(500).unwrap(), 100);
+        assert_eq!(pool.reward_pool_shortfall(600).unwrap(), 0);
+    }
+
+    #[test]
+    fn only_unattributed_rewards_are_excess() {
+        let mut pool = zeroed_pool();
+        let mut a = zeroed_deposit();
+
+        // Credited while nobody is deposited: not owed to anyone yet
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        assert_eq!(pool.total_claimable, 0);
+        assert_eq!(pool.excess_rewards(), 100);
+
+        // Handed to the first depositor: now owed
+        stake(&mut pool, &mut a, 1_000);
+        pool.credit_fee_to_pool(50, 0).unwrap();
+        assert_eq!(pool.total_claimable, 150);
+        assert_eq!(pool.excess_rewards(), 0);
+
+        let claimable = a.calculate_claimable_rewards(pool.reward_per_share).unwrap();
+        pool.pay_rewards(claimable).unwrap();
+        assert_eq!(pool.total_claimable, 0);
+        assert_eq!(pool.reward_pool_balance, 0);
+    }
+
+    #[test]
+    fn principal_or_rewards_block_a_close() {
+        let mut pool = zeroed_pool();
+        assert!(!pool.holds_backer_funds());
+
+        // Platform fees are the admin's own and do not block
+        pool.platform_pool_balance = 1_000;
+        assert!(!pool.holds_backer_funds());
+
+        pool.reward_pool_balance = 1;
+        assert!(pool.holds_backer_funds());
+
+        pool.reward_pool_balance = 0;
+        pool.total_deposited = 1;
+        assert!(pool.holds_backer_funds());
+    }
+
+    #[test]
+    fn partial_early_unstake_penalty_is_fully_claimable() {
+        let mut pool = zeroed_pool();
+        let (mut a, mut b) = (zeroed_deposit(), zeroed_deposit());
+        stake(&mut pool, &mut a, 1_000);
+        stake(&mut pool, &mut b, 3_000);
+
+        // a unstakes 500 early (same order as unstake_sol): 30 lamport penalty
+        a.settle_pending_rewards(pool.reward_per_share).unwrap();
+        a.deposited_amount -= 500;
+        pool.total_deposited -= 500;
+        pool.credit_fee_to_pool_excluding(30, a.deposited_amount).unwrap();
+        a.update_reward_debt(pool.reward_per_share).unwrap();
+
+        // Every lamport goes to b, none is stranded on a's reset debt
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 0);
+        assert_eq!(b.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 30);
+        assert_eq!(pool.total_claimable, 30);
+        assert_eq!(pool.undistributed_dust, 0);
+        assert_eq!(pool.excess_rewards(), 0);
+
+        // Sole remaining backer: held for the next first depositor instead
+        unstake_all(&mut pool, &mut b);
+        pool.credit_fee_to_pool_excluding(10, a.deposited_amount).unwrap();
+        assert_eq!(pool.unallocated_rewards, 10);
+        assert_eq!(pool.total_claimable, 30);
+    }
+
+    #[test]
+    fn deploy_fee_counts_only_claimable_rewards_as_owed() {
+        let mut pool = zeroed_pool();
+        let mut a = zeroed_deposit();
+        stake(&mut pool, &mut a, 3_000);
+
+        // Same crediting as create_deploy_request: 100_000 over 3_000 truncates 1 lamport
+        let payment = pool.deploy_payment(100_000, 10_000, 1, 1_000_000_000, None).unwrap();
+        pool.credit_fee_to_pool(payment.reward_share, payment.platform_fee_amount).unwrap();
+
+        let claimable = a.calculate_claimable_rewards(pool.reward_per_share).unwrap();
+        assert_eq!(claimable, 99_999);
+        assert_eq!(pool.total_claimable, claimable);
+        assert_eq!(pool.undistributed_dust, 1);
+        assert_eq!(pool.excess_rewards(), 0);
+    }
+
+    #[test]
+    fn utilization_is_borrowed_over_deposited() {
+        assert_eq!(TreasuryPool::utilization_bps(1_000_000, 0).unwrap(), 0);
+        assert_eq!(TreasuryPool::utilization_bps(0, 4_000_000).unwrap(), 0);
+        assert_eq!(TreasuryPool::utilization_bps(1_000_000, 4_000_000).unwrap(), 2_500);
+        // Rounds down
+        assert_eq!(TreasuryPool::utilization_bps(1, 3).unwrap(), 3_333);
+    }
+
+    #[test]
+    fn cancel_after_credit_leaves_owed_rewards_backed() {
+        let mut pool = zeroed_pool();
+        let mut a = zeroed_deposit();
+        let mut request = zeroed_request();
+        stake(&mut pool, &mut a, 1_000);
+
+        // Same crediting as create_deploy_request: service fee to backers, months escrowed
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        pool.escrow_prepaid_fees(&mut request, 300).unwrap();
+
+        // Cancel asks for both back; only the escrow is still the developer's
+        let refund = pool.refund_fees(&mut request, 100, 300).unwrap();
+        assert_eq!(refund, 300);
+        assert_eq!(request.prepaid_balance, 0);
+        assert!(pool.reward_pool_balance >= pool.total_claimable);
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 100);
+        assert_eq!(pool.reward_pool_shortfall(100).unwrap(), 0);
+    }
+
+    #[test]
+    fn cancel_on_empty_pool_refunds_the_unallocated_fee() {
+        let mut pool = zeroed_pool();
+        let mut a = zeroed_deposit();
+        let mut request = zeroed_request();
+
+        // Nobody staked: the service fee is held, not yet owed to anyone
+        pool.credit_fee_to_pool(100, 0).unwrap();
+        pool.escrow_prepaid_fees(&mut request, 300).unwrap();
+        assert_eq!(pool.refund_fees(&mut request, 100, 300).unwrap(), 400);
+        assert_eq!(pool.reward_pool_balance, 0);
+        assert_eq!(pool.unallocated_rewards, 0);
+
+        // The next first depositor is not handed the refunded fee
+        stake(&mut pool, &mut a, 1_000);
+        assert_eq!(a.calculate_claimable_rewards(pool.reward_per_share).unwrap(), 0);
+    }
+}
@@ -6,11 +6,64 @@ pub struct UserDeployStats {
     pub user: Pubkey,         // User public key
     pub active_sessions: u32, // Current active sessions
     pub daily_deploys: u32,   // Daily deploy count
-    pub total_deploys: u64,   // Total deployments
+    pub total_deploys: u64,   // Total deployments (lifetime, never reset)
     pub last_reset: i64,      // Last daily reset timestamp
     pub bump: u8,             // PDA bump
+    pub failed_deploys: u32,  // Failed deployments since the last success (drives failure slashing)
 }
 
 impl UserDeployStats {
     pub const PREFIX_SEED: &'static [u8] = b"user_stats";
+    pub const DAILY_RESET_SECS: i64 = 86400;
+
+    /// Start a new day once DAILY_RESET_SECS have passed since last_reset
+    /// Returns the daily_deploys count the reset discarded (None = same day, nothing reset)
+    pub fn reset_daily_if_due(&mut self, current_time: i64) -> Option<u32> {
+        if current_time - self.last_reset <= Self::DAILY_RESET_SECS {
+            return None;
+        }
+        let previous_daily_deploys = self.daily_deploys;
+        self.daily_deploys = 0;
+        self.last_reset = current_time;
+        Some(previous_daily_deploys)
+    }
+
+    /// daily_deploys as the next deploy would see it (0 once reset_daily_if_due would fire)
+    pub fn daily_deploys_at(&self, current_time: i64) -> u32 {
+        if current_time - self.last_reset > Self::DAILY_RESET_SECS {
+            0
+        } else {
+            self.daily_deploys
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_reset_fires_once_per_day_and_keeps_lifetime_total() {
+        let mut stats = UserDeployStats {
+            user: Pubkey::default(),
+            active_sessions: 0,
+            daily_deploys: 3,
+            total_deploys: 10,
+            last_reset: 1_000,
+            bump: 0,
+            failed_deploys: 0,
+        };
+
+        assert_eq!(stats.reset_daily_if_due(1_000 + UserDeployStats::DAILY_RESET_SECS), None);
+        assert_eq!(stats.daily_deploys, 3);
+        assert_eq!(stats.daily_deploys_at(1_000 + UserDeployStats::DAILY_RESET_SECS), 3);
+        assert_eq!(stats.daily_deploys_at(1_001 + UserDeployStats::DAILY_RESET_SECS), 0);
+
+        let next_day = 1_001 + UserDeployStats::DAILY_RESET_SECS;
+        assert_eq!(stats.reset_daily_if_due(next_day), Some(3));
+        assert_eq!(stats.daily_deploys, 0);
+        assert_eq!(stats.last_reset, next_day);
+        assert_eq!(stats.total_deploys, 10);
+        assert_eq!(stats.reset_daily_if_due(next_day + 1), None);
+    }
 }
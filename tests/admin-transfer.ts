@@ -0,0 +1,105 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Admin Transfer", () => {
+  // Test accounts
+  const newAdmin = Keypair.generate();
+  const impostor = Keypair.generate();
+
+  async function updateApy(signer: Keypair) {
+    await program.methods
+      .updateApy(new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(newAdmin.publicKey, 1 * LAMPORTS_PER_SOL);
+    await airdrop(impostor.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+  });
+
+  it("Should let the admin propose and cancel a transfer", async () => {
+    await program.methods
+      .proposeAdmin(impostor.publicKey)
+      .accounts({ treasuryPool: treasuryPoolPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    let pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.pendingAdmin.toString()).to.equal(impostor.publicKey.toString());
+
+    await program.methods
+      .cancelAdminTransfer()
+      .accounts({ treasuryPool: treasuryPoolPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.pendingAdmin).to.be.null;
+    expect(pool.admin.toString()).to.equal(admin.publicKey.toString());
+  });
+
+  it("Should reject accept_admin from a key that was not proposed", async () => {
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({ treasuryPool: treasuryPoolPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+
+    try {
+      await program.methods
+        .acceptAdmin()
+        .accounts({ treasuryPool: treasuryPoolPda, newAdmin: impostor.publicKey })
+        .signers([impostor])
+        .rpc();
+      expect.fail("Should have thrown Unauthorized");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should hand over admin rights on accept", async () => {
+    await program.methods
+      .acceptAdmin()
+      .accounts({ treasuryPool: treasuryPoolPda, newAdmin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.admin.toString()).to.equal(newAdmin.publicKey.toString());
+    expect(pool.pendingAdmin).to.be.null;
+
+    // Old admin has lost access
+    try {
+      await updateApy(admin);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    // New admin has access
+    await updateApy(newAdmin);
+  });
+
+  after(async () => {
+    // Hand admin back so other suites keep working
+    await program.methods
+      .proposeAdmin(admin.publicKey)
+      .accounts({ treasuryPool: treasuryPoolPda, admin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ treasuryPool: treasuryPoolPda, newAdmin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+  });
+});
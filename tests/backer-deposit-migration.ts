@@ -0,0 +1,82 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+// Undersized legacy accounts cannot be created against the current program;
+// decoding of the old layout is covered by the unit tests in states/lender_stake.rs
+describe("Backer Deposit Migration", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const payer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  before(async () => {
+    await airdrop(backer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(payer.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(1 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should create new positions at the current layout version", async () => {
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.layoutVersion).to.equal(5);
+  });
+
+  it("Should leave an up-to-date position untouched", async () => {
+    const before = await provider.connection.getAccountInfo(backerDepositPda);
+
+    await program.methods
+      .migrateBackerDeposit()
+      .accounts({
+        backerDeposit: backerDepositPda,
+        backer: backer.publicKey,
+        payer: payer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payer])
+      .rpc();
+
+    const after = await provider.connection.getAccountInfo(backerDepositPda);
+    expect(after.data.length).to.equal(before.data.length);
+    expect(after.lamports).to.equal(before.lamports);
+    expect(Buffer.compare(after.data, before.data)).to.equal(0);
+  });
+
+  it("Should reject a backer that does not match the position", async () => {
+    try {
+      await program.methods
+        .migrateBackerDeposit()
+        .accounts({
+          backerDeposit: backerDepositPda,
+          backer: payer.publicKey,
+          payer: payer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+      expect.fail("Should have rejected mismatched backer");
+    } catch (err) {
+      expect(err.toString()).to.match(/ConstraintSeeds|Unauthorized/);
+    }
+  });
+});
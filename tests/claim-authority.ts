@@ -0,0 +1,166 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Claim Authority", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const hotWallet = Keypair.generate();
+  const destination = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const FEE_REWARD = new BN(0.01 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function setClaimAuthority(claimAuthority: PublicKey | null) {
+    return program.methods
+      .setClaimAuthority(claimAuthority)
+      .accounts({
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .signers([backer])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  function claim(signer: Keypair, to: PublicKey | null) {
+    return program.methods
+      .claimRewards(null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: signer.publicKey,
+        destination: to,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer]);
+  }
+
+  async function creditFee() {
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(hotWallet.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should record the delegate and emit ClaimAuthorityUpdated", async () => {
+    const txSig = await setClaimAuthority(hotWallet.publicKey);
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.claimAuthority.toString()).to.equal(hotWallet.publicKey.toString());
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = Array.from(parser.parseLogs(tx.meta.logMessages));
+    const updated = events.find((e) => e.name === "claimAuthorityUpdated");
+    expect(updated.data.oldClaimAuthority).to.be.null;
+    expect(updated.data.newClaimAuthority.toString()).to.equal(hotWallet.publicKey.toString());
+  });
+
+  it("Should reject a claim signed by the backer while a delegate is set", async () => {
+    await creditFee();
+
+    try {
+      await claim(backer, null).rpc();
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should let the delegate claim to a separate destination", async () => {
+    const { events } = await claim(hotWallet, destination.publicKey).simulate();
+    const claimed = events.find((e) => e.name === "claimed");
+    expect(claimed.data.claimAuthority.toString()).to.equal(hotWallet.publicKey.toString());
+    expect(claimed.data.destination.toString()).to.equal(destination.publicKey.toString());
+
+    const backerBalanceBefore = await provider.connection.getBalance(backer.publicKey);
+    await claim(hotWallet, destination.publicKey).rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.claimedTotal.toNumber()).to.be.greaterThan(0);
+    const destinationBalance = await provider.connection.getBalance(destination.publicKey);
+    expect(destinationBalance).to.equal(deposit.claimedTotal.toNumber());
+    expect(await provider.connection.getBalance(backer.publicKey)).to.equal(backerBalanceBefore);
+  });
+
+  it("Should keep principal withdrawals restricted to the backer", async () => {
+    try {
+      await program.methods
+        .unstakeSol(new BN(1 * LAMPORTS_PER_SOL), false)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          treasuryPda: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          lenderStake: backerDepositPda,
+          lender: hotWallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([hotWallet])
+        .rpc();
+      expect.fail("Delegate should not be able to unstake");
+    } catch (err) {
+      // lender_stake PDA is derived from the signer, so the delegate cannot address it
+      expect(err.toString()).to.include("ConstraintSeeds");
+    }
+  });
+
+  it("Should return claim rights to the backer when cleared", async () => {
+    await setClaimAuthority(null);
+    await creditFee();
+
+    await claim(backer, null).rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.claimAuthority).to.be.null;
+  });
+});
@@ -0,0 +1,124 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Claim Cooldown", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const FEE_REWARD = new BN(0.01 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function setClaimCooldown(claimCooldownSecs: number) {
+    return program.methods
+      .setClaimCooldown(new BN(claimCooldownSecs))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  function claim() {
+    return program.methods
+      .claimRewards(null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Cooldown set before the deposit it anchors to
+    await setClaimCooldown(60 * 60);
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    // Fee credit right after the deposit - the snipe the cooldown guards against
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  after(async () => {
+    await setClaimCooldown(0);
+  });
+
+  it("Should record the deposit time on the backer deposit", async () => {
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.lastActionTs.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Should reject a claim within the cooldown of the last deposit", async () => {
+    try {
+      await claim();
+      expect.fail("Should have thrown ClaimCooldownActive");
+    } catch (err) {
+      expect(err.toString()).to.include("ClaimCooldownActive");
+    }
+  });
+
+  it("Should reject a cooldown above 7 days", async () => {
+    try {
+      await setClaimCooldown(7 * 24 * 60 * 60 + 1);
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should allow the claim once the cooldown is off", async () => {
+    await setClaimCooldown(0);
+    await claim();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.claimedTotal.toNumber()).to.be.greaterThan(0);
+  });
+});
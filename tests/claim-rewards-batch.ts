@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Claim Rewards Batch", () => {
+  // Test accounts
+  const backers = [Keypair.generate(), Keypair.generate()];
+
+  // PDAs
+  let depositPdas: PublicKey[];
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  function remainingAccounts() {
+    return backers.flatMap((backer, i) => [
+      { pubkey: depositPdas[i], isWritable: true, isSigner: false },
+      { pubkey: backer.publicKey, isWritable: true, isSigner: false },
+    ]);
+  }
+
+  before(async () => {
+    for (const backer of backers) {
+      await airdrop(backer.publicKey, 5 * LAMPORTS_PER_SOL);
+    }
+
+    depositPdas = backers.map(
+      (backer) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+          program.programId
+        )[0]
+    );
+
+    await initializePool();
+
+    for (let i = 0; i < backers.length; i++) {
+      await program.methods
+        .stakeSol(new BN(2 * LAMPORTS_PER_SOL), new BN(0))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          treasuryPda: treasuryPoolPda,
+          lenderStake: depositPdas[i],
+          lender: backers[i].publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([backers[i]])
+        .rpc();
+    }
+
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), new BN(0.1 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Should pay every backer in a single instruction", async () => {
+    const balancesBefore = await Promise.all(
+      backers.map((backer) => provider.connection.getBalance(backer.publicKey))
+    );
+    const depositsBefore = await Promise.all(
+      depositPdas.map((pda) => program.account.backerDeposit.fetch(pda))
+    );
+
+    const txSig = await program.methods
+      .claimRewardsBatch()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        admin: admin.publicKey,
+      })
+      .remainingAccounts(remainingAccounts())
+      .signers([admin])
+      .rpc();
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const batch = Array.from(events).find((e) => e.name === "rewardsClaimedBatch");
+    expect(batch, "RewardsClaimedBatch event").to.not.be.undefined;
+    expect(batch.data.backersPaid).to.equal(backers.length);
+
+    let totalPaid = 0;
+    for (let i = 0; i < backers.length; i++) {
+      const depositAfter = await program.account.backerDeposit.fetch(depositPdas[i]);
+      const paid = depositAfter.claimedTotal.sub(depositsBefore[i].claimedTotal).toNumber();
+      expect(paid).to.be.greaterThan(0);
+      expect(depositAfter.pendingRewards.toNumber()).to.equal(0);
+
+      const balanceAfter = await provider.connection.getBalance(backers[i].publicKey);
+      expect(balanceAfter - balancesBefore[i]).to.equal(paid);
+      totalPaid += paid;
+    }
+    expect(batch.data.totalPaid.toNumber()).to.equal(totalPaid);
+  });
+
+  it("Should skip backers with nothing left to claim", async () => {
+    const txSig = await program.methods
+      .claimRewardsBatch()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        admin: admin.publicKey,
+      })
+      .remainingAccounts(remainingAccounts())
+      .signers([admin])
+      .rpc();
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const batch = Array.from(events).find((e) => e.name === "rewardsClaimedBatch");
+    expect(batch.data.backersPaid).to.equal(0);
+    expect(batch.data.totalPaid.toNumber()).to.equal(0);
+  });
+});
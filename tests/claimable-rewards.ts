@@ -0,0 +1,149 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Claimable Rewards View", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const FEE_REWARD = new BN(0.5 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function getClaimableRewards() {
+    return program.methods
+      .getClaimableRewards()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .view();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    // Known fee credit so there is something to claim
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Should return claimable rewards matching the reward-per-share math", async () => {
+    const view = await getClaimableRewards();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    // claimable = pending + (deposited * reward_per_share - reward_debt) / PRECISION
+    const PRECISION = new BN("1000000000000");
+    const expected = deposit.pendingRewards.add(
+      deposit.depositedAmount.mul(pool.rewardPerShare).sub(deposit.rewardDebt).div(PRECISION)
+    );
+
+    expect(view.claimable.toString()).to.equal(expected.toString());
+    expect(view.claimable.toNumber()).to.be.greaterThan(0);
+    expect(view.rewardPerShare.toString()).to.equal(pool.rewardPerShare.toString());
+    expect(view.depositedAmount.toString()).to.equal(deposit.depositedAmount.toString());
+  });
+
+  it("Should report deposit time tracking and a loyalty multiplier", async () => {
+    const view = await getClaimableRewards();
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    expect(view.depositStartTs.toNumber()).to.be.greaterThan(0);
+    expect(view.depositStartTs.toString()).to.equal(deposit.depositStartTs.toString());
+    // Fresh position: at most a sliver of the 10% bonus
+    expect(view.loyaltyMultiplierBps.toNumber()).to.be.at.least(10000);
+    expect(view.loyaltyMultiplierBps.toNumber()).to.be.lessThan(10010);
+  });
+
+  it("Should not mutate the backer deposit", async () => {
+    const before = await program.account.backerDeposit.fetch(backerDepositPda);
+    await getClaimableRewards();
+    const after = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    expect(after.pendingRewards.toString()).to.equal(before.pendingRewards.toString());
+    expect(after.rewardDebt.toString()).to.equal(before.rewardDebt.toString());
+  });
+
+  it("Should keep rewards claimable after a full unstake", async () => {
+    const before = await getClaimableRewards();
+    expect(before.claimable.toNumber()).to.be.greaterThan(0);
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    await program.methods
+      .unstakeSol(deposit.depositedAmount, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    const after = await getClaimableRewards();
+    expect(after.depositedAmount.toNumber()).to.equal(0);
+    expect(after.claimable.toString()).to.equal(before.claimable.toString());
+
+    await program.methods
+      .claimRewards(null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        destination: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    const claimed = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(claimed.claimedTotal.toString()).to.equal(before.claimable.toString());
+  });
+});
@@ -0,0 +1,183 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Close Program Refund", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+  const refundSource = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+  let deployRequestPda: PublicKey;
+
+  const programHash = crypto.randomBytes(32);
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100); // 1% of borrowed amount
+  const INITIAL_MONTHS = 3;
+  const RECOVERED_LAMPORTS = new BN(0.5 * LAMPORTS_PER_SOL);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(refundSource.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+    [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should deploy a program with prepaid months", async () => {
+    await transfer(
+      developer,
+      rewardPoolPda,
+      SERVICE_FEE.add(MONTHLY_FEE.muln(INITIAL_MONTHS)).toNumber()
+    );
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, INITIAL_MONTHS, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const ephemeralKey = Keypair.generate();
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .confirmDeploymentSuccess(Array.from(programHash), Keypair.generate().publicKey, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ active: {} });
+  });
+
+  it("Should refuse to force-close a request whose subscription is still paid", async () => {
+    try {
+      await program.methods
+        .forceCloseAbandoned(Array.from(programHash), RECOVERED_LAMPORTS, 0)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          deployRequest: deployRequestPda,
+          userStats: userStatsPda,
+          admin: admin.publicKey,
+          refundSource: refundSource.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin, refundSource])
+        .rpc();
+      expect.fail("Should have thrown RequestNotAbandoned");
+    } catch (err) {
+      expect(err.toString()).to.include("RequestNotAbandoned");
+    }
+  });
+
+  it("Should refund unused whole months when the program is closed", async () => {
+    const developerBalanceBefore = await provider.connection.getBalance(developer.publicKey);
+
+    const txSig = await program.methods
+      .closeProgramAndRefund(Array.from(programHash), RECOVERED_LAMPORTS)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        refundSource: refundSource.publicKey,
+        rewardPool: rewardPoolPda,
+        developerWallet: developer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, refundSource])
+      .rpc();
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const closed = Array.from(events).find((e) => e.name === "programClosed");
+    expect(closed, "ProgramClosed event").to.not.be.undefined;
+
+    // The current (partially used) month is not refunded
+    const expectedRefund = MONTHLY_FEE.muln(INITIAL_MONTHS - 1);
+    expect(closed.data.refundedAmount.toString()).to.equal(expectedRefund.toString());
+
+    const developerBalanceAfter = await provider.connection.getBalance(developer.publicKey);
+    expect(developerBalanceAfter - developerBalanceBefore).to.equal(expectedRefund.toNumber());
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ closed: {} });
+    expect(deployRequest.prepaidBalance.toNumber()).to.equal(0);
+  });
+});
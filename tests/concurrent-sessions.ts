@@ -0,0 +1,120 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Concurrent Deploy Sessions", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(0.1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.01 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function cancel(programHash: Buffer, deployRequestPda: PublicKey) {
+    await program.methods
+      .cancelDeployRequest(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+      })
+      .signers([developer])
+      .rpc();
+  }
+
+  async function activeSessions(): Promise<number> {
+    const userStats = await program.account.userDeployStats.fetch(userStatsPda);
+    return userStats.activeSessions;
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  it("Should free a session when a pending request is cancelled", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const sessionsBefore = await activeSessions();
+
+    await cancel(programHash, deployRequestPda);
+
+    expect(await activeSessions()).to.equal(sessionsBefore - 1);
+  });
+
+  it("Should reject a new request once the developer is at max_concurrent_sessions", async () => {
+    const treasuryPool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const cap = treasuryPool.maxConcurrentSessions;
+    expect(cap).to.equal(10);
+
+    const open: { programHash: Buffer; deployRequestPda: PublicKey }[] = [];
+    while ((await activeSessions().catch(() => 0)) < cap) {
+      open.push(await createPendingRequest());
+    }
+
+    try {
+      await createPendingRequest();
+      expect.fail("Should have thrown MaxConcurrentSessionsExceeded");
+    } catch (err) {
+      expect(err.toString()).to.include("MaxConcurrentSessionsExceeded");
+    }
+
+    // Cancelling one request frees a slot for the next
+    const freed = open.pop();
+    await cancel(freed.programHash, freed.deployRequestPda);
+    open.push(await createPendingRequest());
+    expect(await activeSessions()).to.equal(cap);
+
+    for (const { programHash, deployRequestPda } of open) {
+      await cancel(programHash, deployRequestPda);
+    }
+    expect(await activeSessions()).to.equal(0);
+  });
+});
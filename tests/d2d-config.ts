@@ -0,0 +1,174 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("D2D Config", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+
+  // PDAs
+  let configPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function parseEvents(txSig: string) {
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+  }
+
+  async function updateConfig(feeRate: BN, maxConcurrentPerUser: number, isPaused: boolean) {
+    return program.methods
+      .updateConfig(feeRate, maxConcurrentPerUser, isPaused)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        config: configPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function createRequest(serviceFee: BN, withConfig: boolean) {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, serviceFee.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), serviceFee, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        config: withConfig ? configPda : null,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return deployRequestPda;
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("d2d_config")],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    // Leave the shared config permissive for other suites
+    await updateConfig(new BN(0), 10, false);
+  });
+
+  it("Should emit ConfigInitialized when the config is created", async () => {
+    try {
+      const txSig = await program.methods
+        .initializeConfig(new BN(0), 10)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          config: configPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+      const events = await parseEvents(txSig);
+      const initialized = events.find((e) => e.name === "configInitialized");
+      expect(initialized).to.not.be.undefined;
+      expect(initialized.data.maxConcurrentPerUser).to.equal(10);
+    } catch (err) {
+      // May already be initialized by an earlier run
+      console.log("Config may already be initialized");
+    }
+
+    const config = await program.account.d2DConfig.fetch(configPda);
+    expect(config.admin.toString()).to.equal(admin.publicKey.toString());
+  });
+
+  it("Should emit ConfigUpdated with old and new values", async () => {
+    const before = await program.account.d2DConfig.fetch(configPda);
+
+    const txSig = await updateConfig(new BN(1000), 3, false);
+
+    const events = await parseEvents(txSig);
+    const updated = events.find((e) => e.name === "configUpdated");
+    expect(updated).to.not.be.undefined;
+    expect(updated.data.oldFeeRate.toString()).to.equal(before.feeRate.toString());
+    expect(updated.data.newFeeRate.toNumber()).to.equal(1000);
+    expect(updated.data.newMaxConcurrentPerUser).to.equal(3);
+  });
+
+  it("Should reject a service fee below the config fee_rate", async () => {
+    await updateConfig(SERVICE_FEE.muln(2), 10, false);
+
+    try {
+      await createRequest(SERVICE_FEE, true);
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+
+    // Without the config the pool default (any non-zero fee) still applies
+    const deployRequestPda = await createRequest(SERVICE_FEE, false);
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.serviceFee.toString()).to.equal(SERVICE_FEE.toString());
+  });
+
+  it("Should cap open sessions at max_concurrent_per_user", async () => {
+    const stats = await program.account.userDeployStats.fetch(userStatsPda);
+    await updateConfig(new BN(0), stats.activeSessions, false);
+
+    try {
+      await createRequest(SERVICE_FEE, true);
+      expect.fail("Should have thrown MaxConcurrentSessionsExceeded");
+    } catch (err) {
+      expect(err.toString()).to.include("MaxConcurrentSessionsExceeded");
+    }
+  });
+
+  it("Should reject deploy requests while the config is paused", async () => {
+    await updateConfig(new BN(0), 10, true);
+
+    try {
+      await createRequest(SERVICE_FEE, true);
+      expect.fail("Should have thrown ProgramPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramPaused");
+    }
+  });
+});
@@ -0,0 +1,179 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deploy Limits", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+  const outsider = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(0.1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.01 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function cancel(programHash: Buffer, deployRequestPda: PublicKey) {
+    await program.methods
+      .cancelDeployRequest(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+      })
+      .signers([developer])
+      .rpc();
+  }
+
+  async function setDeployLimits(signer: Keypair, maxConcurrentSessions: number, maxDailyDeploys: number) {
+    await program.methods
+      .setDeployLimits(maxConcurrentSessions, maxDailyDeploys)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function setDeploymentCostCap(maxDeploymentCost: BN, maxDeploymentBps: number) {
+    await program.methods
+      .setDeploymentCostCap(maxDeploymentCost, new BN(maxDeploymentBps))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(outsider.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    // Restore the defaults other suites rely on
+    await setDeployLimits(admin, 10, 20);
+    await setDeploymentCostCap(new BN(0), 0);
+  });
+
+  it("Should reject set_deploy_limits from a non-admin", async () => {
+    try {
+      await setDeployLimits(outsider, 5, 5);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should reject zero limits", async () => {
+    for (const [sessions, daily] of [[0, 5], [5, 0]]) {
+      try {
+        await setDeployLimits(admin, sessions, daily);
+        expect.fail("Should have thrown InvalidAmount");
+      } catch (err) {
+        expect(err.toString()).to.include("InvalidAmount");
+      }
+    }
+  });
+
+  it("Should reject a max_deployment_bps above 100%", async () => {
+    try {
+      await setDeploymentCostCap(new BN(0), 10001);
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should reject a deployment_cost above max_deployment_cost", async () => {
+    await setDeploymentCostCap(DEPLOYMENT_COST.subn(1), 0);
+    try {
+      await createPendingRequest();
+      expect.fail("Should have thrown DeploymentCostTooLarge");
+    } catch (err) {
+      expect(err.toString()).to.include("DeploymentCostTooLarge");
+    }
+    await setDeploymentCostCap(new BN(0), 0);
+  });
+
+  it("Should reject deploy requests beyond max_daily_deploys", async () => {
+    await setDeployLimits(admin, 10, 2);
+
+    const treasuryPool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(treasuryPool.maxConcurrentSessions).to.equal(10);
+    expect(treasuryPool.maxDailyDeploys).to.equal(2);
+
+    const first = await createPendingRequest();
+    const second = await createPendingRequest();
+
+    try {
+      await createPendingRequest();
+      expect.fail("Should have thrown DailyDeployLimitExceeded");
+    } catch (err) {
+      expect(err.toString()).to.include("DailyDeployLimitExceeded");
+    }
+
+    // Cancelling frees a session but not today's deploy count
+    await cancel(first.programHash, first.deployRequestPda);
+    try {
+      await createPendingRequest();
+      expect.fail("Should have thrown DailyDeployLimitExceeded");
+    } catch (err) {
+      expect(err.toString()).to.include("DailyDeployLimitExceeded");
+    }
+    await cancel(second.programHash, second.deployRequestPda);
+
+    const userStats = await program.account.userDeployStats.fetch(userStatsPda);
+    expect(userStats.dailyDeploys).to.equal(2);
+    expect(userStats.activeSessions).to.equal(0);
+  });
+});
@@ -0,0 +1,168 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deploy Ownership Transfer", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+  const newOwner = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+  let newUserStatsPda: PublicKey;
+  let deployRequestPda: PublicKey;
+
+  const programHash = crypto.randomBytes(32);
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  function blacklistPda(key: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), key.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function transferOwnership(current: Keypair, next: Keypair) {
+    const [currentStats] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), current.publicKey.toBuffer()],
+      program.programId
+    );
+    const [nextStats] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), next.publicKey.toBuffer()],
+      program.programId
+    );
+    return program.methods
+      .transferDeployOwnership(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        oldUserStats: currentStats,
+        newUserStats: nextStats,
+        developer: current.publicKey,
+        newDeveloper: next.publicKey,
+        blacklist: blacklistPda(next.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([current, next]);
+  }
+
+  async function createRequest() {
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(newOwner.publicKey, 2 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+    [newUserStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), newOwner.publicKey.toBuffer()],
+      program.programId
+    );
+    [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await initializePool();
+
+    await createRequest();
+  });
+
+  it("Should reject a transfer to the current developer", async () => {
+    try {
+      await transferOwnership(developer, developer).rpc();
+      expect.fail("Should have thrown SameDeveloper");
+    } catch (err) {
+      expect(err.toString()).to.include("SameDeveloper");
+    }
+  });
+
+  it("Should move the request and its session to the new developer", async () => {
+    const oldStatsBefore = await program.account.userDeployStats.fetch(userStatsPda);
+
+    const { events } = await transferOwnership(developer, newOwner).simulate();
+    const transferred = events.find((e) => e.name === "deployOwnershipTransferred");
+    expect(transferred.data.oldDeveloper.toString()).to.equal(developer.publicKey.toString());
+    expect(transferred.data.newDeveloper.toString()).to.equal(newOwner.publicKey.toString());
+
+    await transferOwnership(developer, newOwner).rpc();
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.developer.toString()).to.equal(newOwner.publicKey.toString());
+    expect(deployRequest.originalDeveloper.toString()).to.equal(developer.publicKey.toString());
+
+    const oldStats = await program.account.userDeployStats.fetch(userStatsPda);
+    const newStats = await program.account.userDeployStats.fetch(newUserStatsPda);
+    expect(oldStats.activeSessions).to.equal(oldStatsBefore.activeSessions - 1);
+    expect(newStats.user.toString()).to.equal(newOwner.publicKey.toString());
+    expect(newStats.activeSessions).to.equal(1);
+  });
+
+  it("Should report the moved session via get_user_stats", async () => {
+    const stats = await program.methods
+      .getUserStats()
+      .accounts({ userStats: newUserStatsPda, treasuryPool: treasuryPoolPda })
+      .view();
+    expect(stats.activeSessions).to.equal(1);
+    expect(stats.totalDeploys.toNumber()).to.equal(0);
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(stats.deploysRemainingToday).to.equal(pool.maxDailyDeploys - stats.dailyDeploys);
+
+    // Without the Treasury Pool the daily limit is not known
+    const bare = await program.methods
+      .getUserStats()
+      .accounts({ userStats: newUserStatsPda, treasuryPool: null })
+      .view();
+    expect(bare.deploysRemainingToday).to.be.null;
+  });
+
+  it("Should reject a transfer signed by the previous developer", async () => {
+    try {
+      await transferOwnership(developer, newOwner).rpc();
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should not let the original developer reclaim the request", async () => {
+    try {
+      await createRequest();
+      expect.fail("Should have thrown InvalidRequestId");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidRequestId");
+    }
+  });
+});
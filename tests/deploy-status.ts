@@ -0,0 +1,210 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deploy Status View", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  const SECONDS_PER_MONTH = 30 * 24 * 60 * 60;
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(months: number): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE.muln(months)).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, months, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function confirmFailure(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "status test cleanup")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  }
+
+  async function getDeployStatus(programHash: Buffer, deployRequestPda: PublicKey) {
+    return program.methods
+      .getDeployStatus(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+      })
+      .view();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should report a pending request with a valid subscription", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest(2);
+
+    const status = await getDeployStatus(programHash, deployRequestPda);
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+
+    expect(status.status).to.deep.equal({ pendingDeployment: {} });
+    expect(status.deployedProgramId).to.equal(null);
+    expect(status.subscriptionPaidUntil.toNumber()).to.equal(
+      deployRequest.createdAt.toNumber() + 2 * SECONDS_PER_MONTH
+    );
+    expect(status.subscriptionValid).to.equal(true);
+    expect(status.deployedAt).to.equal(null);
+    expect(status.deployLatencySecs).to.equal(null);
+  });
+
+  it("Should persist deployed_at and report deploy latency after confirm_deployment_success", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest(1);
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    const confirm = program.methods
+      .confirmDeploymentSuccess(Array.from(programHash), Keypair.generate().publicKey, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey]);
+
+    const { events } = await confirm.simulate();
+    const confirmed = events.find((e) => e.name === "deploymentConfirmed");
+    await confirm.rpc();
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.deployedAt).to.not.equal(null);
+    const latency = deployRequest.deployedAt.sub(deployRequest.createdAt).toNumber();
+    expect(latency).to.be.at.least(0);
+    expect(confirmed.data.deployLatencySecs.toNumber()).to.be.at.least(0);
+
+    const status = await getDeployStatus(programHash, deployRequestPda);
+    expect(status.status).to.deep.equal({ active: {} });
+    expect(status.deployedAt.toString()).to.equal(deployRequest.deployedAt.toString());
+    expect(status.deployLatencySecs.toNumber()).to.equal(latency);
+  });
+
+  it("Should report a failed request after confirm_deployment_failure", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest(1);
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+    await confirmFailure(programHash, deployRequestPda, ephemeralKey);
+
+    const status = await getDeployStatus(programHash, deployRequestPda);
+    expect(status.status).to.deep.equal({ failed: {} });
+    expect(status.deployedProgramId).to.equal(null);
+  });
+
+  it("Should report subscription validity for a batch of requests as a bitmap", async () => {
+    const { deployRequestPda } = await createPendingRequest(1);
+
+    const batch = await program.methods
+      .checkSubscriptionsBatch()
+      .accounts({ treasuryPool: treasuryPoolPda })
+      .remainingAccounts([
+        { pubkey: deployRequestPda, isWritable: false, isSigner: false },
+        // Not a DeployRequest: reads as invalid
+        { pubkey: treasuryPoolPda, isWritable: false, isSigner: false },
+      ])
+      .view();
+
+    expect(batch.count).to.equal(2);
+    expect(batch.validBitmap.length).to.equal(1);
+    expect(batch.validBitmap[0] & 0b01).to.equal(1);
+    expect(batch.validBitmap[0] & 0b10).to.equal(0);
+  });
+});
@@ -0,0 +1,326 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deployment Funding", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function confirmSuccess(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    await program.methods
+      .confirmDeploymentSuccess(Array.from(programHash), Keypair.generate().publicKey, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should record borrowed_amount through create -> fund -> confirm", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+
+    let deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.borrowedAmount.toNumber()).to.equal(0);
+    expect(deployRequest.lastFeeAccrual.toString()).to.equal(deployRequest.createdAt.toString());
+
+    const ephemeralKey = Keypair.generate();
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.borrowedAmount.toString()).to.equal(DEPLOYMENT_COST.toString());
+
+    await confirmSuccess(programHash, deployRequestPda, ephemeralKey);
+
+    deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ active: {} });
+    expect(deployRequest.borrowedAmount.toString()).to.equal(deployRequest.deploymentCost.toString());
+  });
+
+  it("Should not activate a request that was never funded", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+
+    try {
+      await confirmSuccess(programHash, deployRequestPda, Keypair.generate());
+      expect.fail("Should have thrown InvalidEphemeralKey");
+    } catch (err) {
+      // No ephemeral key until fund_temporary_wallet runs
+      expect(err.toString()).to.include("InvalidEphemeralKey");
+    }
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ pendingDeployment: {} });
+  });
+
+  it("Should report utilization from total_borrowed while funds are out", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+
+    const before = await program.methods
+      .getUtilization()
+      .accounts({ treasuryPool: treasuryPoolPda })
+      .view();
+
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const during = await program.methods
+      .getUtilization()
+      .accounts({ treasuryPool: treasuryPoolPda })
+      .view();
+    expect(during.totalBorrowed.toString()).to.equal(before.totalBorrowed.add(DEPLOYMENT_COST).toString());
+    expect(during.utilizationBps.toString()).to.equal(
+      during.totalBorrowed.muln(10000).div(during.totalDeposited).toString()
+    );
+
+    await confirmSuccess(programHash, deployRequestPda, ephemeralKey);
+
+    const after = await program.methods
+      .getUtilization()
+      .accounts({ treasuryPool: treasuryPoolPda })
+      .view();
+    expect(after.totalBorrowed.toString()).to.equal(before.totalBorrowed.toString());
+  });
+
+  it("Should escrow deployment_cost from request until the wallet is funded", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const available = pool.liquidBalance.sub(pool.escrowedAmount);
+
+    const requestFunds = async (programHash: Buffer, deploymentCost: BN) =>
+      program.methods
+        .requestDeploymentFunds(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, deploymentCost)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          platformPool: platformPoolPda,
+          userStats: userStatsPda,
+          developer: developer.publicKey,
+          admin: admin.publicKey,
+          treasuryWallet: pool.treasuryWallet,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([developer, admin])
+        .rpc();
+
+    // More than liquid_balance - escrowed_amount can never be reserved
+    try {
+      await requestFunds(crypto.randomBytes(32), available.addn(1));
+      expect.fail("Should have thrown InsufficientLiquidBalance");
+    } catch (err) {
+      expect(err.toString()).to.include("InsufficientLiquidBalance");
+    }
+
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+    await requestFunds(programHash, DEPLOYMENT_COST);
+
+    let deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.reservedAmount.toString()).to.equal(DEPLOYMENT_COST.toString());
+    let after = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(after.escrowedAmount.toString()).to.equal(pool.escrowedAmount.add(DEPLOYMENT_COST).toString());
+
+    // Funding converts the reservation into total_borrowed
+    const ephemeralKey = Keypair.generate();
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.reservedAmount.toNumber()).to.equal(0);
+    after = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(after.escrowedAmount.toString()).to.equal(pool.escrowedAmount.toString());
+    expect(after.totalBorrowed.toString()).to.equal(pool.totalBorrowed.add(DEPLOYMENT_COST).toString());
+
+    await confirmSuccess(programHash, deployRequestPda, ephemeralKey);
+  });
+
+  it("Should escrow deployment_cost from create_deploy_request as well", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const { deployRequestPda } = await createPendingRequest();
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.reservedAmount.toString()).to.equal(DEPLOYMENT_COST.toString());
+    const after = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(after.escrowedAmount.toString()).to.equal(pool.escrowedAmount.add(DEPLOYMENT_COST).toString());
+  });
+
+  it("Should pack the headline pool figures into get_pool_stats", async () => {
+    const stats = await program.methods
+      .getPoolStats()
+      .accounts({ treasuryPool: treasuryPoolPda })
+      .view();
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    expect(stats.totalDeposited.toString()).to.equal(pool.totalDeposited.toString());
+    expect(stats.liquidBalance.toString()).to.equal(pool.liquidBalance.toString());
+    expect(stats.rewardPoolBalance.toString()).to.equal(pool.rewardPoolBalance.toString());
+    expect(stats.platformPoolBalance.toString()).to.equal(pool.platformPoolBalance.toString());
+    expect(stats.rewardPerShare.toString()).to.equal(pool.rewardPerShare.toString());
+    expect(stats.totalRewardsClaimed.toString()).to.equal(pool.totalRewardsClaimed.toString());
+    expect(stats.utilizationBps.toString()).to.equal(
+      pool.totalBorrowed.muln(10000).div(pool.totalDeposited).toString()
+    );
+  });
+
+  it("Should emit DeployRequested alongside DeploymentFundsRequested", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const programHash = crypto.randomBytes(32);
+    const rewardPoolBefore = await provider.connection.getBalance(rewardPoolPda);
+    const platformPoolBefore = await provider.connection.getBalance(platformPoolPda);
+
+    const txSig = await program.methods
+      .requestDeploymentFunds(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        treasuryWallet: pool.treasuryWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([developer, admin])
+      .rpc();
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = Array.from(
+      new anchor.EventParser(program.programId, program.coder).parseLogs(tx.meta.logMessages)
+    );
+    const requested = events.find((e) => e.name === "deployRequested");
+    const fundsRequested = events.find((e) => e.name === "deploymentFundsRequested");
+    expect(requested, "DeployRequested event").to.not.be.undefined;
+    expect(fundsRequested, "DeploymentFundsRequested event").to.not.be.undefined;
+
+    expect(requested.data.totalPayment.toString()).to.equal(fundsRequested.data.totalPayment.toString());
+    expect(requested.data.requestedAt.toString()).to.equal(fundsRequested.data.requestedAt.toString());
+
+    // Paid into the Reward Pool and Platform Pool with the same split as create_deploy_request
+    const upFrontFee = SERVICE_FEE.add(DEPLOYMENT_COST.divn(1000));
+    const { rewardFeeAmount, platformFeeAmount, rewardSplitBps } = fundsRequested.data;
+    expect(rewardSplitBps.toNumber()).to.be.lessThan(10000);
+    expect(rewardFeeAmount.add(platformFeeAmount).toString()).to.equal(upFrontFee.toString());
+    const rewardPoolAfter = await provider.connection.getBalance(rewardPoolPda);
+    const platformPoolAfter = await provider.connection.getBalance(platformPoolPda);
+    expect(rewardPoolAfter - rewardPoolBefore).to.equal(rewardFeeAmount.add(fundsRequested.data.monthlyFee).toNumber());
+    expect(platformPoolAfter - platformPoolBefore).to.equal(platformFeeAmount.toNumber());
+  });
+});
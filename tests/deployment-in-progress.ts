@@ -0,0 +1,191 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deployment In Progress Lock", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function confirmFailure(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "test failure")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should fund only one of two concurrent fund_temporary_wallet calls", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const first = Keypair.generate();
+    const second = Keypair.generate();
+
+    const results = await Promise.allSettled([
+      fund(programHash, deployRequestPda, first.publicKey),
+      fund(programHash, deployRequestPda, second.publicKey),
+    ]);
+
+    const fulfilled = results.filter((r) => r.status === "fulfilled");
+    const rejected = results.filter((r) => r.status === "rejected") as PromiseRejectedResult[];
+    expect(fulfilled).to.have.length(1);
+    expect(rejected).to.have.length(1);
+    expect(rejected[0].reason.toString()).to.include("DeploymentInProgress");
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.fundingLocked).to.equal(true);
+    expect(deployRequest.borrowedAmount.toString()).to.equal(DEPLOYMENT_COST.toString());
+
+    const winner = results[0].status === "fulfilled" ? first : second;
+    expect(deployRequest.ephemeralKey.toBase58()).to.equal(winner.publicKey.toBase58());
+
+    await confirmFailure(programHash, deployRequestPda, winner);
+  });
+
+  it("Should reject mutating a request while funds are in flight", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    try {
+      await program.methods
+        .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          platformPool: platformPoolPda,
+          deployRequest: deployRequestPda,
+          userStats: userStatsPda,
+          developer: developer.publicKey,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have thrown DeploymentInProgress");
+    } catch (err) {
+      expect(err.toString()).to.include("DeploymentInProgress");
+    }
+
+    try {
+      await program.methods
+        .cancelDeployRequest(Array.from(programHash))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          deployRequest: deployRequestPda,
+          userStats: userStatsPda,
+          developer: developer.publicKey,
+        })
+        .signers([developer])
+        .rpc();
+      expect.fail("Should have thrown DeploymentInProgress");
+    } catch (err) {
+      expect(err.toString()).to.include("DeploymentInProgress");
+    }
+
+    await confirmFailure(programHash, deployRequestPda, ephemeralKey);
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ failed: {} });
+    expect(deployRequest.fundingLocked).to.equal(false);
+  });
+});
@@ -0,0 +1,102 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deposit Ordering", () => {
+  // Test accounts
+  const earlyBacker = Keypair.generate();
+  const lateBacker = Keypair.generate();
+
+  const DEPOSIT = new BN(2 * LAMPORTS_PER_SOL);
+  const FEE_REWARD = new BN(0.1 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  function depositPda(backer: Keypair) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function stake(backer: Keypair) {
+    await program.methods
+      .stakeSol(DEPOSIT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: depositPda(backer),
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  async function creditFee() {
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function claimable(backer: Keypair): Promise<BN> {
+    const view = await program.methods
+      .getClaimableRewards()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        lenderStake: depositPda(backer),
+        lender: backer.publicKey,
+      })
+      .view();
+    return view.claimable;
+  }
+
+  before(async () => {
+    await airdrop(earlyBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(lateBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+  });
+
+  it("Should give a fee only to backers deposited before it was credited", async () => {
+    // deposit -> credit -> deposit
+    await stake(earlyBacker);
+    await creditFee();
+    await stake(lateBacker);
+
+    expect((await claimable(earlyBacker)).toNumber()).to.be.greaterThan(0);
+    expect((await claimable(lateBacker)).toNumber()).to.equal(0);
+  });
+
+  it("Should split the next fee between both backers by deposit", async () => {
+    const earlyBefore = await claimable(earlyBacker);
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    await creditFee();
+
+    // Equal deposits earn the same share of the new fee (up to per-share truncation)
+    const share = FEE_REWARD.mul(DEPOSIT).div(pool.totalDeposited);
+    const earlyGain = (await claimable(earlyBacker)).sub(earlyBefore);
+    const lateGain = await claimable(lateBacker);
+    expect(earlyGain.toString()).to.equal(lateGain.toString());
+    expect(share.sub(lateGain).abs().toNumber()).to.be.lessThanOrEqual(1);
+  });
+});
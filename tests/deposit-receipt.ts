@@ -0,0 +1,217 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, airdrop, initializePool } from "./helpers/fixture";
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+describe("Deposit Receipt", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const plainBacker = Keypair.generate();
+  const emergencyBacker = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let receiptMint: PublicKey;
+  let receiptTokenAccount: PublicKey;
+  let insurancePoolPda: PublicKey;
+
+  const DEPOSIT = new BN(1 * LAMPORTS_PER_SOL);
+
+  function unstake(amount: BN, withReceipt: boolean) {
+    return program.methods
+      .unstakeSol(amount, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+        receiptMint: withReceipt ? receiptMint : null,
+        receiptTokenAccount: withReceipt ? receiptTokenAccount : null,
+        tokenProgram: withReceipt ? TOKEN_PROGRAM_ID : null,
+      })
+      .signers([backer]);
+  }
+
+  async function setPaused(pause: boolean) {
+    await program.methods
+      .emergencyPause(pause)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(plainBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(emergencyBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_receipt"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    receiptTokenAccount = getAssociatedTokenAddressSync(receiptMint, backer.publicKey);
+    [insurancePoolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_pool")],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    // Leave the pool unpaused
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    if (pool.emergencyPause) {
+      await setPaused(false);
+    }
+  });
+
+  it("Should mint one frozen receipt when the backer opts in", async () => {
+    await program.methods
+      .stakeSol(DEPOSIT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+        receiptMint,
+        receiptTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([backer])
+      .rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.receiptMint.toString()).to.equal(receiptMint.toString());
+
+    const mint = await getMint(provider.connection, receiptMint);
+    expect(mint.decimals).to.equal(0);
+    expect(mint.supply.toString()).to.equal("1");
+
+    const tokenAccount = await getAccount(provider.connection, receiptTokenAccount);
+    expect(tokenAccount.amount.toString()).to.equal("1");
+    expect(tokenAccount.isFrozen).to.be.true;
+  });
+
+  it("Should let other backers stake without token accounts", async () => {
+    const [plainDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), plainBacker.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .stakeSol(DEPOSIT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: plainDepositPda,
+        lender: plainBacker.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([plainBacker])
+      .rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(plainDepositPda);
+    expect(deposit.receiptMint).to.be.null;
+  });
+
+  it("Should require the receipt accounts for a full withdrawal", async () => {
+    try {
+      await unstake(DEPOSIT, false).rpc();
+      expect.fail("Should have thrown DepositReceiptRequired");
+    } catch (err) {
+      expect(err.toString()).to.include("DepositReceiptRequired");
+    }
+  });
+
+  it("Should burn the receipt on full withdrawal", async () => {
+    await unstake(DEPOSIT, true).rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.receiptMint).to.be.null;
+
+    const mint = await getMint(provider.connection, receiptMint);
+    expect(mint.supply.toString()).to.equal("0");
+  });
+
+  it("Should burn the receipt on a full emergency exit so the position can be closed", async () => {
+    const [depositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), emergencyBacker.publicKey.toBuffer()],
+      program.programId
+    );
+    const [mint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_receipt"), emergencyBacker.publicKey.toBuffer()],
+      program.programId
+    );
+    const tokenAccount = getAssociatedTokenAddressSync(mint, emergencyBacker.publicKey);
+
+    await program.methods
+      .stakeSol(DEPOSIT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: depositPda,
+        lender: emergencyBacker.publicKey,
+        systemProgram: SystemProgram.programId,
+        receiptMint: mint,
+        receiptTokenAccount: tokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .signers([emergencyBacker])
+      .rpc();
+
+    await setPaused(true);
+    await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        insurancePool: insurancePoolPda,
+        lenderStake: depositPda,
+        lender: emergencyBacker.publicKey,
+        systemProgram: SystemProgram.programId,
+        receiptMint: mint,
+        receiptTokenAccount: tokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([emergencyBacker])
+      .rpc();
+    await setPaused(false);
+
+    const deposit = await program.account.backerDeposit.fetch(depositPda);
+    expect(deposit.depositedAmount.toNumber()).to.equal(0);
+    expect(deposit.receiptMint).to.be.null;
+    expect((await getMint(provider.connection, mint)).supply.toString()).to.equal("0");
+
+    // No longer stuck behind StakeNotEmpty
+    await program.methods
+      .closeStake()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        lenderStake: depositPda,
+        lender: emergencyBacker.publicKey,
+      })
+      .signers([emergencyBacker])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(depositPda)).to.be.null;
+  });
+});
@@ -0,0 +1,110 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Developer Blacklist", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+  let blacklistPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createRequest(programHash: Buffer): Promise<PublicKey> {
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        blacklist: blacklistPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return deployRequestPda;
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+    [blacklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("blacklist"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  it("Should reject deploy requests from a blacklisted developer", async () => {
+    await program.methods
+      .blacklistDeveloper()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        blacklist: blacklistPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const blacklist = await program.account.blacklist.fetch(blacklistPda);
+    expect(blacklist.developer.toBase58()).to.equal(developer.publicKey.toBase58());
+
+    try {
+      await createRequest(crypto.randomBytes(32));
+      expect.fail("Should have thrown DeveloperBlacklisted");
+    } catch (err) {
+      expect(err.toString()).to.include("DeveloperBlacklisted");
+    }
+  });
+
+  it("Should accept deploy requests again once the developer is removed", async () => {
+    await program.methods
+      .unblacklistDeveloper()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        blacklist: blacklistPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(blacklistPda)).to.equal(null);
+
+    const deployRequestPda = await createRequest(crypto.randomBytes(32));
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ pendingDeployment: {} });
+  });
+});
@@ -0,0 +1,148 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Developer-Scoped Deploy Requests", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+  const otherDeveloper = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+  let otherUserStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createRequest(
+    programHash: Buffer,
+    requestDeveloper: Keypair,
+    requestUserStats: PublicKey
+  ): Promise<PublicKey> {
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), requestDeveloper.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(requestDeveloper, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(requestDeveloper, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: requestUserStats,
+        developer: requestDeveloper.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return deployRequestPda;
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(otherDeveloper.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+    [otherUserStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), otherDeveloper.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  it("Should give each developer its own request for the same program hash", async () => {
+    const programHash = crypto.randomBytes(32);
+
+    const firstPda = await createRequest(programHash, developer, userStatsPda);
+    const secondPda = await createRequest(programHash, otherDeveloper, otherUserStatsPda);
+    expect(firstPda.toBase58()).to.not.equal(secondPda.toBase58());
+
+    const first = await program.account.deployRequest.fetch(firstPda);
+    const second = await program.account.deployRequest.fetch(secondPda);
+    expect(first.developer.toBase58()).to.equal(developer.publicKey.toBase58());
+    expect(second.developer.toBase58()).to.equal(otherDeveloper.publicKey.toBase58());
+    expect(first.status).to.deep.equal({ pendingDeployment: {} });
+    expect(second.status).to.deep.equal({ pendingDeployment: {} });
+  });
+
+  it("Should reject a deploy_request PDA derived from a different program hash", async () => {
+    const programHash = crypto.randomBytes(32);
+    const [otherHashPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), crypto.randomBytes(32)],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          platformPool: platformPoolPda,
+          deployRequest: otherHashPda,
+          userStats: userStatsPda,
+          developer: developer.publicKey,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have rejected the substituted account");
+    } catch (err) {
+      expect(err.toString()).to.match(/ConstraintSeeds|InvalidRequestId/);
+    }
+
+    // Nothing was created at the substituted address
+    expect(await provider.connection.getAccountInfo(otherHashPda)).to.be.null;
+  });
+
+  it("Should reject a legacy migration when no hash-only request exists", async () => {
+    const programHash = crypto.randomBytes(32);
+    const [legacyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), programHash],
+      program.programId
+    );
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .migrateDeployRequest(Array.from(programHash))
+        .accounts({
+          legacyRequest: legacyPda,
+          deployRequest: deployRequestPda,
+          developer: developer.publicKey,
+          payer: developer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([developer])
+        .rpc();
+      expect.fail("Should have thrown InvalidAccountOwner");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAccountOwner");
+    }
+  });
+});
@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Emergency Withdrawal Fee", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let insurancePoolPda: PublicKey;
+  let backerDepositPda: PublicKey;
+
+  const FEE_BPS = new BN(500); // 5%
+  const STAKE_AMOUNT = new BN(3 * LAMPORTS_PER_SOL);
+  const UNSTAKE_AMOUNT = new BN(LAMPORTS_PER_SOL);
+
+  async function setFeeBps(feeBps: BN) {
+    await program.methods
+      .setEmergencyWithdrawFeeBps(feeBps)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setPaused(pause: boolean) {
+    await program.methods
+      .emergencyPause(pause)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function emergencyUnstake(amount: BN) {
+    return program.methods
+      .emergencyUnstake(amount)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        insurancePool: insurancePoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  async function parseEvent(txSig: string, name: string) {
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    return Array.from(events).find((e) => e.name === name);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [insurancePoolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_pool")],
+      program.programId
+    );
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(STAKE_AMOUNT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    await setFeeBps(FEE_BPS);
+  });
+
+  after(async () => {
+    // Leave the pool unpaused with free emergency exits
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    if (pool.emergencyPause) {
+      await setPaused(false);
+    }
+    await setFeeBps(new BN(0));
+  });
+
+  it("Should reject a fee above the maximum", async () => {
+    try {
+      await setFeeBps(new BN(2001));
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should not charge the fee on a normal unstake", async () => {
+    const txSig = await program.methods
+      .unstakeSol(UNSTAKE_AMOUNT, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    const unstaked = await parseEvent(txSig, "solUnstaked");
+    expect(unstaked, "SolUnstaked event").to.not.be.undefined;
+    expect(unstaked.data.amount.toString()).to.equal(UNSTAKE_AMOUNT.toString());
+  });
+
+  it("Should reject emergency_unstake while the program is not paused", async () => {
+    try {
+      await emergencyUnstake(UNSTAKE_AMOUNT);
+      expect.fail("Should have thrown ProgramNotPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramNotPaused");
+    }
+  });
+
+  it("Should charge the fee on emergency_unstake and route it to the insurance pool", async () => {
+    await setPaused(true);
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const insuranceLamportsBefore = await provider.connection.getBalance(insurancePoolPda);
+
+    const txSig = await emergencyUnstake(UNSTAKE_AMOUNT);
+    const unstaked = await parseEvent(txSig, "emergencyUnstaked");
+    expect(unstaked, "EmergencyUnstaked event").to.not.be.undefined;
+
+    const expectedFee = UNSTAKE_AMOUNT.mul(FEE_BPS).divn(10000);
+    expect(unstaked.data.fee.toString()).to.equal(expectedFee.toString());
+    expect(unstaked.data.amount.toString()).to.equal(UNSTAKE_AMOUNT.sub(expectedFee).toString());
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(
+      poolAfter.insurancePoolBalance.sub(poolBefore.insurancePoolBalance).toString()
+    ).to.equal(expectedFee.toString());
+
+    // First emergency exit also pays rent for the Insurance Pool PDA
+    const insuranceLamportsAfter = await provider.connection.getBalance(insurancePoolPda);
+    expect(insuranceLamportsAfter - insuranceLamportsBefore).to.be.at.least(expectedFee.toNumber());
+  });
+
+  it("Should make emergency exits free when the fee is zero", async () => {
+    await setFeeBps(new BN(0));
+
+    const txSig = await emergencyUnstake(UNSTAKE_AMOUNT);
+    const unstaked = await parseEvent(txSig, "emergencyUnstaked");
+    expect(unstaked.data.fee.toNumber()).to.equal(0);
+    expect(unstaked.data.amount.toString()).to.equal(UNSTAKE_AMOUNT.toString());
+  });
+});
@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Emergency Withdraw", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let insurancePoolPda: PublicKey;
+
+  const STAKE_AMOUNT = new BN(2 * LAMPORTS_PER_SOL);
+  const FEE_BPS = new BN(100); // 1%
+
+  async function setFeeBps(feeBps: BN) {
+    await program.methods
+      .setEmergencyWithdrawFeeBps(feeBps)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setPaused(pause: boolean) {
+    await program.methods
+      .emergencyPause(pause)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function emergencyWithdraw() {
+    return program.methods
+      .emergencyWithdraw()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        insurancePool: insurancePoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function parseEvent(txSig: string, name: string) {
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    return Array.from(events).find((e) => e.name === name);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [insurancePoolPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_pool")],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(STAKE_AMOUNT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    await setFeeBps(FEE_BPS);
+  });
+
+  after(async () => {
+    // Leave the pool unpaused with free emergency exits
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    if (pool.emergencyPause) {
+      await setPaused(false);
+    }
+    await setFeeBps(new BN(0));
+  });
+
+  it("Should reject emergency_withdraw while the program is not paused", async () => {
+    try {
+      await emergencyWithdraw();
+      expect.fail("Should have thrown ProgramNotPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramNotPaused");
+    }
+  });
+
+  it("Should return the whole deposit less the emergency fee during an emergency pause", async () => {
+    await setPaused(true);
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const txSig = await emergencyWithdraw();
+
+    // Same fee as emergency_unstake, so neither exit is cheaper
+    const expectedFee = STAKE_AMOUNT.mul(FEE_BPS).divn(10000);
+    const unstaked = await parseEvent(txSig, "emergencyUnstaked");
+    expect(unstaked, "EmergencyUnstaked event").to.not.be.undefined;
+    expect(unstaked.data.fee.toString()).to.equal(expectedFee.toString());
+    expect(unstaked.data.amount.toString()).to.equal(STAKE_AMOUNT.sub(expectedFee).toString());
+    expect(unstaked.data.remainingStaked.toNumber()).to.equal(0);
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolBefore.totalDeposited.sub(poolAfter.totalDeposited).toString()).to.equal(STAKE_AMOUNT.toString());
+    expect(poolBefore.liquidBalance.sub(poolAfter.liquidBalance).toString()).to.equal(STAKE_AMOUNT.toString());
+    expect(
+      poolAfter.insurancePoolBalance.sub(poolBefore.insurancePoolBalance).toString()
+    ).to.equal(expectedFee.toString());
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.depositedAmount.toNumber()).to.equal(0);
+    expect(deposit.isActive).to.equal(false);
+
+    // Nothing left to withdraw
+    try {
+      await emergencyWithdraw();
+      expect.fail("Should have thrown InsufficientStake");
+    } catch (err) {
+      expect(err.toString()).to.include("InsufficientStake");
+    }
+  });
+});
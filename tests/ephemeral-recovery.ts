@@ -0,0 +1,185 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Ephemeral Key Recovery", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  const DEFAULT_TIMEOUT_SECS = new BN(24 * 60 * 60);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function forceRecover(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    return program.methods
+      .forceRecoverEphemeral(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  }
+
+  async function setRecoveryTimeout(timeoutSecs: BN) {
+    await program.methods
+      .setEphemeralRecoveryTimeout(timeoutSecs)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  after(async () => {
+    await setRecoveryTimeout(DEFAULT_TIMEOUT_SECS);
+  });
+
+  it("Should reject a zero recovery timeout", async () => {
+    try {
+      await setRecoveryTimeout(new BN(0));
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should reject recovery before the timeout has elapsed", async () => {
+    await setRecoveryTimeout(DEFAULT_TIMEOUT_SECS);
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    try {
+      await forceRecover(programHash, deployRequestPda, ephemeralKey);
+      expect.fail("Should have thrown SessionNotExpired");
+    } catch (err) {
+      expect(err.toString()).to.include("SessionNotExpired");
+    }
+
+    // Shorten the timeout so the hung request can be cleaned up
+    await setRecoveryTimeout(new BN(1));
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await forceRecover(programHash, deployRequestPda, ephemeralKey);
+  });
+
+  it("Should sweep the ephemeral key and fail the request after the timeout", async () => {
+    await setRecoveryTimeout(new BN(1));
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const swept = await provider.connection.getBalance(ephemeralKey.publicKey);
+    expect(swept).to.equal(DEPLOYMENT_COST.toNumber());
+
+    await forceRecover(programHash, deployRequestPda, ephemeralKey);
+
+    expect(await provider.connection.getBalance(ephemeralKey.publicKey)).to.equal(0);
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolAfter.liquidBalance.sub(poolBefore.liquidBalance).toNumber()).to.equal(swept);
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ failed: {} });
+    expect(deployRequest.fundingLocked).to.equal(false);
+
+    const userStats = await program.account.userDeployStats.fetch(userStatsPda);
+    expect(userStats.activeSessions).to.equal(0);
+  });
+});
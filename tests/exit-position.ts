@@ -0,0 +1,162 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Exit Position", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const DEPOSIT = new BN(3 * LAMPORTS_PER_SOL);
+  const FEE_REWARD = new BN(0.05 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  function exitPosition() {
+    return program.methods
+      .exitPosition()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer]);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(DEPOSIT, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Should refuse to close a position that still holds a deposit", async () => {
+    try {
+      await program.methods
+        .closeStake()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          lenderStake: backerDepositPda,
+          lender: backer.publicKey,
+        })
+        .signers([backer])
+        .rpc();
+      expect.fail("Should have thrown StakeNotEmpty");
+    } catch (err) {
+      expect(err.toString()).to.include("StakeNotEmpty");
+    }
+  });
+
+  it("Should emit Claimed and SolUnstaked for the whole position", async () => {
+    const view = await program.methods
+      .getClaimableRewards()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .view();
+    expect(view.claimable.toNumber()).to.be.greaterThan(0);
+
+    const { events } = await exitPosition().simulate();
+    const claimed = events.find((e) => e.name === "claimed");
+    const unstaked = events.find((e) => e.name === "solUnstaked");
+    expect(claimed.data.amount.toString()).to.equal(view.claimable.toString());
+    expect(unstaked.data.amount.toString()).to.equal(DEPOSIT.toString());
+    expect(unstaked.data.remainingStaked.toNumber()).to.equal(0);
+  });
+
+  it("Should pay rewards and principal and leave nothing stranded", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const balanceBefore = await provider.connection.getBalance(backer.publicKey);
+
+    await exitPosition().rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.depositedAmount.toNumber()).to.equal(0);
+    expect(deposit.pendingRewards.toNumber()).to.equal(0);
+    expect(deposit.isActive).to.be.false;
+    expect(deposit.claimedTotal.toNumber()).to.be.greaterThan(0);
+
+    const balanceAfter = await provider.connection.getBalance(backer.publicKey);
+    const received = balanceAfter - balanceBefore;
+    // Principal + rewards, less the transaction fee
+    expect(received).to.be.greaterThan(DEPOSIT.toNumber());
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolBefore.totalDeposited.sub(poolAfter.totalDeposited).toString()).to.equal(DEPOSIT.toString());
+  });
+
+  it("Should reject a second exit with nothing deposited", async () => {
+    try {
+      await exitPosition().rpc();
+      expect.fail("Should have thrown InsufficientStake");
+    } catch (err) {
+      expect(err.toString()).to.include("InsufficientStake");
+    }
+  });
+
+  it("Should close the exited position and return its rent", async () => {
+    const rent = await provider.connection.getBalance(backerDepositPda);
+    expect(rent).to.be.greaterThan(0);
+    const balanceBefore = await provider.connection.getBalance(backer.publicKey);
+
+    await program.methods
+      .closeStake()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .signers([backer])
+      .rpc({ commitment: "confirmed" });
+
+    const balanceAfter = await provider.connection.getBalance(backer.publicKey, "confirmed");
+    // Rent back, less the transaction fee
+    expect(balanceAfter - balanceBefore).to.be.greaterThan(rent - 10_000);
+    expect(await provider.connection.getAccountInfo(backerDepositPda, "confirmed")).to.be.null;
+  });
+});
@@ -0,0 +1,255 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Exit Preview", () => {
+  // Test accounts
+  const unlockedBacker = Keypair.generate();
+  const lockedBacker = Keypair.generate();
+  const proRataBacker = Keypair.generate();
+  const developer = Keypair.generate();
+
+  const ONE_DAY = 24 * 60 * 60;
+  const STAKE_AMOUNT = new BN(3 * LAMPORTS_PER_SOL);
+  const FEE_REWARD = new BN(0.3 * LAMPORTS_PER_SOL);
+  const DEPLOYMENT_COST = new BN(3 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = new BN(0.02 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  function depositPda(backer: Keypair): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function stake(backer: Keypair, lockPeriod: number) {
+    await program.methods
+      .stakeSol(STAKE_AMOUNT, new BN(lockPeriod))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: depositPda(backer),
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  async function previewExit(backer: Keypair) {
+    const simulation = await program.methods
+      .previewExit()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: depositPda(backer),
+        lender: backer.publicKey,
+      })
+      .simulate();
+    const event = simulation.events.find((e) => e.name === "exitPreview");
+    expect(event, "ExitPreview event").to.not.be.undefined;
+    return event.data;
+  }
+
+  async function parseEvent(txSig: string, name: string) {
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    return Array.from(events).find((e) => e.name === name);
+  }
+
+  // Full exit: unstake the whole deposit (accepting any penalty), then claim everything
+  // Returns the lamports actually paid out by the program
+  async function exit(backer: Keypair): Promise<BN> {
+    const deposit = await program.account.backerDeposit.fetch(depositPda(backer));
+    const unstakeSig = await program.methods
+      .unstakeSol(deposit.depositedAmount, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: depositPda(backer),
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+    const unstaked = await parseEvent(unstakeSig, "solUnstaked");
+
+    const claimSig = await program.methods
+      .claimRewards(null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: depositPda(backer),
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+    const claimed = await parseEvent(claimSig, "rewardsClaimed");
+
+    return unstaked.data.amount.add(claimed.data.amount);
+  }
+
+  // Lose a deployment's borrowed funds so the pool falls short of its obligations
+  async function induceShortfall() {
+    const programHash = crypto.randomBytes(32);
+    const ephemeralKey = Keypair.generate();
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+    const [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const drained = await provider.connection.getBalance(ephemeralKey.publicKey);
+    await transfer(ephemeralKey, Keypair.generate().publicKey, drained);
+
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "ephemeral key drained")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+
+    await program.methods
+      .flagUndercollateralization()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        caller: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(unlockedBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(lockedBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(proRataBacker.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+
+    await stake(unlockedBacker, 0);
+    await stake(lockedBacker, ONE_DAY);
+    await stake(proRataBacker, 0);
+
+    // Known fee credit so every backer has rewards to claim
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Should quote an unlocked exit exactly", async () => {
+    const preview = await previewExit(unlockedBacker);
+    expect(preview.principal.toString()).to.equal(STAKE_AMOUNT.toString());
+    expect(preview.penalty.toNumber()).to.equal(0);
+    expect(preview.haircut.toNumber()).to.equal(0);
+    expect(preview.rewards.toNumber()).to.be.greaterThan(0);
+    expect(preview.net.toString()).to.equal(preview.principal.add(preview.rewards).toString());
+
+    const paid = await exit(unlockedBacker);
+    expect(paid.toString()).to.equal(preview.net.toString());
+  });
+
+  it("Should include the early-unstake penalty for a locked stake", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const preview = await previewExit(lockedBacker);
+    const expectedPenalty = STAKE_AMOUNT.mul(pool.penaltyBps).divn(10000);
+    expect(preview.penalty.toString()).to.equal(expectedPenalty.toString());
+
+    const paid = await exit(lockedBacker);
+    expect(paid.toString()).to.equal(preview.net.toString());
+  });
+
+  it("Should apply the pro-rata haircut while undercollateralized", async () => {
+    await induceShortfall();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.undercollateralized).to.be.true;
+
+    const preview = await previewExit(proRataBacker);
+    expect(preview.haircut.toNumber()).to.be.greaterThan(0);
+
+    const paid = await exit(proRataBacker);
+    expect(paid.toString()).to.equal(preview.net.toString());
+  });
+});
@@ -0,0 +1,215 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Deployment Failure Refund", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  const PREPAID_MONTHS = 6;
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(months: number): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE.muln(months)).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, months, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function confirmFailure(
+    programHash: Buffer,
+    deployRequestPda: PublicKey,
+    ephemeralKey: Keypair,
+    reason = "refund test"
+  ) {
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), reason)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should refund every prepaid month on failure and leave the credited service fee with backers", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest(PREPAID_MONTHS);
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    let deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.initialMonths).to.equal(PREPAID_MONTHS);
+
+    // Backers are staked, so the service fee is already owed to them and is not clawed back
+    const expectedRefund = MONTHLY_FEE.muln(PREPAID_MONTHS);
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const developerBefore = await provider.connection.getBalance(developer.publicKey);
+
+    await confirmFailure(programHash, deployRequestPda, ephemeralKey);
+
+    const developerAfter = await provider.connection.getBalance(developer.publicKey);
+    expect(developerAfter - developerBefore).to.equal(expectedRefund.toNumber());
+
+    // Prepaid months come out of escrow; rewards owed to backers stay backed
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolBefore.prepaidFeesBalance.sub(poolAfter.prepaidFeesBalance).toString()).to.equal(
+      MONTHLY_FEE.muln(PREPAID_MONTHS).toString()
+    );
+    expect(poolAfter.rewardPoolBalance.toString()).to.equal(poolBefore.rewardPoolBalance.toString());
+    expect(poolAfter.rewardPoolBalance.gte(poolAfter.totalClaimable)).to.be.true;
+
+    deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ failed: {} });
+    expect(deployRequest.prepaidBalance.toNumber()).to.equal(0);
+  });
+
+  it("Should let the developer retry a failed request on the same PDA", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest(PREPAID_MONTHS);
+    const ephemeralKey = Keypair.generate();
+
+    const retry = () =>
+      program.methods
+        .retryDeployment(Array.from(programHash))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          deployRequest: deployRequestPda,
+          userStats: userStatsPda,
+          developer: developer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([developer])
+        .rpc();
+
+    // Only a Failed request can be retried
+    try {
+      await retry();
+      expect.fail("Should have thrown InvalidRequestStatus");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidRequestStatus");
+    }
+
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+    await confirmFailure(programHash, deployRequestPda, ephemeralKey);
+
+    const payment = SERVICE_FEE.add(MONTHLY_FEE.muln(PREPAID_MONTHS));
+    const rewardPoolBefore = await provider.connection.getBalance(rewardPoolPda);
+
+    await retry();
+
+    const rewardPoolAfter = await provider.connection.getBalance(rewardPoolPda);
+    expect(rewardPoolAfter - rewardPoolBefore).to.equal(payment.toNumber());
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ pendingDeployment: {} });
+    expect(deployRequest.ephemeralKey).to.be.null;
+    expect(deployRequest.deployedProgramId).to.be.null;
+    expect(deployRequest.prepaidBalance.toString()).to.equal(MONTHLY_FEE.muln(PREPAID_MONTHS).toString());
+  });
+
+  it("Should reject a failure reason longer than 256 bytes", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest(1);
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    try {
+      await confirmFailure(programHash, deployRequestPda, ephemeralKey, "x".repeat(257));
+      expect.fail("Should have thrown ReasonTooLong");
+    } catch (err) {
+      expect(err.toString()).to.include("ReasonTooLong");
+    }
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ pendingDeployment: {} });
+
+    await confirmFailure(programHash, deployRequestPda, ephemeralKey, "x".repeat(256));
+  });
+});
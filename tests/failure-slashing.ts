@@ -0,0 +1,173 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Repeated Failure Slashing", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  const SLASH_THRESHOLD = 1;
+  const SLASH_BPS = 2000;
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(months: number): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE.muln(months)).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, months, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setFailureSlash(threshold: number, slashBps: number) {
+    await program.methods
+      .setFailureSlash(threshold, new BN(slashBps))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function failDeployment(): Promise<{ developerGain: number; platformGain: number }> {
+    const { programHash, deployRequestPda } = await createPendingRequest(1);
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    const developerBefore = await provider.connection.getBalance(developer.publicKey);
+    const platformBefore = await provider.connection.getBalance(platformPoolPda);
+    await confirmFailure(programHash, deployRequestPda, ephemeralKey);
+    const developerAfter = await provider.connection.getBalance(developer.publicKey);
+    const platformAfter = await provider.connection.getBalance(platformPoolPda);
+
+    return { developerGain: developerAfter - developerBefore, platformGain: platformAfter - platformBefore };
+  }
+
+  async function confirmFailure(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "slashing test")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployments below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    await setFailureSlash(SLASH_THRESHOLD, SLASH_BPS);
+  });
+
+  after(async () => {
+    await setFailureSlash(0, 0);
+  });
+
+  it("Should refund in full up to the failure threshold", async () => {
+    const { developerGain, platformGain } = await failDeployment();
+
+    // The service fee is already owed to the staked backer; only the prepaid month comes back
+    expect(developerGain).to.equal(MONTHLY_FEE.toNumber());
+    expect(platformGain).to.equal(0);
+
+    const userStats = await program.account.userDeployStats.fetch(userStatsPda);
+    expect(userStats.failedDeploys).to.equal(1);
+  });
+
+  it("Should slash the refund into the Platform Pool past the threshold", async () => {
+    const refund = MONTHLY_FEE;
+    const slashed = refund.muln(SLASH_BPS).divn(10000);
+
+    const { developerGain, platformGain } = await failDeployment();
+
+    expect(developerGain).to.equal(refund.sub(slashed).toNumber());
+    expect(platformGain).to.equal(slashed.toNumber());
+
+    const userStats = await program.account.userDeployStats.fetch(userStatsPda);
+    expect(userStats.failedDeploys).to.equal(2);
+  });
+});
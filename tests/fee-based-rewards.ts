@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { D2dProgramSol } from "../target/types/d2d_program_sol";
 import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { admin, initializePool } from "./helpers/fixture";
 import { expect } from "chai";
 
 describe("Fee-Based Reward System", () => {
@@ -11,7 +12,6 @@ describe("Fee-Based Reward System", () => {
   const program = anchor.workspace.D2dProgramSol as Program<D2dProgramSol>;
   
   // Test accounts
-  const admin = Keypair.generate();
   const devWallet = Keypair.generate();
   const backer1 = Keypair.generate();
   const backer2 = Keypair.generate();
@@ -30,7 +30,6 @@ describe("Fee-Based Reward System", () => {
 
   before(async () => {
     // Airdrop SOL to test accounts
-    await provider.connection.requestAirdrop(admin.publicKey, 10 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(backer1.publicKey, 10 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(backer2.publicKey, 10 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(devWallet.publicKey, 1 * LAMPORTS_PER_SOL);
@@ -64,24 +63,8 @@ describe("Fee-Based Reward System", () => {
       program.programId
     );
 
-    // Initialize treasury pool
-    try {
-      await program.methods
-        .initialize(0, devWallet.publicKey) // initial_apy = 0 (not used)
-        .accounts({
-          treasuryPool: treasuryPoolPda,
-          rewardPool: rewardPoolPda,
-          platformPool: platformPoolPda,
-          admin: admin.publicKey,
-          devWallet: devWallet.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([admin])
-        .rpc();
-    } catch (err) {
-      // May already be initialized
-      console.log("Treasury pool may already be initialized");
-    }
+    // Initialize treasury pool (shared with the other suites)
+    await initializePool();
   });
 
   describe("deposit()", () => {
@@ -213,7 +196,7 @@ describe("Fee-Based Reward System", () => {
 
       // Claim rewards
       await program.methods
-        .claimRewards()
+        .claimRewards(null)
         .accounts({
           treasuryPool: treasuryPoolPda,
           rewardPool: rewardPoolPda,
@@ -257,7 +240,7 @@ describe("Fee-Based Reward System", () => {
       // Attempting to claim should fail or do nothing
       try {
         await program.methods
-          .claimRewards()
+          .claimRewards(null)
           .accounts({
             treasuryPool: treasuryPoolPda,
             rewardPool: rewardPoolPda,
@@ -0,0 +1,80 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Fee Rates", () => {
+  // Test accounts
+  const outsider = Keypair.generate();
+
+  const DEFAULT_REWARD_FEE_BPS = new BN(100);
+  const DEFAULT_PLATFORM_FEE_BPS = new BN(10);
+
+  async function setFeeRates(rewardFeeBps: BN, platformFeeBps: BN, signer: Keypair = admin) {
+    return program.methods
+      .setFeeRates(rewardFeeBps, platformFeeBps)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(outsider.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+  });
+
+  after(async () => {
+    await setFeeRates(DEFAULT_REWARD_FEE_BPS, DEFAULT_PLATFORM_FEE_BPS);
+  });
+
+  it("Should update both fee rates and emit FeeRatesUpdated", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const txSig = await setFeeRates(new BN(200), new BN(20));
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const updated = Array.from(events).find((e) => e.name === "feeRatesUpdated");
+    expect(updated, "FeeRatesUpdated event").to.not.be.undefined;
+    expect(updated.data.oldRewardFeeBps.toString()).to.equal(poolBefore.rewardFeeBps.toString());
+    expect(updated.data.oldPlatformFeeBps.toString()).to.equal(poolBefore.platformFeeBps.toString());
+    expect(updated.data.newRewardFeeBps.toNumber()).to.equal(200);
+    expect(updated.data.newPlatformFeeBps.toNumber()).to.equal(20);
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.rewardFeeBps.toNumber()).to.equal(200);
+    expect(pool.platformFeeBps.toNumber()).to.equal(20);
+  });
+
+  it("Should reject a rate above 10%", async () => {
+    for (const [reward, platform] of [
+      [1001, 10],
+      [100, 1001],
+    ]) {
+      try {
+        await setFeeRates(new BN(reward), new BN(platform));
+        expect.fail("Should have thrown InvalidAmount");
+      } catch (err) {
+        expect(err.toString()).to.include("InvalidAmount");
+      }
+    }
+  });
+
+  it("Should reject a non-admin signer", async () => {
+    try {
+      await setFeeRates(new BN(100), new BN(10), outsider);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+});
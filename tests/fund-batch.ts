@@ -0,0 +1,148 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Fund Temporary Wallets Batch", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createRequest(): Promise<PublicKey> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    return deployRequestPda;
+  }
+
+  function fundBatch(pairs: [PublicKey, PublicKey][]) {
+    return program.methods
+      .fundTemporaryWalletsBatch(true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+      })
+      .remainingAccounts(
+        pairs.flatMap(([request, wallet]) => [
+          { pubkey: request, isWritable: true, isSigner: false },
+          { pubkey: wallet, isWritable: true, isSigner: false },
+        ])
+      )
+      .signers([admin]);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should fund every request in the batch", async () => {
+    const requests = [await createRequest(), await createRequest()];
+    const wallets = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const { events } = await fundBatch([
+      [requests[0], wallets[0]],
+      [requests[1], wallets[1]],
+    ]).simulate();
+    expect(events.filter((e) => e.name === "temporaryWalletFunded")).to.have.length(2);
+
+    await fundBatch([
+      [requests[0], wallets[0]],
+      [requests[1], wallets[1]],
+    ]).rpc();
+
+    for (let i = 0; i < 2; i++) {
+      const request = await program.account.deployRequest.fetch(requests[i]);
+      expect(request.ephemeralKey.toString()).to.equal(wallets[i].toString());
+      expect(request.borrowedAmount.toString()).to.equal(DEPLOYMENT_COST.toString());
+      expect(request.fundingLocked).to.be.true;
+      expect(await provider.connection.getBalance(wallets[i])).to.equal(DEPLOYMENT_COST.toNumber());
+    }
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolAfter.totalBorrowed.sub(poolBefore.totalBorrowed).toString()).to.equal(
+      DEPLOYMENT_COST.muln(2).toString()
+    );
+  });
+
+  it("Should fund nothing when one pair in the batch fails", async () => {
+    const request = await createRequest();
+    const wallet = Keypair.generate().publicKey;
+
+    // The same request twice: the second pair is already funding_locked
+    try {
+      await fundBatch([
+        [request, wallet],
+        [request, wallet],
+      ]).rpc();
+      expect.fail("Should have thrown DeploymentInProgress");
+    } catch (err) {
+      expect(err.toString()).to.include("DeploymentInProgress");
+    }
+
+    const deployRequest = await program.account.deployRequest.fetch(request);
+    expect(deployRequest.fundingLocked).to.be.false;
+    expect(await provider.connection.getBalance(wallet)).to.equal(0);
+  });
+});
@@ -0,0 +1,176 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Governance", () => {
+  // Test accounts
+  const whale = Keypair.generate();
+  const minnow = Keypair.generate();
+  const latecomer = Keypair.generate();
+
+  function depositPda(backer: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function proposalPda(proposalId: BN): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), proposalId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  }
+
+  async function stake(backer: Keypair, amount: number) {
+    await program.methods
+      .stakeSol(new BN(amount), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: depositPda(backer.publicKey),
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  async function createProposal(param: any, newValue: number): Promise<BN> {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const proposalId = pool.proposalCount;
+    await program.methods
+      .createProposal(param, new BN(newValue))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        proposal: proposalPda(proposalId),
+        proposer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    return proposalId;
+  }
+
+  async function vote(voter: Keypair, proposalId: BN, support: boolean) {
+    const proposal = proposalPda(proposalId);
+    const [voteRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote_record"), proposal.toBuffer(), voter.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .vote(proposalId, support)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        proposal,
+        lenderStake: depositPda(voter.publicKey),
+        voteRecord,
+        voter: voter.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(whale.publicKey, 20 * LAMPORTS_PER_SOL);
+    await airdrop(minnow.publicKey, 2 * LAMPORTS_PER_SOL);
+    await airdrop(latecomer.publicKey, 2 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+
+    await stake(whale, 15 * LAMPORTS_PER_SOL);
+    await stake(minnow, 1 * LAMPORTS_PER_SOL);
+
+    // Votes only count stake made strictly before proposal creation
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+  });
+
+  it("Should tally a passing proposal by deposit weight", async () => {
+    const proposalId = await createProposal({ rewardFeeBps: {} }, 150);
+
+    await vote(whale, proposalId, true);
+    await vote(minnow, proposalId, false);
+
+    const proposal = await program.account.proposal.fetch(proposalPda(proposalId));
+    expect(proposal.votesFor.toNumber()).to.equal(15 * LAMPORTS_PER_SOL);
+    expect(proposal.votesAgainst.toNumber()).to.equal(1 * LAMPORTS_PER_SOL);
+    expect(proposal.votesFor.gt(proposal.votesAgainst)).to.be.true;
+    expect(proposal.executed).to.be.false;
+
+    // Cannot execute until the voting deadline has passed
+    try {
+      await program.methods
+        .executeProposal(proposalId)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          proposal: proposalPda(proposalId),
+          executor: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have thrown VotingStillOpen");
+    } catch (error) {
+      expect(error.toString()).to.include("VotingStillOpen");
+    }
+  });
+
+  it("Should reject double votes and stake added after creation", async () => {
+    const proposalId = await createProposal({ platformFeeBps: {} }, 20);
+
+    await vote(minnow, proposalId, true);
+    try {
+      await vote(minnow, proposalId, true);
+      expect.fail("Should have rejected a second vote");
+    } catch (error) {
+      // VoteRecord PDA already exists
+      expect(error.toString()).to.not.include("Should have rejected");
+    }
+
+    await stake(latecomer, 1 * LAMPORTS_PER_SOL);
+    try {
+      await vote(latecomer, proposalId, true);
+      expect.fail("Should have thrown StakeAfterProposal");
+    } catch (error) {
+      expect(error.toString()).to.include("StakeAfterProposal");
+    }
+  });
+
+  it("Should leave a failing proposal unexecutable", async () => {
+    const proposalId = await createProposal({ rewardFeeBps: {} }, 500);
+
+    await vote(whale, proposalId, false);
+    await vote(minnow, proposalId, true);
+
+    const proposal = await program.account.proposal.fetch(proposalPda(proposalId));
+    expect(proposal.votesAgainst.gt(proposal.votesFor)).to.be.true;
+
+    try {
+      await program.methods
+        .executeProposal(proposalId)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          proposal: proposalPda(proposalId),
+          executor: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should not execute a rejected proposal");
+    } catch (error) {
+      expect(error.toString()).to.not.include("Should not execute");
+    }
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.rewardFeeBps.toNumber()).to.not.equal(500);
+  });
+
+  it("Should reject out-of-range fee proposals", async () => {
+    try {
+      await createProposal({ platformFeeBps: {} }, 5000);
+      expect.fail("Should have thrown InvalidProposalValue");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidProposalValue");
+    }
+  });
+});
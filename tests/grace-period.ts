@@ -0,0 +1,116 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Subscription Grace Period", () => {
+  // Test accounts
+  const outsider = Keypair.generate();
+
+  const ONE_DAY = 24 * 60 * 60;
+  const DEFAULT_GRACE_PERIOD_SECS = 7 * ONE_DAY;
+  const MAX_GRACE_PERIOD_SECS = 90 * ONE_DAY;
+  const MAX_LAPSE_SECS = 365 * ONE_DAY;
+
+  async function setGracePeriod(gracePeriodSecs: number, signer: Keypair = admin) {
+    return program.methods
+      .setGracePeriod(new BN(gracePeriodSecs))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function setMaxLapse(maxLapseSecs: number, signer: Keypair = admin) {
+    return program.methods
+      .setMaxLapse(new BN(maxLapseSecs))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: signer.publicKey,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(outsider.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+  });
+
+  after(async () => {
+    await setGracePeriod(DEFAULT_GRACE_PERIOD_SECS);
+    await setMaxLapse(0);
+  });
+
+  it("Should update the grace period and emit GracePeriodUpdated", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const txSig = await setGracePeriod(3 * ONE_DAY);
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const updated = Array.from(events).find((e) => e.name === "gracePeriodUpdated");
+    expect(updated, "GracePeriodUpdated event").to.not.be.undefined;
+    expect(updated.data.oldGracePeriodSecs.toString()).to.equal(poolBefore.gracePeriodSecs.toString());
+    expect(updated.data.newGracePeriodSecs.toNumber()).to.equal(3 * ONE_DAY);
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.gracePeriodSecs.toNumber()).to.equal(3 * ONE_DAY);
+  });
+
+  it("Should allow suspending immediately on expiry (zero grace)", async () => {
+    await setGracePeriod(0);
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.gracePeriodSecs.toNumber()).to.equal(0);
+  });
+
+  it("Should reject a negative or over-long grace period", async () => {
+    for (const gracePeriodSecs of [-1, MAX_GRACE_PERIOD_SECS + 1]) {
+      try {
+        await setGracePeriod(gracePeriodSecs);
+        expect.fail("Should have thrown InvalidAmount");
+      } catch (err) {
+        expect(err.toString()).to.include("InvalidAmount");
+      }
+    }
+  });
+
+  it("Should reject a non-admin signer", async () => {
+    try {
+      await setGracePeriod(ONE_DAY, outsider);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should set the reinstatement window for suspended requests", async () => {
+    await setMaxLapse(30 * ONE_DAY);
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.maxLapseSecs.toNumber()).to.equal(30 * ONE_DAY);
+
+    for (const maxLapseSecs of [-1, MAX_LAPSE_SECS + 1]) {
+      try {
+        await setMaxLapse(maxLapseSecs);
+        expect.fail("Should have thrown InvalidAmount");
+      } catch (err) {
+        expect(err.toString()).to.include("InvalidAmount");
+      }
+    }
+
+    try {
+      await setMaxLapse(ONE_DAY, outsider);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+});
@@ -0,0 +1,57 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { D2dProgramSol } from "../../target/types/d2d_program_sol";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+
+// Shared setup for every suite. They all run against one validator and one TreasuryPool,
+// so the pool is administered by the provider wallet instead of a per-suite keypair.
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.D2dProgramSol as Program<D2dProgramSol>;
+
+export const admin: Keypair = (provider.wallet as anchor.Wallet).payer;
+
+export const [treasuryPoolPda] = PublicKey.findProgramAddressSync(
+  [Buffer.from("treasury_pool")],
+  program.programId
+);
+export const [rewardPoolPda] = PublicKey.findProgramAddressSync(
+  [Buffer.from("reward_pool")],
+  program.programId
+);
+export const [platformPoolPda] = PublicKey.findProgramAddressSync(
+  [Buffer.from("platform_pool")],
+  program.programId
+);
+
+export async function airdrop(publicKey: PublicKey, amount: number) {
+  const signature = await provider.connection.requestAirdrop(publicKey, amount);
+  await provider.connection.confirmTransaction(signature);
+}
+
+// Initialize the TreasuryPool unless an earlier suite already did
+// Fails instead of carrying on when the pool belongs to another admin: every admin call would be Unauthorized
+export async function initializePool() {
+  const pool = await program.account.treasuryPool.fetchNullable(treasuryPoolPda);
+  if (pool === null) {
+    const devWallet = Keypair.generate().publicKey;
+    await program.methods
+      .initialize(new BN(0), devWallet, 256)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        admin: admin.publicKey,
+        devWallet,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    return;
+  }
+  if (!pool.admin.equals(admin.publicKey)) {
+    throw new Error(
+      `TreasuryPool admin is ${pool.admin.toBase58()}, expected the provider wallet ${admin.publicKey.toBase58()}`
+    );
+  }
+}
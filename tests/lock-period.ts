@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Lock-Period Staking", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const ONE_DAY = 24 * 60 * 60;
+  const STAKE_AMOUNT = new BN(2 * LAMPORTS_PER_SOL);
+  const UNSTAKE_AMOUNT = new BN(LAMPORTS_PER_SOL);
+
+  async function unstake(amount: BN, acceptPenalty: boolean) {
+    return program.methods
+      .unstakeSol(amount, acceptPenalty)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  it("Should record locked_until when staking with a lock period", async () => {
+    const before = Math.floor(Date.now() / 1000);
+
+    await program.methods
+      .stakeSol(STAKE_AMOUNT, new BN(ONE_DAY))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    // Allow for clock drift between the validator and this machine
+    expect(deposit.lockedUntil.toNumber()).to.be.greaterThan(before + ONE_DAY - 60);
+  });
+
+  it("Should reject an early unstake without accept_penalty", async () => {
+    try {
+      await unstake(UNSTAKE_AMOUNT, false);
+      expect.fail("Should have thrown StakeLocked");
+    } catch (err) {
+      expect(err.toString()).to.include("StakeLocked");
+    }
+  });
+
+  it("Should apply the penalty and route it to the reward pool", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const rewardPoolBalanceBefore = await provider.connection.getBalance(rewardPoolPda);
+
+    const txSig = await unstake(UNSTAKE_AMOUNT, true);
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const unstaked = Array.from(events).find((e) => e.name === "solUnstaked");
+    expect(unstaked, "SolUnstaked event").to.not.be.undefined;
+
+    const expectedPenalty = UNSTAKE_AMOUNT.mul(poolBefore.penaltyBps).divn(10000);
+    expect(unstaked.data.penalty.toString()).to.equal(expectedPenalty.toString());
+    expect(unstaked.data.amount.toString()).to.equal(
+      UNSTAKE_AMOUNT.sub(expectedPenalty).toString()
+    );
+
+    const rewardPoolBalanceAfter = await provider.connection.getBalance(rewardPoolPda);
+    expect(rewardPoolBalanceAfter - rewardPoolBalanceBefore).to.equal(expectedPenalty.toNumber());
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    // Penalty is credited to backers, less any reward_per_share truncation dust
+    expect(
+      poolAfter.rewardPoolBalance
+        .add(poolAfter.undistributedDust)
+        .sub(poolBefore.rewardPoolBalance.add(poolBefore.undistributedDust))
+        .toString()
+    ).to.equal(expectedPenalty.toString());
+  });
+
+  it("Should reject a penalty above the maximum", async () => {
+    try {
+      await program.methods
+        .setPenaltyBps(new BN(5001))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.match(/InvalidAmount|Unauthorized/);
+    }
+  });
+});
@@ -0,0 +1,124 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Minimum Claim", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const FEE_REWARD = new BN(0.01 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function setMinClaim(minClaimAmount: BN) {
+    await program.methods
+      .setMinClaim(minClaimAmount)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    // Small fee credit so the backer has a little to claim
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  after(async () => {
+    await setMinClaim(new BN(0));
+  });
+
+  it("Should reject a claim below min_claim_amount", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    const PRECISION = new BN("1000000000000");
+    const claimable = deposit.pendingRewards.add(
+      deposit.depositedAmount.mul(pool.rewardPerShare).sub(deposit.rewardDebt).div(PRECISION)
+    );
+    expect(claimable.toNumber()).to.be.greaterThan(0);
+
+    await setMinClaim(claimable.addn(1));
+
+    try {
+      await program.methods
+        .claimRewards(null)
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          lenderStake: backerDepositPda,
+          lender: backer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([backer])
+        .rpc();
+      expect.fail("Should have thrown ClaimBelowThreshold");
+    } catch (err) {
+      expect(err.toString()).to.include("ClaimBelowThreshold");
+    }
+  });
+
+  it("Should let compound_rewards bypass the threshold", async () => {
+    const depositBefore = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    await program.methods
+      .compoundRewards()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .signers([backer])
+      .rpc();
+
+    const depositAfter = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(depositAfter.depositedAmount.gt(depositBefore.depositedAmount)).to.be.true;
+  });
+});
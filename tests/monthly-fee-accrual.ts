@@ -0,0 +1,167 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Monthly Fee Accrual", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+  const cranker = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+  let deployRequestPda: PublicKey;
+  let ephemeralKey: Keypair;
+
+  const programHash = crypto.randomBytes(32);
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100); // 1% of borrowed amount
+  const INITIAL_MONTHS = 3;
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(cranker.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+    [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should escrow prepaid months instead of crediting them up front", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    await transfer(
+      developer,
+      rewardPoolPda,
+      SERVICE_FEE.add(MONTHLY_FEE.muln(INITIAL_MONTHS)).toNumber()
+    );
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, INITIAL_MONTHS, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+
+    const prepaid = MONTHLY_FEE.muln(INITIAL_MONTHS);
+    expect(deployRequest.prepaidBalance.toString()).to.equal(prepaid.toString());
+    expect(
+      poolAfter.prepaidFeesBalance.sub(poolBefore.prepaidFeesBalance).toString()
+    ).to.equal(prepaid.toString());
+    // Only the service fee reaches backers immediately
+    expect(
+      poolAfter.rewardPoolBalance.sub(poolBefore.rewardPoolBalance).toString()
+    ).to.equal(SERVICE_FEE.toString());
+  });
+
+  it("Should start the accrual clock when deployment funds are borrowed", async () => {
+    ephemeralKey = Keypair.generate();
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.borrowedAmount.toString()).to.equal(DEPLOYMENT_COST.toString());
+    expect(deployRequest.lastFeeAccrual.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Should not accrue before a full month has elapsed", async () => {
+    try {
+      await program.methods
+        .accrueMonthlyFee()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          deployRequest: deployRequestPda,
+          caller: cranker.publicKey,
+        })
+        .signers([cranker])
+        .rpc();
+      expect.fail("Should have thrown NoFeeToAccrue");
+    } catch (err) {
+      expect(err.toString()).to.include("NoFeeToAccrue");
+    }
+
+    // Escrow untouched
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.prepaidBalance.toString()).to.equal(
+      MONTHLY_FEE.muln(INITIAL_MONTHS).toString()
+    );
+  });
+
+  // Return the borrowed funds so the pool is left as we found it
+  after(async () => {
+    if (!ephemeralKey) return;
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "accrual test cleanup")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+  });
+});
@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Monthly Fee Bps", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE_BPS = new BN(200);
+  // Ignored by the program while monthly_fee_bps is set
+  const EXPLICIT_MONTHLY_FEE = new BN(12345);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function setMonthlyFeeBps(monthlyFeeBps: BN) {
+    return program.methods
+      .setMonthlyFeeBps(monthlyFeeBps)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function createRequest(deploymentCost: BN, expectedMonthlyFee: BN) {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(expectedMonthlyFee).toNumber());
+    await transfer(developer, platformPoolPda, deploymentCost.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, EXPLICIT_MONTHLY_FEE, 1, deploymentCost, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        config: null,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return deployRequestPda;
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    // Restore the explicit monthly_fee path for other suites
+    await setMonthlyFeeBps(new BN(0));
+  });
+
+  it("Should emit MonthlyFeeBpsUpdated with old and new values", async () => {
+    const before = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const txSig = await setMonthlyFeeBps(MONTHLY_FEE_BPS);
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = Array.from(parser.parseLogs(tx.meta.logMessages));
+    const updated = events.find((e) => e.name === "monthlyFeeBpsUpdated");
+    expect(updated.data.oldMonthlyFeeBps.toString()).to.equal(before.monthlyFeeBps.toString());
+    expect(updated.data.newMonthlyFeeBps.toString()).to.equal(MONTHLY_FEE_BPS.toString());
+  });
+
+  it("Should derive monthly_fee from deployment_cost", async () => {
+    const expected = DEPLOYMENT_COST.mul(MONTHLY_FEE_BPS).divn(10000);
+    const deployRequestPda = await createRequest(DEPLOYMENT_COST, expected);
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.monthlyFee.toString()).to.equal(expected.toString());
+  });
+
+  it("Should reject a deployment too small to yield a non-zero fee", async () => {
+    try {
+      await createRequest(new BN(49), new BN(0));
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should reject a rate above 10%", async () => {
+    try {
+      await setMonthlyFeeBps(new BN(1001));
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+});
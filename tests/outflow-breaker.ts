@@ -0,0 +1,217 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Reward Outflow Circuit Breaker", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const FEE_REWARD = new BN(0.01 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function setOutflowBreaker(maxOutflowPerWindow: BN, outflowWindowSecs: number) {
+    return program.methods
+      .setOutflowBreaker(maxOutflowPerWindow, new BN(outflowWindowSecs))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  function claim() {
+    return program.methods
+      .claimRewards(null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer]);
+  }
+
+  function exitPosition() {
+    return program.methods
+      .exitPosition()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer]);
+  }
+
+  function compound() {
+    return program.methods
+      .compoundRewards()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .signers([backer]);
+  }
+
+  function claimBatch() {
+    return program.methods
+      .claimRewardsBatch()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        admin: admin.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: backerDepositPda, isSigner: false, isWritable: true },
+        { pubkey: backer.publicKey, isSigner: false, isWritable: true },
+      ])
+      .signers([admin]);
+  }
+
+  async function setPaused(pause: boolean) {
+    await program.methods
+      .emergencyPause(pause)
+      .accounts({ treasuryPool: treasuryPoolPda, admin: admin.publicKey })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  after(async () => {
+    await setOutflowBreaker(new BN(0), 0);
+    await setPaused(false);
+  });
+
+  it("Should pause instead of paying a claim over the outflow limit", async () => {
+    await setOutflowBreaker(new BN(1), 60 * 60);
+    const depositBefore = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    const { events } = await claim().simulate();
+    const toggled = events.find((e) => e.name === "emergencyPauseToggled");
+    expect(toggled.data.paused).to.be.true;
+
+    await claim().rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.emergencyPause).to.be.true;
+    const depositAfter = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(depositAfter.claimedTotal.toString()).to.equal(depositBefore.claimedTotal.toString());
+  });
+
+  it("Should reject claims while the breaker holds the program paused", async () => {
+    try {
+      await claim().rpc();
+      expect.fail("Should have thrown ProgramPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramPaused");
+    }
+  });
+
+  it("Should pause instead of paying out an exit_position over the outflow limit", async () => {
+    await setPaused(false);
+    const depositBefore = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    const { events } = await exitPosition().simulate();
+    expect(events.find((e) => e.name === "emergencyPauseToggled").data.paused).to.be.true;
+    expect(events.find((e) => e.name === "solUnstaked")).to.be.undefined;
+
+    await exitPosition().rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.emergencyPause).to.be.true;
+    // Neither rewards nor principal left the pool
+    const depositAfter = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(depositAfter.claimedTotal.toString()).to.equal(depositBefore.claimedTotal.toString());
+    expect(depositAfter.depositedAmount.toString()).to.equal(depositBefore.depositedAmount.toString());
+  });
+
+  it("Should pause and stop a claim_rewards_batch over the outflow limit", async () => {
+    await setPaused(false);
+    const depositBefore = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    const { events } = await claimBatch().simulate();
+    expect(events.find((e) => e.name === "emergencyPauseToggled").data.paused).to.be.true;
+    const batch = events.find((e) => e.name === "rewardsClaimedBatch");
+    expect(batch.data.backersPaid).to.equal(0);
+    expect(batch.data.totalPaid.toNumber()).to.equal(0);
+
+    await claimBatch().rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.emergencyPause).to.be.true;
+    const depositAfter = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(depositAfter.claimedTotal.toString()).to.equal(depositBefore.claimedTotal.toString());
+  });
+
+  it("Should pause instead of compounding over the outflow limit", async () => {
+    await setPaused(false);
+    const depositBefore = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    const { events } = await compound().simulate();
+    expect(events.find((e) => e.name === "emergencyPauseToggled").data.paused).to.be.true;
+    expect(events.find((e) => e.name === "rewardsCompounded")).to.be.undefined;
+
+    await compound().rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.emergencyPause).to.be.true;
+    const depositAfter = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(depositAfter.claimedTotal.toString()).to.equal(depositBefore.claimedTotal.toString());
+    expect(depositAfter.depositedAmount.toString()).to.equal(depositBefore.depositedAmount.toString());
+  });
+});
@@ -0,0 +1,167 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Pause Flags", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  // TreasuryPool::PAUSE_* bits
+  const PAUSE_DEPOSIT = 1 << 0;
+  const PAUSE_WITHDRAW = 1 << 1;
+  const PAUSE_CLAIM = 1 << 2;
+  const PAUSE_DEPLOY = 1 << 3;
+  const PAUSE_PAY_SUBSCRIPTION = 1 << 4;
+  const PAUSE_ALL = PAUSE_DEPOSIT | PAUSE_WITHDRAW | PAUSE_CLAIM | PAUSE_DEPLOY | PAUSE_PAY_SUBSCRIPTION;
+
+  const STAKE_AMOUNT = new BN(2 * LAMPORTS_PER_SOL);
+  const SMALL_AMOUNT = new BN(0.1 * LAMPORTS_PER_SOL);
+
+  async function setPauseFlags(pausedOps: number) {
+    await program.methods
+      .setPauseFlags(pausedOps)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function stake(amount: BN) {
+    await program.methods
+      .stakeSol(amount, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  async function unstake(amount: BN) {
+    await program.methods
+      .unstakeSol(amount, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await stake(STAKE_AMOUNT);
+  });
+
+  after(async () => {
+    await setPauseFlags(0);
+  });
+
+  it("Should block deposits but still allow withdrawals", async () => {
+    await setPauseFlags(PAUSE_DEPOSIT);
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.pausedOps).to.equal(PAUSE_DEPOSIT);
+    expect(pool.emergencyPause).to.be.false;
+
+    try {
+      await stake(SMALL_AMOUNT);
+      expect.fail("Should have thrown ProgramPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramPaused");
+    }
+
+    const depositBefore = await program.account.backerDeposit.fetch(backerDepositPda);
+    await unstake(SMALL_AMOUNT);
+    const depositAfter = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(depositBefore.depositedAmount.sub(depositAfter.depositedAmount).toString()).to.equal(
+      SMALL_AMOUNT.toString()
+    );
+  });
+
+  it("Should block withdrawals but still allow deposits", async () => {
+    await setPauseFlags(PAUSE_WITHDRAW);
+
+    try {
+      await unstake(SMALL_AMOUNT);
+      expect.fail("Should have thrown ProgramPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramPaused");
+    }
+
+    await stake(SMALL_AMOUNT);
+  });
+
+  it("Should reject unknown bits", async () => {
+    try {
+      await setPauseFlags(1 << 5);
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should treat all bits set as emergency_pause", async () => {
+    await setPauseFlags(PAUSE_ALL);
+    let pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.emergencyPause).to.be.true;
+
+    await setPauseFlags(0);
+    pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.emergencyPause).to.be.false;
+  });
+
+  it("Should keep emergency_pause working for existing clients", async () => {
+    await program.methods
+      .emergencyPause(true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    let pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.pausedOps).to.equal(PAUSE_ALL);
+
+    try {
+      await stake(SMALL_AMOUNT);
+      expect.fail("Should have thrown ProgramPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("ProgramPaused");
+    }
+
+    await program.methods
+      .emergencyPause(false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.pausedOps).to.equal(0);
+  });
+});
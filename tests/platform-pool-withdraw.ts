@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Platform Pool Withdraw", () => {
+  // Test accounts
+  const destination = Keypair.generate();
+
+  const FEE_PLATFORM = new BN(0.05 * LAMPORTS_PER_SOL);
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function creditPlatformFee(feeId: Buffer) {
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), new BN(0), FEE_PLATFORM)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function withdrawPlatformPool(amount: BN, reason: string) {
+    return program.methods
+      .withdrawPlatformPool(amount, reason)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        platformPool: platformPoolPda,
+        admin: admin.publicKey,
+        destination: destination.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+
+    await initializePool();
+
+    // Known platform fee credit to withdraw
+    await creditPlatformFee(crypto.randomBytes(32));
+  });
+
+  it("Should withdraw exactly what credit_fee_to_pool credited", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const destinationBefore = await provider.connection.getBalance(destination.publicKey);
+
+    const txSig = await withdrawPlatformPool(FEE_PLATFORM, "platform fee sweep");
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const destinationAfter = await provider.connection.getBalance(destination.publicKey);
+
+    expect(destinationAfter - destinationBefore).to.equal(FEE_PLATFORM.toNumber());
+    expect(
+      poolBefore.platformPoolBalance.sub(poolAfter.platformPoolBalance).toString()
+    ).to.equal(FEE_PLATFORM.toString());
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const withdrew = Array.from(events).find((e) => e.name === "adminWithdrew");
+    expect(withdrew, "AdminWithdrew event").to.not.be.undefined;
+    expect(withdrew.data.reason).to.equal("platform fee sweep");
+    expect(withdrew.data.amount.toString()).to.equal(FEE_PLATFORM.toString());
+  });
+
+  it("Should reject withdrawing more than platform_pool_balance", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    try {
+      await withdrawPlatformPool(pool.platformPoolBalance.addn(1), "too much");
+      expect.fail("Should have thrown InsufficientTreasuryFunds");
+    } catch (err) {
+      expect(err.toString()).to.include("InsufficientTreasuryFunds");
+    }
+  });
+
+  it("Should let admin_withdraw spend credited platform fees", async () => {
+    await creditPlatformFee(crypto.randomBytes(32));
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const destinationBefore = await provider.connection.getBalance(destination.publicKey);
+
+    // ADMIN_POOL_SEED aliases the Platform Pool PDA
+    await program.methods
+      .adminWithdraw(FEE_PLATFORM, "legacy admin withdraw")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        adminPool: platformPoolPda,
+        admin: admin.publicKey,
+        destination: destination.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const destinationAfter = await provider.connection.getBalance(destination.publicKey);
+    expect(destinationAfter - destinationBefore).to.equal(FEE_PLATFORM.toNumber());
+    expect(
+      poolBefore.platformPoolBalance.sub(poolAfter.platformPoolBalance).toString()
+    ).to.equal(FEE_PLATFORM.toString());
+  });
+
+  it("Should reject a retried fee_id without crediting twice", async () => {
+    const feeId = crypto.randomBytes(32);
+    await creditPlatformFee(feeId);
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    try {
+      await creditPlatformFee(feeId);
+      expect.fail("Should have thrown DuplicateFee");
+    } catch (err) {
+      expect(err.toString()).to.include("DuplicateFee");
+    }
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolAfter.platformPoolBalance.toString()).to.equal(poolBefore.platformPoolBalance.toString());
+
+    const processed = await program.account.processedFee.fetch(processedFeePda(feeId));
+    expect(processed.feePlatform.toString()).to.equal(FEE_PLATFORM.toString());
+  });
+});
@@ -0,0 +1,147 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Move Platform To Reward", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const FEE_PLATFORM = new BN(0.05 * LAMPORTS_PER_SOL);
+  const PRECISION = new BN("1000000000000");
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function movePlatformToReward(amount: BN) {
+    return program.methods
+      .movePlatformToReward(amount)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        platformPool: platformPoolPda,
+        rewardPool: rewardPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    // Known platform fee credit to move
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), new BN(0), FEE_PLATFORM)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Should move platform fees to the reward pool and bump reward_per_share", async () => {
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const rewardLamportsBefore = await provider.connection.getBalance(rewardPoolPda);
+    const platformLamportsBefore = await provider.connection.getBalance(platformPoolPda);
+
+    const txSig = await movePlatformToReward(FEE_PLATFORM);
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const rewardLamportsAfter = await provider.connection.getBalance(rewardPoolPda);
+    const platformLamportsAfter = await provider.connection.getBalance(platformPoolPda);
+
+    expect(rewardLamportsAfter - rewardLamportsBefore).to.equal(FEE_PLATFORM.toNumber());
+    expect(platformLamportsBefore - platformLamportsAfter).to.equal(FEE_PLATFORM.toNumber());
+    expect(
+      poolBefore.platformPoolBalance.sub(poolAfter.platformPoolBalance).toString()
+    ).to.equal(FEE_PLATFORM.toString());
+    // Credited to backers, less any reward_per_share truncation dust
+    expect(
+      poolAfter.rewardPoolBalance
+        .add(poolAfter.undistributedDust)
+        .sub(poolBefore.rewardPoolBalance.add(poolBefore.undistributedDust))
+        .toString()
+    ).to.equal(FEE_PLATFORM.toString());
+
+    // Same formula as credit_fee_to_pool: fee_reward * PRECISION / total_deposited
+    const expectedDelta = FEE_PLATFORM.mul(PRECISION).div(poolBefore.totalDeposited);
+    expect(
+      poolAfter.rewardPerShare.sub(poolBefore.rewardPerShare).toString()
+    ).to.equal(expectedDelta.toString());
+
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = new anchor.EventParser(program.programId, program.coder).parseLogs(
+      tx.meta.logMessages
+    );
+    const moved = Array.from(events).find((e) => e.name === "adminMovedToRewardPool");
+    expect(moved, "AdminMovedToRewardPool event").to.not.be.undefined;
+    expect(moved.data.amount.toString()).to.equal(FEE_PLATFORM.toString());
+  });
+
+  it("Should reject moving more than platform_pool_balance", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    try {
+      await movePlatformToReward(pool.platformPoolBalance.addn(1));
+      expect.fail("Should have thrown InsufficientTreasuryFunds");
+    } catch (err) {
+      expect(err.toString()).to.include("InsufficientTreasuryFunds");
+    }
+  });
+
+  it("Should reject a backstop while the Reward Pool is solvent", async () => {
+    // Solvent pools only; the partial / full top-up math is covered by the reward_pool_shortfall unit test
+    try {
+      await program.methods
+        .backstopRewardPool()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          platformPool: platformPoolPda,
+          rewardPool: rewardPoolPda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have thrown RewardPoolSolvent");
+    } catch (err) {
+      expect(err.toString()).to.include("RewardPoolSolvent");
+    }
+  });
+});
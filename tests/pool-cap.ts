@@ -0,0 +1,81 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Pool Deposit Cap", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const STAKE_AMOUNT = new BN(1 * LAMPORTS_PER_SOL);
+
+  async function setPoolCap(maxTotalDeposited: BN) {
+    await program.methods
+      .setPoolCap(maxTotalDeposited)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    await setPoolCap(new BN(0));
+  });
+
+  async function stake(amount: BN) {
+    await program.methods
+      .stakeSol(amount, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  it("Should accept deposits up to max_total_deposited", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    await setPoolCap(pool.totalDeposited.add(STAKE_AMOUNT));
+
+    await stake(STAKE_AMOUNT);
+
+    const after = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(after.totalDeposited.toString()).to.equal(after.maxTotalDeposited.toString());
+  });
+
+  it("Should reject a deposit past max_total_deposited", async () => {
+    try {
+      await stake(new BN(1));
+      expect.fail("Should have thrown PoolCapReached");
+    } catch (err) {
+      expect(err.toString()).to.include("PoolCapReached");
+    }
+  });
+
+  it("Should accept deposits again once the cap is lifted", async () => {
+    await setPoolCap(new BN(0));
+    await stake(STAKE_AMOUNT);
+
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+    expect(deposit.depositedAmount.toString()).to.equal(STAKE_AMOUNT.muln(2).toString());
+  });
+});
@@ -0,0 +1,197 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Pool Solvency", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+  const watchdog = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(2 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = new BN(0.02 * LAMPORTS_PER_SOL);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 20 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(watchdog.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(10 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should not flag a fully collateralized pool", async () => {
+    await program.methods
+      .flagUndercollateralization()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        caller: watchdog.publicKey,
+      })
+      .signers([watchdog])
+      .rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.undercollateralized).to.be.false;
+  });
+
+  it("Should flag the pool after deployment funds are lost", async () => {
+    const programHash = crypto.randomBytes(32);
+    const ephemeralKey = Keypair.generate();
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+    const [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Developer pays fees to the pools off-chain before the admin creates the request
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    // Simulate the ephemeral key being drained before the deployment resolves
+    const drained = await provider.connection.getBalance(ephemeralKey.publicKey);
+    await transfer(ephemeralKey, Keypair.generate().publicKey, drained);
+
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "ephemeral key drained")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+
+    await program.methods
+      .flagUndercollateralization()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        caller: watchdog.publicKey,
+      })
+      .signers([watchdog])
+      .rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.undercollateralized).to.be.true;
+  });
+
+  it("Should fail verify_solvency on the treasury invariant after the loss", async () => {
+    // Reward and Platform Pool invariants are broken in the verify_solvency unit test;
+    // the drained deployment above breaks the treasury one for real
+    try {
+      await program.methods
+        .verifySolvency()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          platformPool: platformPoolPda,
+        })
+        .rpc();
+      expect.fail("Should have thrown TreasuryInsolvent");
+    } catch (error) {
+      expect(error.toString()).to.include("TreasuryInsolvent");
+    }
+  });
+
+  it("Should refuse to clear the flag while the shortfall remains", async () => {
+    try {
+      await program.methods
+        .clearUndercollateralization()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should have thrown PoolStillUndercollateralized");
+    } catch (error) {
+      expect(error.toString()).to.include("PoolStillUndercollateralized");
+    }
+  });
+
+  it("Should clear the flag after the shortfall is covered", async () => {
+    // Admin recapitalizes the treasury PDA with the lost deployment cost
+    await transfer(admin, treasuryPoolPda, DEPLOYMENT_COST.toNumber());
+
+    await program.methods
+      .clearUndercollateralization()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.undercollateralized).to.be.false;
+  });
+});
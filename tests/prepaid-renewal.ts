@@ -0,0 +1,180 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Prepaid Auto-Renewal", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  const TOP_UP = MONTHLY_FEE.muln(3);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  function confirmSuccess(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    return program.methods
+      .confirmDeploymentSuccess(Array.from(programHash), Keypair.generate().publicKey, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey]);
+  }
+
+  async function createActiveRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+    await confirmSuccess(programHash, deployRequestPda, ephemeralKey).rpc();
+    return { programHash, deployRequestPda };
+  }
+
+  async function topUp(programHash: Buffer, deployRequestPda: PublicKey, signer: Keypair, amount: BN) {
+    await program.methods
+      .topUpPrepaid(Array.from(programHash), amount)
+      .accounts({
+        deployRequest: deployRequestPda,
+        developer: signer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should hold top-ups in the deploy request", async () => {
+    const { programHash, deployRequestPda } = await createActiveRequest();
+    const lamportsBefore = await provider.connection.getBalance(deployRequestPda);
+
+    await topUp(programHash, deployRequestPda, developer, TOP_UP);
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.renewalBalance.toString()).to.equal(TOP_UP.toString());
+    const lamportsAfter = await provider.connection.getBalance(deployRequestPda);
+    expect(lamportsAfter - lamportsBefore).to.equal(TOP_UP.toNumber());
+  });
+
+  it("Should reject top-ups from anyone but the developer", async () => {
+    const { programHash, deployRequestPda } = await createActiveRequest();
+    try {
+      await topUp(programHash, deployRequestPda, backer, TOP_UP);
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should not renew before the renewal window", async () => {
+    const { programHash, deployRequestPda } = await createActiveRequest();
+    await topUp(programHash, deployRequestPda, developer, TOP_UP);
+
+    // A fresh one-month subscription is weeks away from the 3-day window
+    try {
+      await program.methods
+        .renewFromPrepaid()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          deployRequest: deployRequestPda,
+          rewardPool: rewardPoolPda,
+          caller: backer.publicKey,
+        })
+        .signers([backer])
+        .rpc();
+      expect.fail("Should have thrown RenewalNotDue");
+    } catch (err) {
+      expect(err.toString()).to.include("RenewalNotDue");
+    }
+  });
+});
@@ -0,0 +1,194 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Pro-Rata Withdrawal", () => {
+  // Test accounts
+  const backer1 = Keypair.generate();
+  const backer2 = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backer1DepositPda: PublicKey;
+  let backer2DepositPda: PublicKey;
+
+  const STAKE_AMOUNT = new BN(5 * LAMPORTS_PER_SOL);
+  const UNSTAKE_AMOUNT = new BN(1 * LAMPORTS_PER_SOL);
+  const DEPLOYMENT_COST = new BN(3 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = new BN(0.02 * LAMPORTS_PER_SOL);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function unstake(backer: Keypair, depositPda: PublicKey, amount: BN) {
+    await program.methods
+      .unstakeSol(amount, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        lenderStake: depositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  }
+
+  // Lose a deployment's borrowed funds so the pool falls short of its obligations
+  async function induceShortfall() {
+    const programHash = crypto.randomBytes(32);
+    const ephemeralKey = Keypair.generate();
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+    const [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, false)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet: ephemeralKey.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const drained = await provider.connection.getBalance(ephemeralKey.publicKey);
+    await transfer(ephemeralKey, Keypair.generate().publicKey, drained);
+
+    await program.methods
+      .confirmDeploymentFailure(Array.from(programHash), "ephemeral key drained")
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey])
+      .rpc();
+
+    await program.methods
+      .flagUndercollateralization()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        caller: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(backer1.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(backer2.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backer1DepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer1.publicKey.toBuffer()],
+      program.programId
+    );
+    [backer2DepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer2.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    for (const [backer, depositPda] of [
+      [backer1, backer1DepositPda],
+      [backer2, backer2DepositPda],
+    ] as [Keypair, PublicKey][]) {
+      await program.methods
+        .stakeSol(STAKE_AMOUNT, new BN(0))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          treasuryPda: treasuryPoolPda,
+          lenderStake: depositPda,
+          lender: backer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([backer])
+        .rpc();
+    }
+  });
+
+  it("Should pay the full amount in normal mode", async () => {
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.undercollateralized).to.be.false;
+
+    const balanceBefore = await provider.connection.getBalance(backer1.publicKey);
+    await unstake(backer1, backer1DepositPda, UNSTAKE_AMOUNT);
+    const balanceAfter = await provider.connection.getBalance(backer1.publicKey);
+
+    // Backer is fee payer, allow for the transaction fee
+    expect(balanceAfter - balanceBefore).to.be.greaterThan(UNSTAKE_AMOUNT.toNumber() - 10_000);
+
+    const deposit = await program.account.backerDeposit.fetch(backer1DepositPda);
+    expect(deposit.haircutTotal.toNumber()).to.equal(0);
+    expect(deposit.proRataWithdrawn.toNumber()).to.equal(0);
+  });
+
+  it("Should apply a pro-rata haircut while undercollateralized", async () => {
+    await induceShortfall();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(pool.undercollateralized).to.be.true;
+
+    const depositBefore = await program.account.backerDeposit.fetch(backer2DepositPda);
+    const balanceBefore = await provider.connection.getBalance(backer2.publicKey);
+    await unstake(backer2, backer2DepositPda, UNSTAKE_AMOUNT);
+    const balanceAfter = await provider.connection.getBalance(backer2.publicKey);
+
+    const deposit = await program.account.backerDeposit.fetch(backer2DepositPda);
+    const payout = deposit.proRataWithdrawn.toNumber();
+    const haircut = deposit.haircutTotal.toNumber();
+
+    // Received less than requested, shortfall recorded as haircut
+    expect(haircut).to.be.greaterThan(0);
+    expect(payout + haircut).to.equal(UNSTAKE_AMOUNT.toNumber());
+    expect(balanceAfter - balanceBefore).to.be.lessThan(UNSTAKE_AMOUNT.toNumber());
+
+    // Full amount debited so the haircut cannot be withdrawn again
+    expect(depositBefore.depositedAmount.sub(deposit.depositedAmount).toString()).to.equal(
+      UNSTAKE_AMOUNT.toString()
+    );
+  });
+});
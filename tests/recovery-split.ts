@@ -0,0 +1,189 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Recovery Platform Split", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const developer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  const RECOVERED = new BN(0.5 * LAMPORTS_PER_SOL);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function fund(programHash: Buffer, deployRequestPda: PublicKey, temporaryWallet: PublicKey) {
+    return program.methods
+      .fundTemporaryWallet(Array.from(programHash), DEPLOYMENT_COST, true)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: admin.publicKey,
+        treasuryPda: treasuryPoolPda,
+        temporaryWallet,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  async function setRecoveryPlatformBps(bps: number) {
+    await program.methods
+      .setRecoveryPlatformBps(new BN(bps))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  function confirmSuccess(programHash: Buffer, deployRequestPda: PublicKey, ephemeralKey: Keypair) {
+    return program.methods
+      .confirmDeploymentSuccess(Array.from(programHash), Keypair.generate().publicKey, RECOVERED)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        admin: admin.publicKey,
+        ephemeralKey: ephemeralKey.publicKey,
+        developerWallet: developer.publicKey,
+        treasuryPda: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin, ephemeralKey]);
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    // Liquidity for the deployment below
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  after(async () => {
+    await setRecoveryPlatformBps(0);
+  });
+
+  it("Should reject a platform share above the maximum", async () => {
+    try {
+      await setRecoveryPlatformBps(5001);
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should return all recovered funds to liquid_balance by default", async () => {
+    await setRecoveryPlatformBps(0);
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    // Simulate first to read the emitted split
+    const { events } = await confirmSuccess(programHash, deployRequestPda, ephemeralKey).simulate();
+    const confirmed = events.find((e) => e.name === "deploymentConfirmed");
+    expect(confirmed.data.recoveredToLiquid.toString()).to.equal(RECOVERED.toString());
+    expect(confirmed.data.recoveredToPlatform.toNumber()).to.equal(0);
+    expect(confirmed.data.deploymentLoss.toString()).to.equal(DEPLOYMENT_COST.sub(RECOVERED).toString());
+
+    await confirmSuccess(programHash, deployRequestPda, ephemeralKey).rpc();
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolAfter.liquidBalance.sub(poolBefore.liquidBalance).toString()).to.equal(RECOVERED.toString());
+    expect(poolAfter.platformPoolBalance.toString()).to.equal(poolBefore.platformPoolBalance.toString());
+    expect(
+      poolAfter.totalDeploymentLosses.sub(poolBefore.totalDeploymentLosses).toString()
+    ).to.equal(DEPLOYMENT_COST.sub(RECOVERED).toString());
+    // Losses are tracked, backer principal is untouched
+    expect(poolAfter.totalDeposited.toString()).to.equal(poolBefore.totalDeposited.toString());
+  });
+
+  it("Should route recovery_platform_bps of recovered funds to the Platform Pool", async () => {
+    await setRecoveryPlatformBps(1000); // 10%
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    const ephemeralKey = Keypair.generate();
+    await fund(programHash, deployRequestPda, ephemeralKey.publicKey);
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const platformLamportsBefore = await provider.connection.getBalance(platformPoolPda);
+
+    await confirmSuccess(programHash, deployRequestPda, ephemeralKey).rpc();
+
+    const toPlatform = RECOVERED.muln(1000).divn(10000);
+    const toLiquid = RECOVERED.sub(toPlatform);
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(poolAfter.liquidBalance.sub(poolBefore.liquidBalance).toString()).to.equal(toLiquid.toString());
+    expect(poolAfter.platformPoolBalance.sub(poolBefore.platformPoolBalance).toString()).to.equal(toPlatform.toString());
+
+    const platformLamportsAfter = await provider.connection.getBalance(platformPoolPda);
+    expect(platformLamportsAfter - platformLamportsBefore).to.equal(toPlatform.toNumber());
+  });
+});
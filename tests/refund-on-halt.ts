@@ -0,0 +1,156 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Refund On Halt", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = new BN(0.02 * LAMPORTS_PER_SOL);
+  const INITIAL_MONTHS = 3;
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function setDeploymentsPaused(paused: boolean) {
+    await program.methods
+      .setDeploymentsPaused(paused)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  // Developer pays fees, admin creates a pending (unfunded) deploy request
+  async function createPendingRequest(): Promise<{ programHash: Buffer; deployRequestPda: PublicKey }> {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await transfer(
+      developer,
+      rewardPoolPda,
+      SERVICE_FEE.add(MONTHLY_FEE.muln(INITIAL_MONTHS)).toNumber()
+    );
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, INITIAL_MONTHS, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { programHash, deployRequestPda };
+  }
+
+  async function refundOnHalt(programHash: Buffer, deployRequestPda: PublicKey) {
+    return program.methods
+      .refundOnHalt(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+      })
+      .signers([developer])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    // Leave deployments running for the other suites
+    await setDeploymentsPaused(false);
+  });
+
+  it("Should block refunds while deployments are not paused", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+
+    try {
+      await refundOnHalt(programHash, deployRequestPda);
+      expect.fail("Should have thrown DeploymentsNotPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("DeploymentsNotPaused");
+    }
+  });
+
+  it("Should refund all paid fees during a halt", async () => {
+    const { programHash, deployRequestPda } = await createPendingRequest();
+    await setDeploymentsPaused(true);
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const statsBefore = await program.account.userDeployStats.fetch(userStatsPda);
+    const developerBalanceBefore = await provider.connection.getBalance(developer.publicKey);
+
+    await refundOnHalt(programHash, deployRequestPda);
+
+    // The service fee comes back only while it is not yet owed to backers
+    const excessBefore = poolBefore.rewardPoolBalance.sub(BN.min(poolBefore.rewardPoolBalance, poolBefore.totalClaimable));
+    const serviceRefund = BN.min(SERVICE_FEE, excessBefore);
+    const expectedRefund = serviceRefund.add(MONTHLY_FEE.muln(INITIAL_MONTHS));
+    const developerBalanceAfter = await provider.connection.getBalance(developer.publicKey);
+    // Developer pays the transaction fee, so allow a small margin
+    expect(developerBalanceAfter - developerBalanceBefore).to.be.greaterThan(
+      expectedRefund.toNumber() - 10_000
+    );
+
+    // Service fee was credited to rewards, prepaid months were held in escrow
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    expect(
+      poolBefore.rewardPoolBalance.sub(poolAfter.rewardPoolBalance).toString()
+    ).to.equal(serviceRefund.toString());
+    expect(poolAfter.rewardPoolBalance.gte(poolAfter.totalClaimable)).to.be.true;
+    expect(
+      poolBefore.prepaidFeesBalance.sub(poolAfter.prepaidFeesBalance).toString()
+    ).to.equal(MONTHLY_FEE.muln(INITIAL_MONTHS).toString());
+
+    const statsAfter = await program.account.userDeployStats.fetch(userStatsPda);
+    expect(statsAfter.activeSessions).to.equal(statsBefore.activeSessions - 1);
+
+    const deployRequest = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(deployRequest.status).to.deep.equal({ cancelled: {} });
+  });
+
+  it("Should reject new deploy requests during a halt", async () => {
+    try {
+      await createPendingRequest();
+      expect.fail("Should have thrown DeploymentsPaused");
+    } catch (err) {
+      expect(err.toString()).to.include("DeploymentsPaused");
+    }
+  });
+});
@@ -0,0 +1,113 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Reward History", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+  let rewardHistoryPda: PublicKey;
+
+  const FEE_REWARD = new BN(0.1 * LAMPORTS_PER_SOL);
+  const CAPACITY = 32;
+
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
+  async function creditFee(withHistory: boolean) {
+    const feeId = crypto.randomBytes(32);
+    await program.methods
+      .creditFeeToPool(Array.from(feeId), FEE_REWARD, new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        processedFee: processedFeePda(feeId),
+        admin: admin.publicKey,
+        feePayer: admin.publicKey,
+        rewardHistory: withHistory ? rewardHistoryPda : null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  function latest(history: any) {
+    return history.entries[(history.nextIndex + CAPACITY - 1) % CAPACITY];
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    [rewardHistoryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_history")],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+
+    try {
+      await program.methods
+        .initializeRewardHistory()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardHistory: rewardHistoryPda,
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    } catch (err) {
+      // May already be initialized by an earlier run
+      console.log("Reward history may already be initialized");
+    }
+  });
+
+  it("Should record a snapshot when the history is passed to credit_fee_to_pool", async () => {
+    const before = await program.account.rewardHistory.fetch(rewardHistoryPda);
+
+    await creditFee(true);
+
+    const history = await program.account.rewardHistory.fetch(rewardHistoryPda);
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const snapshot = latest(history);
+
+    expect(history.nextIndex).to.equal((before.nextIndex + 1) % CAPACITY);
+    expect(history.len).to.equal(Math.min(before.len + 1, CAPACITY));
+    expect(snapshot.rewardPerShare.toString()).to.equal(pool.rewardPerShare.toString());
+    expect(snapshot.totalDeposited.toString()).to.equal(pool.totalDeposited.toString());
+  });
+
+  it("Should leave the history untouched when it is not passed", async () => {
+    const before = await program.account.rewardHistory.fetch(rewardHistoryPda);
+    await creditFee(false);
+    const after = await program.account.rewardHistory.fetch(rewardHistoryPda);
+
+    expect(after.nextIndex).to.equal(before.nextIndex);
+    expect(after.len).to.equal(before.len);
+  });
+});
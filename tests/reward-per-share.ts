@@ -2,7 +2,9 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { D2dProgramSol } from "../target/types/d2d_program_sol";
 import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { admin, initializePool } from "./helpers/fixture";
 import { expect } from "chai";
+import * as crypto from "crypto";
 import { BN } from "@coral-xyz/anchor";
 
 describe("Reward-Per-Share Model", () => {
@@ -12,7 +14,6 @@ describe("Reward-Per-Share Model", () => {
   const program = anchor.workspace.D2dProgramSol as Program<D2dProgramSol>;
   
   // Test accounts
-  const admin = Keypair.generate();
   const devWallet = Keypair.generate();
   const backer1 = Keypair.generate();
   const backer2 = Keypair.generate();
@@ -31,9 +32,15 @@ describe("Reward-Per-Share Model", () => {
   
   const PRECISION = new BN("1000000000000"); // 1e12
 
+  function processedFeePda(feeId: Buffer): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("processed_fee"), feeId],
+      program.programId
+    )[0];
+  }
+
   before(async () => {
     // Airdrop SOL to test accounts
-    await provider.connection.requestAirdrop(admin.publicKey, 20 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(backer1.publicKey, 20 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(backer2.publicKey, 20 * LAMPORTS_PER_SOL);
     await provider.connection.requestAirdrop(devWallet.publicKey, 1 * LAMPORTS_PER_SOL);
@@ -67,24 +74,8 @@ describe("Reward-Per-Share Model", () => {
       program.programId
     );
 
-    // Initialize treasury pool
-    try {
-      await program.methods
-        .initialize(0, devWallet.publicKey) // initial_apy = 0 (not used)
-        .accounts({
-          treasuryPool: treasuryPoolPda,
-          rewardPool: rewardPoolPda,
-          platformPool: platformPoolPda,
-          admin: admin.publicKey,
-          devWallet: devWallet.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([admin])
-        .rpc();
-    } catch (err) {
-      // May already be initialized
-      console.log("Treasury pool may already be initialized:", err);
-    }
+    // Initialize treasury pool (shared with the other suites)
+    await initializePool();
   });
 
   describe("Scenario A: Two backers deposit, then fee credit", () => {
@@ -129,12 +120,14 @@ describe("Reward-Per-Share Model", () => {
       const feeReward = new BN(1.5 * LAMPORTS_PER_SOL);
       const feePlatform = new BN(0.15 * LAMPORTS_PER_SOL); // 0.1% of 1.5 SOL
       
+      const feeId = crypto.randomBytes(32);
       await program.methods
-        .creditFeeToPool(feeReward, feePlatform)
+        .creditFeeToPool(Array.from(feeId), feeReward, feePlatform)
         .accounts({
           treasuryPool: treasuryPoolPda,
           rewardPool: rewardPoolPda,
           platformPool: platformPoolPda,
+          processedFee: processedFeePda(feeId),
           admin: admin.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -185,20 +178,20 @@ describe("Reward-Per-Share Model", () => {
       const treasuryPoolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
       const backer1DepositBefore = await program.account.backerDeposit.fetch(backer1DepositPda);
       
-      // Calculate claimable before claim
+      // Calculate claimable before claim (pending_rewards + accrued from reward_per_share)
       const claimableBefore = new BN(backer1DepositBefore.depositedAmount.toNumber())
         .mul(treasuryPoolBefore.rewardPerShare)
         .sub(backer1DepositBefore.rewardDebt)
-        .div(PRECISION);
+        .div(PRECISION)
+        .add(backer1DepositBefore.pendingRewards);
       
       if (claimableBefore.toNumber() > 0) {
         // Claim partial (if there are rewards)
         const claimAmount = claimableBefore.div(new BN(2)); // Claim half
+        const initialBalance = await provider.connection.getBalance(backer1.publicKey);
         
-        // Note: We can't claim partial in current implementation, so we'll claim all
-        // This test verifies the reward_debt update after claim
         await program.methods
-          .claimRewards()
+          .claimRewards(claimAmount)
           .accounts({
             treasuryPool: treasuryPoolPda,
             rewardPool: rewardPoolPda,
@@ -211,21 +204,54 @@ describe("Reward-Per-Share Model", () => {
         
         // Get state after claim
         const backer1DepositAfter = await program.account.backerDeposit.fetch(backer1DepositPda);
+        const finalBalance = await provider.connection.getBalance(backer1.publicKey);
+        
+        // Exactly claimAmount was transferred (minus tx fee)
+        expect(finalBalance - initialBalance).to.be.closeTo(claimAmount.toNumber(), 10_000);
+        expect(
+          backer1DepositAfter.claimedTotal.sub(backer1DepositBefore.claimedTotal).toString()
+        ).to.equal(claimAmount.toString());
+        const treasuryPoolAfterClaim = await program.account.treasuryPool.fetch(treasuryPoolPda);
+        expect(
+          treasuryPoolAfterClaim.totalRewardsClaimed.sub(treasuryPoolBefore.totalRewardsClaimed).toString()
+        ).to.equal(claimAmount.toString());
         
-        // reward_debt should be updated to current accumulated value
-        const expectedRewardDebt = new BN(backer1DepositAfter.depositedAmount.toNumber())
-          .mul(treasuryPoolBefore.rewardPerShare);
+        // Unclaimed remainder stays claimable
+        const remaining = new BN(backer1DepositAfter.depositedAmount.toNumber())
+          .mul(treasuryPoolBefore.rewardPerShare)
+          .sub(backer1DepositAfter.rewardDebt)
+          .div(PRECISION)
+          .add(backer1DepositAfter.pendingRewards);
+        expect(remaining.toString()).to.equal(claimableBefore.sub(claimAmount).toString());
         
-        expect(backer1DepositAfter.rewardDebt.toString()).to.equal(expectedRewardDebt.toString());
+        // Claiming more than what is left is rejected
+        try {
+          await program.methods
+            .claimRewards(remaining.addn(1))
+            .accounts({
+              treasuryPool: treasuryPoolPda,
+              rewardPool: rewardPoolPda,
+              lenderStake: backer1DepositPda,
+              lender: backer1.publicKey,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([backer1])
+            .rpc();
+          expect.fail("Should have thrown ClaimExceedsClaimable");
+        } catch (err) {
+          expect(err.toString()).to.include("ClaimExceedsClaimable");
+        }
         
         // Credit new fee
         const newFeeReward = new BN(0.5 * LAMPORTS_PER_SOL);
+        const feeId = crypto.randomBytes(32);
         await program.methods
-          .creditFeeToPool(newFeeReward, new BN(0))
+          .creditFeeToPool(Array.from(feeId), newFeeReward, new BN(0))
           .accounts({
             treasuryPool: treasuryPoolPda,
             rewardPool: rewardPoolPda,
             platformPool: platformPoolPda,
+            processedFee: processedFeePda(feeId),
             admin: admin.publicKey,
             systemProgram: SystemProgram.programId,
           })
@@ -261,7 +287,7 @@ describe("Reward-Per-Share Model", () => {
         const initialBalance = await provider.connection.getBalance(backer1.publicKey);
         
         await program.methods
-          .unstakeSol(unstakeAmount)
+          .unstakeSol(unstakeAmount, false)
           .accounts({
             treasuryPool: treasuryPoolPda,
             treasuryPda: treasuryPoolPda,
@@ -303,7 +329,7 @@ describe("Reward-Per-Share Model", () => {
       if (unstakeAmount.toNumber() <= backer1Deposit.depositedAmount.toNumber()) {
         try {
           await program.methods
-            .unstakeSol(unstakeAmount)
+            .unstakeSol(unstakeAmount, false)
             .accounts({
               treasuryPool: treasuryPoolPda,
               treasuryPda: treasuryPoolPda,
@@ -322,6 +348,73 @@ describe("Reward-Per-Share Model", () => {
     });
   });
 
+  describe("Scenario D: Compound rewards", () => {
+    it("Should restake claimable rewards without leaving the program", async () => {
+      // Credit a fee so backer2 has something to compound
+      const feeId = crypto.randomBytes(32);
+      await program.methods
+        .creditFeeToPool(Array.from(feeId), new BN(0.2 * LAMPORTS_PER_SOL), new BN(0))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          platformPool: platformPoolPda,
+          processedFee: processedFeePda(feeId),
+          admin: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+      const treasuryPoolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+      const backer2DepositBefore = await program.account.backerDeposit.fetch(backer2DepositPda);
+      const claimable = new BN(backer2DepositBefore.depositedAmount.toNumber())
+        .mul(treasuryPoolBefore.rewardPerShare)
+        .sub(backer2DepositBefore.rewardDebt)
+        .div(PRECISION)
+        .add(backer2DepositBefore.pendingRewards);
+      expect(claimable.toNumber()).to.be.greaterThan(0);
+
+      const rewardPoolLamportsBefore = await provider.connection.getBalance(rewardPoolPda);
+      const treasuryLamportsBefore = await provider.connection.getBalance(treasuryPoolPda);
+
+      await program.methods
+        .compoundRewards()
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          treasuryPda: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          lenderStake: backer2DepositPda,
+          lender: backer2.publicKey,
+        })
+        .signers([backer2])
+        .rpc();
+
+      const treasuryPoolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+      const backer2DepositAfter = await program.account.backerDeposit.fetch(backer2DepositPda);
+
+      // Rewards moved into principal
+      expect(backer2DepositAfter.depositedAmount.toString()).to.equal(
+        backer2DepositBefore.depositedAmount.add(claimable).toString()
+      );
+      expect(backer2DepositAfter.pendingRewards.toNumber()).to.equal(0);
+      expect(backer2DepositAfter.rewardDebt.toString()).to.equal(
+        backer2DepositAfter.depositedAmount.mul(treasuryPoolAfter.rewardPerShare).toString()
+      );
+      expect(treasuryPoolAfter.totalDeposited.toString()).to.equal(
+        treasuryPoolBefore.totalDeposited.add(claimable).toString()
+      );
+      expect(treasuryPoolAfter.rewardPoolBalance.toString()).to.equal(
+        treasuryPoolBefore.rewardPoolBalance.sub(claimable).toString()
+      );
+
+      // Lamports moved Reward Pool PDA -> Treasury PDA
+      const rewardPoolLamportsAfter = await provider.connection.getBalance(rewardPoolPda);
+      const treasuryLamportsAfter = await provider.connection.getBalance(treasuryPoolPda);
+      expect(rewardPoolLamportsBefore - rewardPoolLamportsAfter).to.equal(claimable.toNumber());
+      expect(treasuryLamportsAfter - treasuryLamportsBefore).to.equal(claimable.toNumber());
+    });
+  });
+
   describe("Division by zero protection", () => {
     it("Should handle credit_fee_to_pool when total_deposited is 0", async () => {
       // This test requires a fresh treasury pool with no deposits
@@ -334,12 +427,14 @@ describe("Reward-Per-Share Model", () => {
       // Should not panic even if total_deposited is 0
       // reward_per_share simply won't increase
       try {
+        const feeId = crypto.randomBytes(32);
         await program.methods
-          .creditFeeToPool(feeReward, feePlatform)
+          .creditFeeToPool(Array.from(feeId), feeReward, feePlatform)
           .accounts({
             treasuryPool: treasuryPoolPda,
             rewardPool: rewardPoolPda,
             platformPool: platformPoolPda,
+            processedFee: processedFeePda(feeId),
             admin: admin.publicKey,
             systemProgram: SystemProgram.programId,
           })
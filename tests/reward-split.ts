@@ -0,0 +1,150 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Reward Split Override", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+  // service_fee + 0.1% platform fee, re-divided by reward_split_bps
+  const UP_FRONT_FEE = SERVICE_FEE.add(DEPLOYMENT_COST.divn(1000));
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  async function setRewardSplitBounds(minBps: number, maxBps: number) {
+    return program.methods
+      .setRewardSplitBounds(new BN(minBps), new BN(maxBps))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function createRequest(rewardSplitBps: number, effectiveBps: number) {
+    const programHash = crypto.randomBytes(32);
+    const [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    // The backend collects each pool's share of the effective split
+    const rewardShare = UP_FRONT_FEE.muln(effectiveBps).divn(10000);
+    await transfer(developer, rewardPoolPda, rewardShare.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, UP_FRONT_FEE.sub(rewardShare).toNumber());
+
+    return program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, new BN(rewardSplitBps))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        config: null,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+  }
+
+  async function parseEvents(txSig: string) {
+    const tx = await provider.connection.getTransaction(txSig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+  });
+
+  after(async () => {
+    // Leave overrides unbounded for other suites
+    await setRewardSplitBounds(0, 10000);
+  });
+
+  it("Should emit RewardSplitBoundsUpdated with old and new values", async () => {
+    const txSig = await setRewardSplitBounds(2000, 9000);
+
+    const events = await parseEvents(txSig);
+    const updated = events.find((e) => e.name === "rewardSplitBoundsUpdated");
+    expect(updated.data.newMinRewardSplitBps.toNumber()).to.equal(2000);
+    expect(updated.data.newMaxRewardSplitBps.toNumber()).to.equal(9000);
+  });
+
+  it("Should reject a minimum above the maximum", async () => {
+    try {
+      await setRewardSplitBounds(9000, 2000);
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should credit each pool its share of the overridden split", async () => {
+    const before = await program.account.treasuryPool.fetch(treasuryPoolPda);
+
+    const txSig = await createRequest(5000, 5000);
+
+    const after = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const half = UP_FRONT_FEE.divn(2);
+    expect(after.platformPoolBalance.sub(before.platformPoolBalance).toString()).to.equal(
+      UP_FRONT_FEE.sub(half).toString()
+    );
+
+    const events = await parseEvents(txSig);
+    const requested = events.find((e) => e.name === "deploymentFundsRequested");
+    expect(requested.data.rewardSplitBps.toNumber()).to.equal(5000);
+    expect(requested.data.rewardFeeAmount.toString()).to.equal(half.toString());
+    expect(requested.data.platformFeeAmount.toString()).to.equal(UP_FRONT_FEE.sub(half).toString());
+  });
+
+  it("Should clamp an override to the admin bounds", async () => {
+    const txSig = await createRequest(10000, 9000);
+
+    const events = await parseEvents(txSig);
+    const requested = events.find((e) => e.name === "deploymentFundsRequested");
+    expect(requested.data.rewardSplitBps.toNumber()).to.equal(9000);
+  });
+
+  it("Should preview the same breakdown via estimate_deploy_cost", async () => {
+    const estimate = await program.methods
+      .estimateDeployCost(MONTHLY_FEE, 1, SERVICE_FEE, DEPLOYMENT_COST, new BN(5000))
+      .accounts({ treasuryPool: treasuryPoolPda, config: null })
+      .view();
+
+    // Matches what createRequest(5000, 5000) transfers to each pool
+    const half = UP_FRONT_FEE.divn(2);
+    expect(estimate.rewardSplitBps.toNumber()).to.equal(5000);
+    expect(estimate.rewardFeeAmount.toString()).to.equal(MONTHLY_FEE.add(half).toString());
+    expect(estimate.platformFeeAmount.toString()).to.equal(UP_FRONT_FEE.sub(half).toString());
+    expect(estimate.totalPayment.toString()).to.equal(UP_FRONT_FEE.add(MONTHLY_FEE).toString());
+  });
+});
@@ -0,0 +1,166 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+import { provider, program, admin, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+describe("SPL Token Deposits", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let tokenTreasuryPda: PublicKey;
+  let backerStakePda: PublicKey;
+
+  // Token accounts
+  let mint: PublicKey;
+  let otherMint: PublicKey;
+  let vault: PublicKey;
+  let adminTokenAccount: PublicKey;
+  let backerTokenAccount: PublicKey;
+  let backerOtherTokenAccount: PublicKey;
+
+  const DECIMALS = 6;
+  const STAKE_AMOUNT = new BN(100_000_000); // 100 tokens
+  const REWARD_AMOUNT = new BN(5_000_000); // 5 tokens
+
+  async function tokenBalance(account: PublicKey): Promise<bigint> {
+    return (await getAccount(provider.connection, account)).amount;
+  }
+
+  function stakeAccounts(tokenAccount: PublicKey) {
+    return {
+      treasuryPool: treasuryPoolPda,
+      tokenTreasury: tokenTreasuryPda,
+      mint,
+      vault,
+      lenderStake: backerStakePda,
+      lenderTokenAccount: tokenAccount,
+      lender: backer.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 2 * LAMPORTS_PER_SOL);
+
+    await initializePool();
+
+    // Stand-in for USDC, plus a second mint the treasury must refuse
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, DECIMALS);
+    otherMint = await createMint(provider.connection, admin, admin.publicKey, null, DECIMALS);
+
+    [tokenTreasuryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_treasury"), mint.toBuffer()],
+      program.programId
+    );
+    [backerStakePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_stake"), mint.toBuffer(), backer.publicKey.toBuffer()],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mint, treasuryPoolPda, true);
+
+    adminTokenAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, admin.publicKey)).address;
+    backerTokenAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, admin, mint, backer.publicKey)).address;
+    backerOtherTokenAccount = (await getOrCreateAssociatedTokenAccount(provider.connection, admin, otherMint, backer.publicKey)).address;
+
+    await mintTo(provider.connection, admin, mint, adminTokenAccount, admin, BigInt(REWARD_AMOUNT.toString()));
+    await mintTo(provider.connection, admin, mint, backerTokenAccount, admin, BigInt(STAKE_AMOUNT.toString()));
+    await mintTo(provider.connection, admin, otherMint, backerOtherTokenAccount, admin, BigInt(STAKE_AMOUNT.toString()));
+
+    await program.methods
+      .initializeTokenTreasury()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        tokenTreasury: tokenTreasuryPda,
+        mint,
+        vault,
+        admin: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  it("Should record the configured mint and vault", async () => {
+    const tokenTreasury = await program.account.tokenTreasury.fetch(tokenTreasuryPda);
+    expect(tokenTreasury.mint.toBase58()).to.equal(mint.toBase58());
+    expect(tokenTreasury.vault.toBase58()).to.equal(vault.toBase58());
+    expect(tokenTreasury.decimals).to.equal(DECIMALS);
+    expect(tokenTreasury.totalDeposited.toNumber()).to.equal(0);
+  });
+
+  it("Should reject deposits from a token account of another mint", async () => {
+    try {
+      await program.methods
+        .stakeSpl(STAKE_AMOUNT)
+        .accounts({ ...stakeAccounts(backerOtherTokenAccount), systemProgram: SystemProgram.programId })
+        .signers([backer])
+        .rpc();
+      expect.fail("Should have thrown InvalidMint");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidMint");
+    }
+  });
+
+  it("Should stake, earn, claim and unstake SPL tokens", async () => {
+    await program.methods
+      .stakeSpl(STAKE_AMOUNT)
+      .accounts({ ...stakeAccounts(backerTokenAccount), systemProgram: SystemProgram.programId })
+      .signers([backer])
+      .rpc();
+
+    expect((await tokenBalance(vault)).toString()).to.equal(STAKE_AMOUNT.toString());
+    let stake = await program.account.backerDeposit.fetch(backerStakePda);
+    expect(stake.depositedAmount.toString()).to.equal(STAKE_AMOUNT.toString());
+
+    await program.methods
+      .fundSplRewards(REWARD_AMOUNT)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        tokenTreasury: tokenTreasuryPda,
+        mint,
+        vault,
+        adminTokenAccount,
+        admin: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    // Sole depositor earns all funded rewards
+    await program.methods
+      .claimRewardsSpl()
+      .accounts(stakeAccounts(backerTokenAccount))
+      .signers([backer])
+      .rpc();
+    expect((await tokenBalance(backerTokenAccount)).toString()).to.equal(REWARD_AMOUNT.toString());
+
+    await program.methods
+      .unstakeSpl(STAKE_AMOUNT)
+      .accounts(stakeAccounts(backerTokenAccount))
+      .signers([backer])
+      .rpc();
+
+    expect((await tokenBalance(backerTokenAccount)).toString()).to.equal(STAKE_AMOUNT.add(REWARD_AMOUNT).toString());
+    expect((await tokenBalance(vault)).toString()).to.equal("0");
+
+    stake = await program.account.backerDeposit.fetch(backerStakePda);
+    expect(stake.depositedAmount.toNumber()).to.equal(0);
+    expect(stake.claimedTotal.toString()).to.equal(REWARD_AMOUNT.toString());
+
+    const tokenTreasury = await program.account.tokenTreasury.fetch(tokenTreasuryPda);
+    expect(tokenTreasury.totalDeposited.toNumber()).to.equal(0);
+    expect(tokenTreasury.rewardPoolBalance.toNumber()).to.equal(0);
+  });
+});
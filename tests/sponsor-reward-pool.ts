@@ -0,0 +1,128 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Reward Pool Sponsorship", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+  const sponsor = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const SPONSOR_AMOUNT = new BN(0.5 * LAMPORTS_PER_SOL);
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(sponsor.publicKey, 2 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(new BN(5 * LAMPORTS_PER_SOL), new BN(0))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should credit sponsored SOL to backers through reward_per_share", async () => {
+    const PRECISION = new BN("1000000000000");
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const rewardPoolBefore = await provider.connection.getBalance(rewardPoolPda);
+
+    await program.methods
+      .sponsorRewardPool(SPONSOR_AMOUNT)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        sponsor: sponsor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sponsor])
+      .rpc();
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const rewardPoolAfter = await provider.connection.getBalance(rewardPoolPda);
+
+    expect(rewardPoolAfter - rewardPoolBefore).to.equal(SPONSOR_AMOUNT.toNumber());
+    const delta = SPONSOR_AMOUNT.mul(PRECISION).div(poolBefore.totalDeposited);
+    expect(poolAfter.rewardPerShare.sub(poolBefore.rewardPerShare).toString()).to.equal(delta.toString());
+    // Only what reward_per_share can pay out is credited; the truncated remainder is dust
+    const distributed = delta.mul(poolBefore.totalDeposited).div(PRECISION);
+    expect(poolAfter.rewardPoolBalance.sub(poolBefore.rewardPoolBalance).toString()).to.equal(
+      distributed.toString()
+    );
+    expect(poolAfter.undistributedDust.sub(poolBefore.undistributedDust).toString()).to.equal(
+      SPONSOR_AMOUNT.sub(distributed).toString()
+    );
+    // Not developer fee income
+    expect(poolAfter.platformPoolBalance.toString()).to.equal(poolBefore.platformPoolBalance.toString());
+  });
+
+  it("Should reject a zero sponsorship", async () => {
+    try {
+      await program.methods
+        .sponsorRewardPool(new BN(0))
+        .accounts({
+          treasuryPool: treasuryPoolPda,
+          rewardPool: rewardPoolPda,
+          sponsor: sponsor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sponsor])
+        .rpc();
+      expect.fail("Should have thrown InvalidAmount");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should sweep truncation dust to the platform pool", async () => {
+    // A single lamport spread over the whole pool cannot be paid out exactly
+    await program.methods
+      .sponsorRewardPool(new BN(1))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        sponsor: sponsor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([sponsor])
+      .rpc();
+
+    const poolBefore = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const platformLamportsBefore = await provider.connection.getBalance(platformPoolPda);
+    expect(poolBefore.undistributedDust.toNumber()).to.be.greaterThan(0);
+
+    await program.methods
+      .sweepDust()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        admin: admin.publicKey,
+      })
+      .signers([admin])
+      .rpc();
+
+    const poolAfter = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const platformLamportsAfter = await provider.connection.getBalance(platformPoolPda);
+    expect(poolAfter.undistributedDust.toNumber()).to.equal(0);
+    expect(platformLamportsAfter - platformLamportsBefore).to.equal(poolBefore.undistributedDust.toNumber());
+    expect(poolAfter.platformPoolBalance.sub(poolBefore.platformPoolBalance).toString()).to.equal(
+      poolBefore.undistributedDust.toString()
+    );
+  });
+});
@@ -0,0 +1,127 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL, Transaction } from "@solana/web3.js";
+import { provider, program, admin, treasuryPoolPda, rewardPoolPda, platformPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+import * as crypto from "crypto";
+
+describe("Subscription Credit", () => {
+  // Test accounts
+  const developer = Keypair.generate();
+  const support = Keypair.generate();
+
+  // PDAs
+  let userStatsPda: PublicKey;
+  let deployRequestPda: PublicKey;
+
+  const programHash = crypto.randomBytes(32);
+  const DEPLOYMENT_COST = new BN(1 * LAMPORTS_PER_SOL);
+  const SERVICE_FEE = new BN(0.1 * LAMPORTS_PER_SOL);
+  const MONTHLY_FEE = DEPLOYMENT_COST.divn(100);
+
+  async function transfer(from: Keypair, to: PublicKey, lamports: number) {
+    const tx = new Transaction().add(
+      SystemProgram.transfer({ fromPubkey: from.publicKey, toPubkey: to, lamports })
+    );
+    await provider.sendAndConfirm(tx, [from]);
+  }
+
+  function grantCredit(months: number, signer: Keypair = admin) {
+    return program.methods
+      .grantSubscriptionCredit(Array.from(programHash), months)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        deployRequest: deployRequestPda,
+        admin: signer.publicKey,
+      })
+      .signers([signer]);
+  }
+
+  async function createRequest() {
+    await transfer(developer, rewardPoolPda, SERVICE_FEE.add(MONTHLY_FEE).toNumber());
+    await transfer(developer, platformPoolPda, DEPLOYMENT_COST.divn(1000).toNumber());
+
+    await program.methods
+      .createDeployRequest(Array.from(programHash), SERVICE_FEE, MONTHLY_FEE, 1, DEPLOYMENT_COST, null)
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        platformPool: platformPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+        admin: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  }
+
+  before(async () => {
+    await airdrop(developer.publicKey, 5 * LAMPORTS_PER_SOL);
+    await airdrop(support.publicKey, 1 * LAMPORTS_PER_SOL);
+
+    [userStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stats"), developer.publicKey.toBuffer()],
+      program.programId
+    );
+    [deployRequestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("deploy_request"), developer.publicKey.toBuffer(), programHash],
+      program.programId
+    );
+
+    await initializePool();
+
+    await createRequest();
+  });
+
+  it("Should extend the subscription for free and emit SubscriptionCredited", async () => {
+    const before = await program.account.deployRequest.fetch(deployRequestPda);
+    const rewardPoolBefore = await provider.connection.getBalance(rewardPoolPda);
+
+    const { events } = await grantCredit(2).simulate();
+    const credited = events.find((e) => e.name === "subscriptionCredited");
+    expect(credited.data.months).to.equal(2);
+    expect(credited.data.admin.toString()).to.equal(admin.publicKey.toString());
+
+    await grantCredit(2).rpc();
+
+    const after = await program.account.deployRequest.fetch(deployRequestPda);
+    expect(after.subscriptionPaidUntil.sub(before.subscriptionPaidUntil).toNumber()).to.equal(
+      2 * 30 * 24 * 60 * 60
+    );
+    expect(after.creditedMonths).to.equal(before.creditedMonths + 2);
+    // No lamports move and nothing is escrowed for free months
+    expect(after.prepaidBalance.toString()).to.equal(before.prepaidBalance.toString());
+    expect(await provider.connection.getBalance(rewardPoolPda)).to.equal(rewardPoolBefore);
+  });
+
+  it("Should reject a non-admin signer", async () => {
+    try {
+      await grantCredit(1, support).rpc();
+      expect.fail("Should have thrown Unauthorized");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Should reject crediting a cancelled request", async () => {
+    await program.methods
+      .cancelDeployRequest(Array.from(programHash))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        rewardPool: rewardPoolPda,
+        deployRequest: deployRequestPda,
+        userStats: userStatsPda,
+        developer: developer.publicKey,
+      })
+      .signers([developer])
+      .rpc();
+
+    try {
+      await grantCredit(1).rpc();
+      expect.fail("Should have thrown InvalidRequestStatus");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidRequestStatus");
+    }
+  });
+});
@@ -0,0 +1,69 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { program, treasuryPoolPda, airdrop, initializePool } from "./helpers/fixture";
+import { expect } from "chai";
+
+describe("Voting Power View", () => {
+  // Test accounts
+  const backer = Keypair.generate();
+
+  // PDAs
+  let backerDepositPda: PublicKey;
+
+  const STAKE_AMOUNT = new BN(5 * LAMPORTS_PER_SOL);
+  const LOCK_PERIOD = 30 * 24 * 60 * 60;
+
+  async function getVotingPower() {
+    return program.methods
+      .getVotingPower()
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+      })
+      .view();
+  }
+
+  before(async () => {
+    await airdrop(backer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    [backerDepositPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lender_stake"), backer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializePool();
+
+    await program.methods
+      .stakeSol(STAKE_AMOUNT, new BN(LOCK_PERIOD))
+      .accounts({
+        treasuryPool: treasuryPoolPda,
+        treasuryPda: treasuryPoolPda,
+        lenderStake: backerDepositPda,
+        lender: backer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([backer])
+      .rpc();
+  });
+
+  it("Should return the backer deposit and the pool-wide denominator", async () => {
+    const view = await getVotingPower();
+
+    const pool = await program.account.treasuryPool.fetch(treasuryPoolPda);
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    expect(view.depositedAmount.toString()).to.equal(STAKE_AMOUNT.toString());
+    expect(view.depositedAmount.toString()).to.equal(deposit.depositedAmount.toString());
+    expect(view.totalDeposited.toString()).to.equal(pool.totalDeposited.toString());
+    expect(view.totalDeposited.gte(view.depositedAmount)).to.equal(true);
+  });
+
+  it("Should report the lock state of the deposit", async () => {
+    const view = await getVotingPower();
+    const deposit = await program.account.backerDeposit.fetch(backerDepositPda);
+
+    expect(view.isLocked).to.equal(true);
+    expect(view.lockedUntil.toString()).to.equal(deposit.lockedUntil.toString());
+  });
+});